@@ -0,0 +1,354 @@
+use crate::error::ContractError;
+use crate::msg::{DepositMsg, ExecuteMsg, InstantiateMsg, OffersResponse, QueryMsg};
+use crate::state::{offers, Asset, Deposit, Offer, OFFER_COUNT};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, WasmMsg,
+};
+use cw1155::msg::Cw1155ExecuteMsg;
+use cw1155::receiver::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw-nft-swap";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateOffer {
+            counterparty,
+            offered,
+            requested,
+            expires,
+        } => execute_create_offer(deps, env, info, counterparty, offered, requested, expires),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => {
+            // the sender is the cw721 contract, the NFT is already owned by this contract
+            let asset = Asset::Cw721 {
+                collection: info.sender,
+                token_id,
+            };
+            let depositor = deps.api.addr_validate(&sender)?;
+            execute_deposit(deps, env, depositor, vec![asset], msg)
+        }
+        ExecuteMsg::Receive(Cw1155ReceiveMsg {
+            operator,
+            from,
+            token_id,
+            amount,
+            msg,
+        }) => {
+            let asset = Asset::Cw1155 {
+                collection: info.sender,
+                token_id,
+                amount,
+            };
+            let depositor = deps.api.addr_validate(&from.unwrap_or(operator))?;
+            execute_deposit(deps, env, depositor, vec![asset], msg)
+        }
+        ExecuteMsg::BatchReceive(Cw1155BatchReceiveMsg {
+            operator,
+            from,
+            batch,
+            msg,
+        }) => {
+            let assets = batch
+                .into_iter()
+                .map(|token| Asset::Cw1155 {
+                    collection: info.sender.clone(),
+                    token_id: token.token_id,
+                    amount: token.amount,
+                })
+                .collect();
+            let depositor = deps.api.addr_validate(&from.unwrap_or(operator))?;
+            execute_deposit(deps, env, depositor, assets, msg)
+        }
+        ExecuteMsg::Cancel { offer_id } => execute_cancel(deps, env, info, offer_id),
+    }
+}
+
+pub fn execute_create_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    counterparty: String,
+    offered: Vec<Asset>,
+    requested: Vec<Asset>,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let counterparty = deps.api.addr_validate(&counterparty)?;
+    if counterparty == info.sender {
+        return Err(ContractError::InvalidCounterparty {});
+    }
+    if offered.is_empty() || requested.is_empty() {
+        return Err(ContractError::NoAssets {});
+    }
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    let id = OFFER_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    OFFER_COUNT.save(deps.storage, &id)?;
+    let offer = Offer {
+        id,
+        creator: info.sender,
+        counterparty,
+        offered: validate_assets(deps.api, offered)?,
+        requested: validate_assets(deps.api, requested)?,
+        expires,
+    };
+    offers().save(deps.storage, id, &offer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_offer")
+        .add_attribute("offer_id", id.to_string())
+        .add_attribute("creator", offer.creator)
+        .add_attribute("counterparty", offer.counterparty)
+        .add_attribute("expires", offer.expires.to_string()))
+}
+
+fn validate_assets(api: &dyn Api, assets: Vec<Asset>) -> Result<Vec<Deposit>, ContractError> {
+    assets
+        .into_iter()
+        .map(|asset| {
+            let asset = match asset {
+                Asset::Cw721 {
+                    collection,
+                    token_id,
+                } => Asset::Cw721 {
+                    collection: api.addr_validate(collection.as_str())?,
+                    token_id,
+                },
+                Asset::Cw1155 {
+                    collection,
+                    token_id,
+                    amount,
+                } => {
+                    if amount.is_zero() {
+                        return Err(ContractError::InvalidAmount {});
+                    }
+                    Asset::Cw1155 {
+                        collection: api.addr_validate(collection.as_str())?,
+                        token_id,
+                        amount,
+                    }
+                }
+            };
+            Ok(Deposit {
+                asset,
+                deposited: false,
+            })
+        })
+        .collect()
+}
+
+/// Deposits assets received from the depositor. Once all assets of both sides are deposited, the
+/// swap is settled.
+pub fn execute_deposit(
+    deps: DepsMut,
+    env: Env,
+    depositor: Addr,
+    assets: Vec<Asset>,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let DepositMsg { offer_id } = from_json(&msg)?;
+    let mut offer = offers().load(deps.storage, offer_id)?;
+    if offer.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    let deposits = if depositor == offer.creator {
+        &mut offer.offered
+    } else if depositor == offer.counterparty {
+        &mut offer.requested
+    } else {
+        return Err(ContractError::Unauthorized {});
+    };
+    // each asset must match exactly one asset of the depositor's side, not yet deposited
+    for asset in assets {
+        let deposit = deposits
+            .iter_mut()
+            .find(|deposit| !deposit.deposited && deposit.asset == asset)
+            .ok_or(ContractError::UnexpectedAsset {})?;
+        deposit.deposited = true;
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("depositor", depositor);
+    let all_deposited = offer
+        .offered
+        .iter()
+        .chain(offer.requested.iter())
+        .all(|deposit| deposit.deposited);
+    if !all_deposited {
+        offers().save(deps.storage, offer_id, &offer)?;
+        return Ok(res);
+    }
+
+    // settle swap
+    offers().remove(deps.storage, offer_id)?;
+    let messages = transfer_msgs(&offer.offered, &offer.counterparty)?
+        .into_iter()
+        .chain(transfer_msgs(&offer.requested, &offer.creator)?);
+    Ok(res.add_messages(messages).add_attribute("settled", "true"))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> Result<Response, ContractError> {
+    let offer = offers().load(deps.storage, offer_id)?;
+    if info.sender != offer.creator
+        && info.sender != offer.counterparty
+        && !offer.expires.is_expired(&env.block)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    offers().remove(deps.storage, offer_id)?;
+
+    // refund deposits
+    let messages = transfer_msgs(&offer.offered, &offer.creator)?
+        .into_iter()
+        .chain(transfer_msgs(&offer.requested, &offer.counterparty)?);
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "cancel")
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+/// Transfers all deposited assets to the recipient.
+fn transfer_msgs(deposits: &[Deposit], recipient: &Addr) -> StdResult<Vec<CosmosMsg>> {
+    deposits
+        .iter()
+        .filter(|deposit| deposit.deposited)
+        .map(|deposit| match &deposit.asset {
+            Asset::Cw721 {
+                collection,
+                token_id,
+            } => DefaultCw721Helper::new(collection.clone()).call(Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.clone(),
+            }),
+            Asset::Cw1155 {
+                collection,
+                token_id,
+                amount,
+            } => Ok(WasmMsg::Execute {
+                contract_addr: collection.to_string(),
+                msg: to_json_binary(&Cw1155ExecuteMsg::<Empty, Empty>::Send {
+                    from: None,
+                    to: recipient.to_string(),
+                    token_id: token_id.clone(),
+                    amount: *amount,
+                    msg: None,
+                })?,
+                funds: vec![],
+            }
+            .into()),
+        })
+        .collect()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Offer { offer_id } => to_json_binary(&query_offer(deps, offer_id)?),
+        QueryMsg::OffersByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_json_binary(&query_offers_by_creator(deps, creator, start_after, limit)?),
+        QueryMsg::OffersByCounterparty {
+            counterparty,
+            start_after,
+            limit,
+        } => to_json_binary(&query_offers_by_counterparty(
+            deps,
+            counterparty,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+pub fn query_offer(deps: Deps, offer_id: u64) -> StdResult<Option<Offer>> {
+    offers().may_load(deps.storage, offer_id)
+}
+
+pub fn query_offers_by_creator(
+    deps: Deps,
+    creator: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let creator = deps.api.addr_validate(&creator)?;
+    let offers = offers()
+        .idx
+        .creator
+        .prefix(creator)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, offer)| offer))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OffersResponse { offers })
+}
+
+pub fn query_offers_by_counterparty(
+    deps: Deps,
+    counterparty: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let counterparty = deps.api.addr_validate(&counterparty)?;
+    let offers = offers()
+        .idx
+        .counterparty
+        .prefix(counterparty)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, offer)| offer))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OffersResponse { offers })
+}
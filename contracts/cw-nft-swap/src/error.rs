@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Offer and requested assets must not be empty")]
+    NoAssets {},
+
+    #[error("Amount must be greater than zero")]
+    InvalidAmount {},
+
+    #[error("Creator can't be the counterparty")]
+    InvalidCounterparty {},
+
+    #[error("Offer expired")]
+    Expired {},
+
+    #[error("Asset is not part of the offer or already deposited")]
+    UnexpectedAsset {},
+}
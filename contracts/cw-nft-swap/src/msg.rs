@@ -0,0 +1,65 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw1155::receiver::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw_utils::Expiration;
+
+use crate::state::{Asset, Offer};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Offer a swap to the counterparty. Assets are deposited afterwards by sending them to this
+    /// contract with a `DepositMsg`.
+    CreateOffer {
+        counterparty: String,
+        offered: Vec<Asset>,
+        requested: Vec<Asset>,
+        expires: Expiration,
+    },
+    /// Deposit a cw721 token, via cw721 `SendNft` with a `DepositMsg`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Deposit cw1155 tokens, via cw1155 `Send` with a `DepositMsg`.
+    Receive(Cw1155ReceiveMsg),
+    /// Deposit cw1155 tokens, via cw1155 `SendBatch` with a `DepositMsg`.
+    BatchReceive(Cw1155BatchReceiveMsg),
+    /// The creator or the counterparty can cancel an offer, anyone can once it is expired. All
+    /// deposited assets are refunded.
+    Cancel { offer_id: u64 },
+}
+
+/// Message passed along a cw721 or cw1155 send. Assets sent by the creator are deposited as
+/// offered, assets sent by the counterparty as requested. The swap is settled as soon as all
+/// assets are deposited.
+#[cw_serde]
+pub struct DepositMsg {
+    pub offer_id: u64,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the offer with the given id, if any.
+    #[returns(Option<Offer>)]
+    Offer { offer_id: u64 },
+    /// Returns offers of a creator, ordered by id.
+    #[returns(OffersResponse)]
+    OffersByCreator {
+        creator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns offers to a counterparty, ordered by id.
+    #[returns(OffersResponse)]
+    OffersByCounterparty {
+        counterparty: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct OffersResponse {
+    pub offers: Vec<Offer>,
+}
@@ -0,0 +1,68 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub enum Asset {
+    Cw721 {
+        collection: Addr,
+        token_id: String,
+    },
+    Cw1155 {
+        collection: Addr,
+        token_id: String,
+        amount: Uint128,
+    },
+}
+
+/// An asset of an offer, and whether it has been deposited in the contract.
+#[cw_serde]
+pub struct Deposit {
+    pub asset: Asset,
+    pub deposited: bool,
+}
+
+/// A swap offered by the creator to the counterparty. The contract holds custody of all deposited
+/// assets, until both sides are deposited and the swap is settled, or the offer is cancelled.
+#[cw_serde]
+pub struct Offer {
+    pub id: u64,
+    pub creator: Addr,
+    pub counterparty: Addr,
+    /// Assets deposited by the creator.
+    pub offered: Vec<Deposit>,
+    /// Assets deposited by the counterparty.
+    pub requested: Vec<Deposit>,
+    pub expires: Expiration,
+}
+
+pub const OFFER_COUNT: Item<u64> = Item::new("offer_count");
+
+pub struct OfferIndexes<'a> {
+    pub creator: MultiIndex<'a, Addr, Offer, u64>,
+    pub counterparty: MultiIndex<'a, Addr, Offer, u64>,
+}
+
+impl<'a> IndexList<Offer> for OfferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.creator, &self.counterparty];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn offers<'a>() -> IndexedMap<'a, u64, Offer, OfferIndexes<'a>> {
+    let indexes = OfferIndexes {
+        creator: MultiIndex::new(
+            |_, offer| offer.creator.clone(),
+            "offers",
+            "offers__creator",
+        ),
+        counterparty: MultiIndex::new(
+            |_, offer| offer.counterparty.clone(),
+            "offers",
+            "offers__counterparty",
+        ),
+    };
+    IndexedMap::new("offers", indexes)
+}
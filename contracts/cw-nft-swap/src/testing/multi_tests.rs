@@ -0,0 +1,480 @@
+use crate::{
+    contract::{execute, instantiate, query},
+    msg::{DepositMsg, ExecuteMsg, InstantiateMsg, OffersResponse, QueryMsg},
+    state::{Asset, Deposit, Offer},
+    ContractError,
+};
+use cosmwasm_std::{to_json_binary, Addr, Empty, Uint128};
+use cw1155::msg::{Cw1155InstantiateMsg, Cw1155MintMsg, TokenAmount};
+use cw1155_base::Cw1155BaseExecuteMsg;
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_nfts_multi_test::{
+    cw1155_base_contract, instantiate_cw721, query_cw1155_balance, query_owner,
+};
+use cw_utils::Expiration;
+
+struct Contracts {
+    cw721: Addr,
+    cw1155: Addr,
+    swap: Addr,
+}
+
+struct Users {
+    alice: Addr,
+    bob: Addr,
+    carol: Addr,
+}
+
+fn swap_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn users(app: &App) -> Users {
+    Users {
+        alice: app.api().addr_make("alice"),
+        bob: app.api().addr_make("bob"),
+        carol: app.api().addr_make("carol"),
+    }
+}
+
+/// Creates a cw721 collection with tokens "1" owned by alice, "2" owned by bob and "3" owned by
+/// carol, a cw1155 collection with 10 "gold" and 5 "silver" owned by bob, and the swap contract.
+fn setup(app: &mut App) -> Contracts {
+    let Users { alice, bob, carol } = users(app);
+    let minter = app.api().addr_make("minter");
+
+    let cw721 = instantiate_cw721(
+        app,
+        &minter,
+        None,
+        &[("1", &alice), ("2", &bob), ("3", &carol)],
+    );
+
+    let cw1155_code_id = app.store_code(cw1155_base_contract());
+    let cw1155 = app
+        .instantiate_contract(
+            cw1155_code_id,
+            minter.clone(),
+            &Cw1155InstantiateMsg {
+                name: "tokens".to_string(),
+                symbol: "TOKENS".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                default_uri: None,
+            },
+            &[],
+            "cw1155",
+            None,
+        )
+        .unwrap();
+    for (token_id, amount) in [("gold", 10), ("silver", 5)] {
+        app.execute_contract(
+            minter.clone(),
+            cw1155.clone(),
+            &Cw1155BaseExecuteMsg::Mint {
+                recipient: bob.to_string(),
+                msg: Cw1155MintMsg {
+                    token_id: token_id.to_string(),
+                    amount: Uint128::new(amount),
+                    token_uri: None,
+                    extension: None,
+                    config: None,
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let swap_code_id = app.store_code(swap_contract());
+    let swap = app
+        .instantiate_contract(swap_code_id, minter, &InstantiateMsg {}, &[], "swap", None)
+        .unwrap();
+
+    Contracts {
+        cw721,
+        cw1155,
+        swap,
+    }
+}
+
+fn cw721_asset(contracts: &Contracts, token_id: &str) -> Asset {
+    Asset::Cw721 {
+        collection: contracts.cw721.clone(),
+        token_id: token_id.to_string(),
+    }
+}
+
+fn cw1155_asset(contracts: &Contracts, token_id: &str, amount: u128) -> Asset {
+    Asset::Cw1155 {
+        collection: contracts.cw1155.clone(),
+        token_id: token_id.to_string(),
+        amount: Uint128::new(amount),
+    }
+}
+
+fn create_offer(
+    app: &mut App,
+    contracts: &Contracts,
+    creator: &Addr,
+    counterparty: &Addr,
+    offered: Vec<Asset>,
+    requested: Vec<Asset>,
+    expires: Expiration,
+) -> Result<u64, ContractError> {
+    app.execute_contract(
+        creator.clone(),
+        contracts.swap.clone(),
+        &ExecuteMsg::CreateOffer {
+            counterparty: counterparty.to_string(),
+            offered,
+            requested,
+            expires,
+        },
+        &[],
+    )
+    .map(|_| {
+        let res: OffersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &contracts.swap,
+                &QueryMsg::OffersByCreator {
+                    creator: creator.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.offers.last().unwrap().id
+    })
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn send_nft(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    token_id: &str,
+    offer_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.cw721.clone(),
+        &Cw721ExecuteMsg::SendNft {
+            contract: contracts.swap.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&DepositMsg { offer_id }).unwrap(),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn send_cw1155(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    batch: Vec<(&str, u128)>,
+    offer_id: u64,
+) -> Result<(), ContractError> {
+    let msg = Some(to_json_binary(&DepositMsg { offer_id }).unwrap());
+    let msg = match batch[..] {
+        [(token_id, amount)] => Cw1155BaseExecuteMsg::Send {
+            from: None,
+            to: contracts.swap.to_string(),
+            token_id: token_id.to_string(),
+            amount: Uint128::new(amount),
+            msg,
+        },
+        _ => Cw1155BaseExecuteMsg::SendBatch {
+            from: None,
+            to: contracts.swap.to_string(),
+            batch: batch
+                .into_iter()
+                .map(|(token_id, amount)| TokenAmount {
+                    token_id: token_id.to_string(),
+                    amount: Uint128::new(amount),
+                })
+                .collect(),
+            msg,
+        },
+    };
+    app.execute_contract(sender.clone(), contracts.cw1155.clone(), &msg, &[])
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+}
+
+fn cancel(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    offer_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.swap.clone(),
+        &ExecuteMsg::Cancel { offer_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn query_offer(app: &App, contracts: &Contracts, offer_id: u64) -> Option<Offer> {
+    app.wrap()
+        .query_wasm_smart(&contracts.swap, &QueryMsg::Offer { offer_id })
+        .unwrap()
+}
+
+#[test]
+fn test_swap_cw721_for_cw1155() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users { alice, bob, .. } = users(&app);
+
+    let offer_id = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![
+            cw1155_asset(&contracts, "gold", 10),
+            cw1155_asset(&contracts, "silver", 5),
+        ],
+        Expiration::Never {},
+    )
+    .unwrap();
+    send_nft(&mut app, &contracts, &alice, "1", offer_id).unwrap();
+    assert_eq!(
+        query_offer(&app, &contracts, offer_id).unwrap(),
+        Offer {
+            id: offer_id,
+            creator: alice.clone(),
+            counterparty: bob.clone(),
+            offered: vec![Deposit {
+                asset: cw721_asset(&contracts, "1"),
+                deposited: true,
+            }],
+            requested: vec![
+                Deposit {
+                    asset: cw1155_asset(&contracts, "gold", 10),
+                    deposited: false,
+                },
+                Deposit {
+                    asset: cw1155_asset(&contracts, "silver", 5),
+                    deposited: false,
+                },
+            ],
+            expires: Expiration::Never {},
+        }
+    );
+
+    // amounts must match the requested assets
+    let err = send_cw1155(&mut app, &contracts, &bob, vec![("silver", 3)], offer_id).unwrap_err();
+    assert_eq!(err, ContractError::UnexpectedAsset {});
+
+    // last deposit settles the swap
+    send_cw1155(
+        &mut app,
+        &contracts,
+        &bob,
+        vec![("gold", 10), ("silver", 5)],
+        offer_id,
+    )
+    .unwrap();
+    assert_eq!(query_offer(&app, &contracts, offer_id), None);
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), bob);
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.cw1155, &alice, "gold"),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.cw1155, &alice, "silver"),
+        Uint128::new(5)
+    );
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.cw1155, &contracts.swap, "gold"),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_swap_cw721_for_cw721() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users { alice, bob, carol } = users(&app);
+
+    let offer_id = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw721_asset(&contracts, "2")],
+        Expiration::Never {},
+    )
+    .unwrap();
+
+    // only the creator and the counterparty can deposit
+    let err = send_nft(&mut app, &contracts, &carol, "3", offer_id).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the counterparty may deposit first
+    send_nft(&mut app, &contracts, &bob, "2", offer_id).unwrap();
+    send_nft(&mut app, &contracts, &alice, "1", offer_id).unwrap();
+    assert_eq!(query_offer(&app, &contracts, offer_id), None);
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), bob);
+    assert_eq!(query_owner(&app, &contracts.cw721, "2"), alice);
+}
+
+#[test]
+fn test_cancel() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users { alice, bob, carol } = users(&app);
+
+    // creator cancels
+    let offer_id = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw721_asset(&contracts, "2")],
+        Expiration::Never {},
+    )
+    .unwrap();
+    send_nft(&mut app, &contracts, &alice, "1", offer_id).unwrap();
+    let err = cancel(&mut app, &contracts, &carol, offer_id).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    cancel(&mut app, &contracts, &alice, offer_id).unwrap();
+    assert_eq!(query_offer(&app, &contracts, offer_id), None);
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), alice);
+
+    // anyone can cancel an expired offer, refunding both sides
+    let expires = Expiration::AtHeight(app.block_info().height + 10);
+    let offer_id = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![
+            cw1155_asset(&contracts, "gold", 10),
+            cw1155_asset(&contracts, "silver", 5),
+        ],
+        expires,
+    )
+    .unwrap();
+    send_nft(&mut app, &contracts, &alice, "1", offer_id).unwrap();
+    send_cw1155(&mut app, &contracts, &bob, vec![("gold", 10)], offer_id).unwrap();
+    app.update_block(|block| block.height += 10);
+    let err = send_cw1155(&mut app, &contracts, &bob, vec![("silver", 5)], offer_id).unwrap_err();
+    assert_eq!(err, ContractError::Expired {});
+    cancel(&mut app, &contracts, &carol, offer_id).unwrap();
+    assert_eq!(query_offer(&app, &contracts, offer_id), None);
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), alice);
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.cw1155, &bob, "gold"),
+        Uint128::new(10)
+    );
+}
+
+#[test]
+fn test_create_offer() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users { alice, bob, .. } = users(&app);
+
+    let err = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &alice,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw721_asset(&contracts, "2")],
+        Expiration::Never {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCounterparty {});
+
+    let err = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![],
+        Expiration::Never {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoAssets {});
+
+    let err = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw1155_asset(&contracts, "gold", 0)],
+        Expiration::Never {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidAmount {});
+
+    let err = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw721_asset(&contracts, "2")],
+        Expiration::AtHeight(app.block_info().height),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Expired {});
+
+    let first = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw721_asset(&contracts, "2")],
+        Expiration::Never {},
+    )
+    .unwrap();
+    let second = create_offer(
+        &mut app,
+        &contracts,
+        &alice,
+        &bob,
+        vec![cw721_asset(&contracts, "1")],
+        vec![cw1155_asset(&contracts, "gold", 10)],
+        Expiration::Never {},
+    )
+    .unwrap();
+    assert_eq!((first, second), (1, 2));
+    let res: OffersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contracts.swap,
+            &QueryMsg::OffersByCounterparty {
+                counterparty: bob.to_string(),
+                start_after: Some(first),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.offers
+            .into_iter()
+            .map(|offer| offer.id)
+            .collect::<Vec<_>>(),
+        vec![second]
+    );
+}
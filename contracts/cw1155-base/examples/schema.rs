@@ -1,5 +1,5 @@
 use cosmwasm_schema::write_api;
-use cw1155::msg::Cw1155InstantiateMsg;
+use cw1155::msg::{Cw1155InstantiateMsg, Cw1155MigrateMsg};
 
 use cw1155_base::{Cw1155BaseExecuteMsg, Cw1155BaseQueryMsg};
 
@@ -8,5 +8,6 @@ fn main() {
         instantiate: Cw1155InstantiateMsg,
         execute: Cw1155BaseExecuteMsg,
         query: Cw1155BaseQueryMsg,
+        migrate: Cw1155MigrateMsg,
     }
 }
@@ -1,27 +1,35 @@
 #[cfg(test)]
 mod tests {
-    use crate::entry::{execute, instantiate, query};
-    use crate::{Cw1155BaseContract, Cw1155BaseExecuteMsg, Cw1155BaseQueryMsg};
+    use crate::entry::{execute, instantiate, migrate, query};
+    use crate::{Cw1155BaseConfig, Cw1155BaseContract, Cw1155BaseExecuteMsg, Cw1155BaseQueryMsg};
     use cosmwasm_schema::cw_serde;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
     use cosmwasm_std::{
-        coin, from_json, to_json_binary, wasm_execute, Addr, Binary, Empty, OverflowError,
-        Response, StdError, Uint128,
+        coin, from_json, to_json_binary, wasm_execute, Addr, Binary, CosmosMsg, Empty,
+        OverflowError, OwnedDeps, Response, StdError, Uint128, WasmMsg,
     };
     use cw1155::error::Cw1155ContractError;
     use cw1155::execute::Cw1155Execute;
     use cw1155::msg::{
-        ApprovedForAllResponse, Balance, BalanceResponse, BalancesResponse, Cw1155InstantiateMsg,
-        Cw1155MintMsg, Cw1155QueryMsg, DefaultBaseUriResponse, IsApprovedForAllResponse,
-        NumTokensResponse, OwnerToken, TokenAmount, TokenApproval, TokenApprovalResponse,
-        TokenInfoResponse, TokenUpdate,
+        AllTokenInfoResponse, ApprovedForAllResponse, Balance, BalanceResponse, BalancesResponse,
+        Cw1155InstantiateMsg, Cw1155MigrateMsg, Cw1155MintMsg, Cw1155QueryMsg,
+        DefaultBaseUriResponse, IsApprovedForAllResponse, NumTokensResponse, OwnerToken,
+        TokenAmount, TokenApproval, TokenApprovalResponse, TokenInfoResponse, TokenUpdate,
     };
     use cw1155::query::Cw1155Query;
-    use cw1155::receiver::Cw1155BatchReceiveMsg;
-    use cw721::msg::TokensResponse;
-    use cw721::Approval;
+    use cw1155::receiver::{Cw1155BatchReceiveMsg, ReceiverExecuteMsg};
+    use cw1155::state::TokenConfig;
+    use cw721::msg::{
+        CollectionExtensionMsg, CollectionInfoAndExtensionResponse, CollectionInfoMsg,
+        TokensResponse,
+    };
+    use cw721::state::{CollectionInfo, CREATOR, MINTER};
+    use cw721::{Approval, DefaultOptionalCollectionExtension};
     use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
-    use cw_ownable::OwnershipError;
+    use cw_ownable::{Ownership, OwnershipError};
+    use cw_storage_plus::{Item, Map};
     use cw_utils::Expiration;
 
     const USEI: &str = "usei";
@@ -52,6 +60,7 @@ mod tests {
                             amount: amount.into(),
                             token_uri,
                             extension: None,
+                            config: None,
                         },
                     },
                     vec![],
@@ -609,7 +618,9 @@ mod tests {
                 &Cw1155InstantiateMsg {
                     name: "cw1155 base contract".to_string(),
                     symbol: "CW1155".to_string(),
+                    collection_info_extension: None,
                     minter: None,
+                    creator: None,
                     default_uri,
                 },
                 &[],
@@ -963,7 +974,9 @@ mod tests {
         let msg = Cw1155InstantiateMsg {
             name: "name".to_string(),
             symbol: "symbol".to_string(),
+            collection_info_extension: None,
             minter: Some(minter.to_string()),
+            creator: None,
             default_uri: None,
         };
         let res = contract
@@ -991,12 +1004,14 @@ mod tests {
                             amount: 5u64.into(),
                             token_uri: None,
                             extension: None,
+                            config: None,
                         },
                         Cw1155MintMsg {
                             token_id: token2.clone(),
                             amount: 5u64.into(),
                             token_uri: None,
                             extension: None,
+                            config: None,
                         },
                     ],
                 },
@@ -1014,6 +1029,7 @@ mod tests {
                         amount: 1u64.into(),
                         token_uri: None,
                         extension: None,
+                        config: None,
                     }],
                 },
             )
@@ -1211,6 +1227,96 @@ mod tests {
         );
     }
 
+    #[test]
+    fn send_batch_single_batch_receive_callback() {
+        let contract = Cw1155BaseContract::default();
+        let receiver = String::from("receive_contract");
+        let minter = String::from("minter");
+        let user1 = String::from("user1");
+        let dummy_msg = to_json_binary("dummy").unwrap();
+
+        let mut deps = mock_dependencies();
+        let msg = Cw1155InstantiateMsg {
+            name: "name".to_string(),
+            symbol: "symbol".to_string(),
+            collection_info_extension: None,
+            minter: Some(minter.to_string()),
+            creator: None,
+            default_uri: None,
+        };
+        contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("operator", &[]),
+                msg,
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        let token_ids = ["token1", "token2", "token3"];
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                Cw1155BaseExecuteMsg::MintBatch {
+                    recipient: user1.clone(),
+                    msgs: token_ids
+                        .iter()
+                        .map(|token_id| Cw1155MintMsg {
+                            token_id: token_id.to_string(),
+                            amount: 5u64.into(),
+                            token_uri: None,
+                            extension: None,
+                            config: None,
+                        })
+                        .collect(),
+                },
+            )
+            .unwrap();
+
+        // sending several tokens with a msg triggers a single BatchReceive callback for all tokens
+        let batch: Vec<TokenAmount> = token_ids
+            .iter()
+            .map(|token_id| TokenAmount {
+                token_id: token_id.to_string(),
+                amount: 1u64.into(),
+            })
+            .collect();
+        let res = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(user1.as_ref(), &[]),
+                Cw1155BaseExecuteMsg::SendBatch {
+                    from: None,
+                    to: receiver.clone(),
+                    batch: batch.clone(),
+                    msg: Some(dummy_msg.clone()),
+                },
+            )
+            .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) = &res.messages[0].msg
+        else {
+            panic!("unexpected message: {:?}", res.messages[0].msg);
+        };
+        assert_eq!(contract_addr, &receiver);
+        assert_eq!(
+            from_json::<ReceiverExecuteMsg>(msg).unwrap(),
+            ReceiverExecuteMsg::BatchReceive(Cw1155BatchReceiveMsg {
+                operator: user1.clone(),
+                from: Some(user1),
+                batch,
+                msg: dummy_msg,
+            })
+        );
+    }
+
     #[test]
     fn check_queries() {
         let contract = Cw1155BaseContract::default();
@@ -1224,7 +1330,9 @@ mod tests {
         let msg = Cw1155InstantiateMsg {
             name: "name".to_string(),
             symbol: "symbol".to_string(),
+            collection_info_extension: None,
             minter: Some(minter.to_string()),
+            creator: None,
             default_uri: None,
         };
         let res = contract
@@ -1252,6 +1360,7 @@ mod tests {
                             amount: 1u64.into(),
                             token_uri: None,
                             extension: None,
+                            config: None,
                         },
                     },
                 )
@@ -1271,6 +1380,7 @@ mod tests {
                             amount: 1u64.into(),
                             token_uri: None,
                             extension: None,
+                            config: None,
                         },
                     },
                 )
@@ -1381,6 +1491,33 @@ mod tests {
             })
         );
 
+        assert_eq!(
+            contract.query(
+                deps.as_ref(),
+                mock_env(),
+                Cw1155QueryMsg::AllTokenInfo {
+                    owner: users[0].clone(),
+                    start_after: Some("token5".to_owned()),
+                    limit: Some(2),
+                },
+            ),
+            to_json_binary(
+                &tokens[6..8]
+                    .iter()
+                    .map(|token_id| AllTokenInfoResponse::<Option<Empty>> {
+                        token_id: token_id.clone(),
+                        balance: Uint128::new(1),
+                        info: TokenInfoResponse {
+                            token_uri: "".to_string(),
+                            extension: None,
+                            config: TokenConfig::default(),
+                            remaining_supply: None,
+                        },
+                    })
+                    .collect::<Vec<_>>()
+            )
+        );
+
         assert_eq!(
             contract.query(
                 deps.as_ref(),
@@ -1406,6 +1543,8 @@ mod tests {
             to_json_binary(&TokenInfoResponse::<Option<Empty>> {
                 token_uri: "".to_string(),
                 extension: None,
+                config: TokenConfig::default(),
+                remaining_supply: None,
             }),
         );
 
@@ -1473,7 +1612,9 @@ mod tests {
         let msg = Cw1155InstantiateMsg {
             name: "name".to_string(),
             symbol: "symbol".to_string(),
+            collection_info_extension: None,
             minter: Some(minter.to_string()),
+            creator: None,
             default_uri: None,
         };
         let res = contract
@@ -1500,6 +1641,7 @@ mod tests {
                         amount: 1u64.into(),
                         token_uri: None,
                         extension: None,
+                        config: None,
                     },
                 },
             )
@@ -1593,7 +1735,9 @@ mod tests {
         let msg = Cw1155InstantiateMsg {
             name: "name".to_string(),
             symbol: "symbol".to_string(),
+            collection_info_extension: None,
             minter: Some(minter.to_string()),
+            creator: None,
             default_uri: None,
         };
         let res = contract
@@ -1620,6 +1764,7 @@ mod tests {
                     amount: u128::MAX.into(),
                     token_uri: None,
                     extension: None,
+                    config: None,
                 },
             },
         );
@@ -1638,6 +1783,7 @@ mod tests {
                     amount: 1u128.into(),
                     token_uri: None,
                     extension: None,
+                    config: None,
                 },
             },
         );
@@ -1662,6 +1808,7 @@ mod tests {
                     amount: 1u128.into(),
                     token_uri: None,
                     extension: None,
+                    config: None,
                 },
             },
         );
@@ -1675,6 +1822,296 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn mint_token_config() {
+        let contract = Cw1155BaseContract::default();
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let user1 = String::from("user1");
+
+        let msg = Cw1155InstantiateMsg {
+            name: "name".to_string(),
+            symbol: "symbol".to_string(),
+            collection_info_extension: None,
+            minter: Some(minter.to_string()),
+            creator: None,
+            default_uri: None,
+        };
+        contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("operator", &[]),
+                msg,
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+        let mint = |deps: &mut MockDeps, token_id: &str, amount: u128, config| {
+            contract.execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                Cw1155BaseExecuteMsg::Mint {
+                    recipient: user1.clone(),
+                    msg: Cw1155MintMsg {
+                        token_id: token_id.to_string(),
+                        amount: amount.into(),
+                        token_uri: None,
+                        extension: None,
+                        config,
+                    },
+                },
+            )
+        };
+        let remaining_supply = |deps: &MockDeps, token_id: &str| {
+            let res: TokenInfoResponse<Option<Empty>> = from_json(
+                contract
+                    .query(
+                        deps.as_ref(),
+                        mock_env(),
+                        Cw1155QueryMsg::TokenInfo {
+                            token_id: token_id.to_string(),
+                        },
+                    )
+                    .unwrap(),
+            )
+            .unwrap();
+            res.remaining_supply
+        };
+
+        // unique NFT, only a single token can be minted
+        let unique = TokenConfig {
+            max_supply: None,
+            unique: true,
+            mintable: true,
+        };
+        mint(&mut deps, "unique", 1, Some(unique)).unwrap();
+        assert_eq!(remaining_supply(&deps, "unique"), Some(Uint128::zero()));
+        let err = mint(&mut deps, "unique", 1, None).unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155ContractError::MaxSupplyExceeded {
+                token_id: "unique".to_string(),
+                remaining: Uint128::zero(),
+                requested: Uint128::one(),
+            }
+        );
+
+        // max supply, later mints allowed up to the max supply
+        let capped = TokenConfig {
+            max_supply: Some(Uint128::new(10)),
+            unique: false,
+            mintable: true,
+        };
+        mint(&mut deps, "capped", 6, Some(capped)).unwrap();
+        assert_eq!(remaining_supply(&deps, "capped"), Some(Uint128::new(4)));
+        let err = mint(&mut deps, "capped", 5, None).unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155ContractError::MaxSupplyExceeded {
+                token_id: "capped".to_string(),
+                remaining: Uint128::new(4),
+                requested: Uint128::new(5),
+            }
+        );
+        mint(&mut deps, "capped", 4, None).unwrap();
+        assert_eq!(remaining_supply(&deps, "capped"), Some(Uint128::zero()));
+
+        // fixed supply, no later mints
+        let fixed = TokenConfig {
+            max_supply: None,
+            unique: false,
+            mintable: false,
+        };
+        mint(&mut deps, "fixed", 3, Some(fixed)).unwrap();
+        assert_eq!(remaining_supply(&deps, "fixed"), Some(Uint128::zero()));
+        let err = mint(&mut deps, "fixed", 1, None).unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155ContractError::MintingDisabled {
+                token_id: "fixed".to_string(),
+            }
+        );
+
+        // no config, unlimited supply
+        mint(&mut deps, "unlimited", 1, None).unwrap();
+        mint(&mut deps, "unlimited", 1, None).unwrap();
+        assert_eq!(remaining_supply(&deps, "unlimited"), None);
+    }
+
+    #[test]
+    fn minter_and_creator() {
+        let contract = Cw1155BaseContract::default();
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        let creator = String::from("creator");
+        let user1 = String::from("user1");
+
+        let msg = Cw1155InstantiateMsg {
+            name: "name".to_string(),
+            symbol: "symbol".to_string(),
+            collection_info_extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://example.com/image.png".to_string()),
+                external_link: None,
+                explicit_content: None,
+                start_trading_time: None,
+                royalty_info: None,
+            }),
+            minter: Some(minter.clone()),
+            creator: Some(creator.clone()),
+            default_uri: None,
+        };
+        contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("operator", &[]),
+                msg,
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        let minter_ownership: Ownership<Addr> = from_json(
+            contract
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    Cw1155BaseQueryMsg::GetMinterOwnership {},
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(minter_ownership.owner, Some(Addr::unchecked(&minter)));
+        let creator_ownership: Ownership<Addr> = from_json(
+            contract
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    Cw1155BaseQueryMsg::GetCreatorOwnership {},
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(creator_ownership.owner, Some(Addr::unchecked(&creator)));
+
+        // only the minter can mint
+        let mint_msg = Cw1155BaseExecuteMsg::Mint {
+            recipient: user1.clone(),
+            msg: Cw1155MintMsg {
+                token_id: "token1".to_string(),
+                amount: 1u128.into(),
+                token_uri: None,
+                extension: None,
+                config: None,
+            },
+        };
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(creator.as_ref(), &[]),
+                mint_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155ContractError::Ownership(OwnershipError::NotOwner)
+        );
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                mint_msg,
+            )
+            .unwrap();
+
+        // only the creator can update metadata and the default uri
+        let update_msg = Cw1155BaseExecuteMsg::UpdateDefaultUri {
+            uri: Some("https://example.com/".to_string()),
+        };
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                update_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155ContractError::Ownership(OwnershipError::NotOwner)
+        );
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(creator.as_ref(), &[]),
+                update_msg,
+            )
+            .unwrap();
+
+        // only the creator can update the collection info
+        let update_msg = Cw1155BaseExecuteMsg::UpdateCollectionInfo {
+            collection_info: CollectionInfoMsg {
+                name: Some("new name".to_string()),
+                symbol: None,
+                extension: Some(CollectionExtensionMsg {
+                    description: Some("new description".to_string()),
+                    image: None,
+                    external_link: None,
+                    explicit_content: None,
+                    start_trading_time: None,
+                    royalty_info: None,
+                }),
+                max_supply: None,
+            },
+        };
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(minter.as_ref(), &[]),
+                update_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155ContractError::Ownership(OwnershipError::NotOwner)
+        );
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(creator.as_ref(), &[]),
+                update_msg,
+            )
+            .unwrap();
+
+        let collection_info: CollectionInfoAndExtensionResponse<
+            DefaultOptionalCollectionExtension,
+        > = from_json(
+            contract
+                .query(
+                    deps.as_ref(),
+                    mock_env(),
+                    Cw1155BaseQueryMsg::GetCollectionInfoAndExtension {},
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(collection_info.name, "new name");
+        assert_eq!(collection_info.symbol, "symbol");
+        let extension = collection_info.extension.unwrap();
+        assert_eq!(extension.description, "new description");
+        assert_eq!(extension.image, "https://example.com/image.png");
+    }
+
     #[test]
     fn token_uri() {
         let mut suite = setup(None);
@@ -2226,4 +2663,425 @@ mod tests {
         // verify approvals
         assert_eq!(suite.query_token_approvals(&user1, token_id), vec![]);
     }
+
+    #[test]
+    fn migrate_from_cw_plus() {
+        let mut app = App::default();
+        let admin = app.api().addr_make("admin");
+        let minter = app.api().addr_make("minter");
+        let user1 = app.api().addr_make("user1");
+        let user2 = app.api().addr_make("user2");
+
+        let legacy_code_id = app.store_code(Box::new(ContractWrapper::new(
+            cw1155_base_016::contract::execute,
+            cw1155_base_016::contract::instantiate,
+            cw1155_base_016::contract::query,
+        )));
+        let code_id = app.store_code(Box::new(
+            ContractWrapper::new(execute, instantiate, query).with_migrate(migrate),
+        ));
+
+        // cw-plus contract with balances of two tokens and an approval
+        let cw1155 = app
+            .instantiate_contract(
+                legacy_code_id,
+                admin.clone(),
+                &cw1155_base_016::msg::InstantiateMsg {
+                    minter: minter.to_string(),
+                },
+                &[],
+                "cw-plus cw1155",
+                Some(admin.to_string()),
+            )
+            .unwrap();
+        for (to, token_id, value) in [(&user1, "1", 10u128), (&user2, "1", 5), (&user1, "2", 1)] {
+            app.execute_contract(
+                minter.clone(),
+                cw1155.clone(),
+                &cw1155_016::Cw1155ExecuteMsg::Mint {
+                    to: to.to_string(),
+                    token_id: token_id.to_string(),
+                    value: Uint128::new(value),
+                    msg: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+        // leaves a zero balance for user1
+        app.execute_contract(
+            user1.clone(),
+            cw1155.clone(),
+            &cw1155_016::Cw1155ExecuteMsg::SendFrom {
+                from: user1.to_string(),
+                to: user2.to_string(),
+                token_id: "2".to_string(),
+                value: Uint128::one(),
+                msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            user1.clone(),
+            cw1155.clone(),
+            &cw1155_016::Cw1155ExecuteMsg::ApproveAll {
+                operator: user2.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // cw-plus has no collection info, so name and symbol are required
+        let err: Cw1155ContractError = app
+            .migrate_contract(
+                admin.clone(),
+                cw1155.clone(),
+                &Cw1155MigrateMsg::WithUpdate {
+                    minter: None,
+                    creator: None,
+                    name: None,
+                    symbol: None,
+                },
+                code_id,
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, Cw1155ContractError::MissingCollectionInfo {});
+
+        app.migrate_contract(
+            admin.clone(),
+            cw1155.clone(),
+            &Cw1155MigrateMsg::WithUpdate {
+                minter: None,
+                creator: None,
+                name: Some("legacy".to_string()),
+                symbol: Some("LEGACY".to_string()),
+            },
+            code_id,
+        )
+        .unwrap();
+
+        // legacy minter is both, minter and creator
+        let minter_ownership: Ownership<Addr> = app
+            .wrap()
+            .query_wasm_smart(&cw1155, &Cw1155BaseQueryMsg::GetMinterOwnership {})
+            .unwrap();
+        assert_eq!(minter_ownership.owner, Some(minter.clone()));
+        let creator_ownership: Ownership<Addr> = app
+            .wrap()
+            .query_wasm_smart(&cw1155, &Cw1155BaseQueryMsg::GetCreatorOwnership {})
+            .unwrap();
+        assert_eq!(creator_ownership.owner, Some(minter.clone()));
+        let collection_info: CollectionInfo = app
+            .wrap()
+            .query_wasm_smart(&cw1155, &Cw1155BaseQueryMsg::ContractInfo {})
+            .unwrap();
+        assert_eq!(collection_info.name, "legacy");
+        assert_eq!(collection_info.symbol, "LEGACY");
+
+        // balances, rebuilt index and supply
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw1155,
+                &Cw1155BaseQueryMsg::BalanceOf(OwnerToken {
+                    owner: user1.to_string(),
+                    token_id: "1".to_string(),
+                }),
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::new(10));
+        let balances: BalancesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw1155,
+                &Cw1155BaseQueryMsg::AllBalances {
+                    token_id: "2".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let mut expected = vec![
+            Balance {
+                token_id: "2".to_string(),
+                owner: user1.clone(),
+                amount: Uint128::zero(),
+            },
+            Balance {
+                token_id: "2".to_string(),
+                owner: user2.clone(),
+                amount: Uint128::one(),
+            },
+        ];
+        expected.sort_by(|a, b| a.owner.cmp(&b.owner));
+        assert_eq!(balances.balances, expected);
+        let num_tokens: NumTokensResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw1155,
+                &Cw1155BaseQueryMsg::NumTokens {
+                    token_id: Some("1".to_string()),
+                },
+            )
+            .unwrap();
+        assert_eq!(num_tokens.count, Uint128::new(15));
+        let num_tokens: NumTokensResponse = app
+            .wrap()
+            .query_wasm_smart(&cw1155, &Cw1155BaseQueryMsg::NumTokens { token_id: None })
+            .unwrap();
+        assert_eq!(num_tokens.count, Uint128::new(16));
+
+        // tokens and approvals
+        let token_info: TokenInfoResponse<String> = app
+            .wrap()
+            .query_wasm_smart(
+                &cw1155,
+                &Cw1155BaseQueryMsg::TokenInfo {
+                    token_id: "1".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(token_info.token_uri, "");
+        assert_eq!(token_info.config, TokenConfig::default());
+        let approved: IsApprovedForAllResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw1155,
+                &Cw1155BaseQueryMsg::IsApprovedForAll {
+                    owner: user1.to_string(),
+                    operator: user2.to_string(),
+                },
+            )
+            .unwrap();
+        assert!(approved.approved);
+
+        // migrated contract keeps working: approved transfer and mint
+        app.execute_contract(
+            user2.clone(),
+            cw1155.clone(),
+            &Cw1155BaseExecuteMsg::Send {
+                from: Some(user1.to_string()),
+                to: user2.to_string(),
+                token_id: "1".to_string(),
+                amount: Uint128::new(4),
+                msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            minter.clone(),
+            cw1155.clone(),
+            &Cw1155BaseExecuteMsg::Mint {
+                recipient: user1.to_string(),
+                msg: Cw1155MintMsg {
+                    token_id: "1".to_string(),
+                    amount: Uint128::one(),
+                    token_uri: None,
+                    extension: None,
+                    config: None,
+                },
+            },
+            &[],
+        )
+        .unwrap();
+        let balances: BalancesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &cw1155,
+                &Cw1155BaseQueryMsg::BalanceOfBatch(vec![
+                    OwnerToken {
+                        owner: user1.to_string(),
+                        token_id: "1".to_string(),
+                    },
+                    OwnerToken {
+                        owner: user2.to_string(),
+                        token_id: "1".to_string(),
+                    },
+                ]),
+            )
+            .unwrap();
+        let amounts: Vec<_> = balances.balances.iter().map(|b| b.amount).collect();
+        assert_eq!(amounts, vec![Uint128::new(7), Uint128::new(9)]);
+
+        // migrating again is a no-op for legacy data
+        app.migrate_contract(
+            admin,
+            cw1155.clone(),
+            &Cw1155MigrateMsg::WithUpdate {
+                minter: None,
+                creator: None,
+                name: None,
+                symbol: None,
+            },
+            code_id,
+        )
+        .unwrap();
+        let num_tokens: NumTokensResponse = app
+            .wrap()
+            .query_wasm_smart(&cw1155, &Cw1155BaseQueryMsg::NumTokens { token_id: None })
+            .unwrap();
+        assert_eq!(num_tokens.count, Uint128::new(17));
+    }
+
+    #[test]
+    fn migrate_legacy_collection_info_and_minter() {
+        let contract = Cw1155BaseContract::default();
+        let mut deps = mock_dependencies();
+        let creator = String::from("creator");
+
+        let msg = Cw1155InstantiateMsg {
+            name: "name".to_string(),
+            symbol: "symbol".to_string(),
+            collection_info_extension: None,
+            minter: None,
+            creator: None,
+            default_uri: None,
+        };
+        contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(&creator, &[]),
+                msg,
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        // older cw1155 versions used a single owner and a dedicated collection info store
+        #[cw_serde]
+        struct LegacyCollectionInfo {
+            name: String,
+            symbol: String,
+        }
+        MINTER.item.remove(deps.as_mut().storage);
+        Cw1155BaseConfig::default()
+            .collection_info
+            .remove(deps.as_mut().storage);
+        Item::new("collection_info")
+            .save(
+                deps.as_mut().storage,
+                &LegacyCollectionInfo {
+                    name: "legacy".to_string(),
+                    symbol: "LEGACY".to_string(),
+                },
+            )
+            .unwrap();
+
+        contract
+            .migrate(
+                deps.as_mut(),
+                mock_env(),
+                Cw1155MigrateMsg::WithUpdate {
+                    minter: None,
+                    creator: None,
+                    name: None,
+                    symbol: None,
+                },
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        // owner is used for both, minter and creator
+        let minter_ownership = MINTER.get_ownership(deps.as_ref().storage).unwrap();
+        assert_eq!(minter_ownership.owner, Some(Addr::unchecked(&creator)));
+        let creator_ownership = CREATOR.get_ownership(deps.as_ref().storage).unwrap();
+        assert_eq!(creator_ownership.owner, Some(Addr::unchecked(&creator)));
+        let collection_info = Cw1155BaseConfig::default()
+            .collection_info
+            .load(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(collection_info.name, "legacy");
+        assert_eq!(collection_info.symbol, "LEGACY");
+    }
+
+    #[test]
+    fn migrate_from_cw_plus_at_documented_size_limit() {
+        let contract = Cw1155BaseContract::default();
+        let config = Cw1155BaseConfig::default();
+        let mut deps = mock_dependencies();
+        contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("minter", &[]),
+                Cw1155InstantiateMsg {
+                    name: "name".to_string(),
+                    symbol: "symbol".to_string(),
+                    collection_info_extension: None,
+                    minter: None,
+                    creator: None,
+                    default_uri: None,
+                },
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        // cw-plus layout with 4,000 balances: 200 owners holding each of 20 tokens
+        config.supply.remove(deps.as_mut().storage);
+        let legacy_balances: Map<(&Addr, &str), Uint128> = Map::new("balances");
+        let legacy_tokens: Map<&str, String> = Map::new("tokens");
+        let owners: Vec<Addr> = (0..200)
+            .map(|i| Addr::unchecked(format!("owner{i}")))
+            .collect();
+        let token_ids: Vec<String> = (0..20).map(|i| format!("token{i}")).collect();
+        for token_id in &token_ids {
+            legacy_tokens
+                .save(deps.as_mut().storage, token_id, &String::new())
+                .unwrap();
+            for owner in &owners {
+                legacy_balances
+                    .save(
+                        deps.as_mut().storage,
+                        (owner, token_id.as_str()),
+                        &Uint128::new(3),
+                    )
+                    .unwrap();
+            }
+        }
+
+        let res = contract
+            .migrate(
+                deps.as_mut(),
+                mock_env(),
+                Cw1155MigrateMsg::WithUpdate {
+                    minter: None,
+                    creator: None,
+                    name: None,
+                    symbol: None,
+                },
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "migrated balances" && attr.value == "4000"));
+
+        let storage = deps.as_ref().storage;
+        assert_eq!(config.supply.load(storage).unwrap(), Uint128::new(12_000));
+        for token_id in &token_ids {
+            assert_eq!(
+                config.token_count(storage, token_id).unwrap(),
+                Uint128::new(600)
+            );
+            assert_eq!(
+                config.tokens.load(storage, token_id).unwrap().minted,
+                Uint128::new(600)
+            );
+        }
+        let balance = config
+            .balances
+            .load(storage, (owners[199].clone(), token_ids[19].clone()))
+            .unwrap();
+        assert_eq!(balance.amount, Uint128::new(3));
+    }
 }
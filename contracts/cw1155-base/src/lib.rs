@@ -25,7 +25,7 @@ pub mod entry {
     use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
     use cw1155::error::Cw1155ContractError;
     use cw1155::execute::Cw1155Execute;
-    use cw1155::msg::{Cw1155ExecuteMsg, Cw1155InstantiateMsg, Cw1155QueryMsg};
+    use cw1155::msg::{Cw1155ExecuteMsg, Cw1155InstantiateMsg, Cw1155MigrateMsg, Cw1155QueryMsg};
     use cw1155::query::Cw1155Query;
 
     // This makes a conscious choice on the various generics used by the contract
@@ -58,7 +58,11 @@ pub mod entry {
     }
 
     #[cfg_attr(not(feature = "library"), entry_point)]
-    pub fn migrate(deps: DepsMut, env: Env, msg: Empty) -> Result<Response, Cw1155ContractError> {
+    pub fn migrate(
+        deps: DepsMut,
+        env: Env,
+        msg: Cw1155MigrateMsg,
+    ) -> Result<Response, Cw1155ContractError> {
         let contract = Cw1155BaseContract::default();
         contract.migrate(deps, env, msg, CONTRACT_NAME, CONTRACT_VERSION)
     }
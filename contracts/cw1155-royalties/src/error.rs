@@ -1,5 +1,6 @@
 use cosmwasm_std::StdError;
 use cw1155::error::Cw1155ContractError;
+use cw721::error::Cw721ContractError;
 use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq)]
@@ -10,9 +11,6 @@ pub enum Cw1155RoyaltiesContractError {
     #[error(transparent)]
     Base(#[from] Cw1155ContractError),
 
-    #[error("Royalty percentage must be between 0 and 100")]
-    InvalidRoyaltyPercentage,
-
-    #[error("Invalid royalty payment address")]
-    InvalidRoyaltyPaymentAddress,
+    #[error(transparent)]
+    Cw721(#[from] Cw721ContractError),
 }
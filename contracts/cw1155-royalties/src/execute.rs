@@ -0,0 +1,51 @@
+use crate::{
+    query_collection_royalty_info, Cw1155RoyaltiesConfig, Cw1155RoyaltiesContractError,
+    MetadataWithRoyalty,
+};
+use cosmwasm_std::{Addr, Deps, Env, MessageInfo};
+use cw721::msg::validate_royalty_share;
+use cw721::state::RoyaltyInfo;
+use cw721::traits::StateFactory;
+
+/// Validates the royalty of a token, same as the collection royalty: share must not exceed the max
+/// share, and the share increase must not exceed the max share delta. The increase is compared to
+/// the royalty currently in effect for the token, which is its own royalty or else the collection
+/// royalty. Removing the token royalty falls back to the collection royalty, which is validated the
+/// same way.
+pub fn validate_royalty_info(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: &str,
+    metadata: Option<&MetadataWithRoyalty>,
+) -> Result<(), Cw1155RoyaltiesContractError> {
+    let config = Cw1155RoyaltiesConfig::default();
+    let token_royalty_info = config
+        .tokens
+        .may_load(deps.storage, token_id)?
+        .and_then(|token_info| token_info.extension)
+        .and_then(|extension| extension.royalty_info);
+    let collection_royalty_info = query_collection_royalty_info(deps)?;
+    match metadata.and_then(|metadata| metadata.royalty_info.as_ref()) {
+        Some(royalty_info) => {
+            let current = token_royalty_info
+                .or(collection_royalty_info)
+                .map(|current| RoyaltyInfo {
+                    payment_address: Addr::unchecked(current.payment_address),
+                    share: current.share,
+                });
+            royalty_info.validate(deps, env, Some(info), current.as_ref())?;
+        }
+        None => {
+            if let (Some(token_royalty_info), Some(collection_royalty_info)) =
+                (token_royalty_info, collection_royalty_info)
+            {
+                validate_royalty_share(
+                    collection_royalty_info.share,
+                    Some(token_royalty_info.share),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
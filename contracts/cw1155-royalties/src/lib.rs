@@ -2,11 +2,15 @@ use cosmwasm_std::Empty;
 use cw1155::msg::{Cw1155ExecuteMsg, Cw1155QueryMsg};
 use cw1155::state::Cw1155Config;
 use cw1155_base::Cw1155Contract;
-use cw2981_royalties::msg::QueryMsg as Cw2981QueryMsg;
-use cw2981_royalties::MetadataWithRoyalty;
+
+mod execute;
+pub use execute::validate_royalty_info;
+
+pub mod msg;
+pub use msg::{MetadataWithRoyalty, QueryExtensionMsg};
 
 mod query;
-pub use query::query_royalties_info;
+pub use query::{check_royalties, query_collection_royalty_info, query_royalties_info};
 
 mod error;
 pub use error::Cw1155RoyaltiesContractError;
@@ -16,11 +20,11 @@ const CONTRACT_NAME: &str = "crates.io:cw1155-royalties";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub type Cw1155RoyaltiesContract<'a> =
-    Cw1155Contract<'a, MetadataWithRoyalty, Empty, Empty, Cw2981QueryMsg>;
+    Cw1155Contract<'a, MetadataWithRoyalty, Empty, Empty, QueryExtensionMsg>;
 pub type Cw1155RoyaltiesExecuteMsg = Cw1155ExecuteMsg<MetadataWithRoyalty, Empty>;
-pub type Cw1155RoyaltiesQueryMsg = Cw1155QueryMsg<MetadataWithRoyalty, Cw2981QueryMsg>;
+pub type Cw1155RoyaltiesQueryMsg = Cw1155QueryMsg<MetadataWithRoyalty, QueryExtensionMsg>;
 pub type Cw1155RoyaltiesConfig<'a> =
-    Cw1155Config<'a, MetadataWithRoyalty, Empty, Empty, Cw2981QueryMsg>;
+    Cw1155Config<'a, MetadataWithRoyalty, Empty, Empty, QueryExtensionMsg>;
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
@@ -29,9 +33,8 @@ pub mod entry {
     use cosmwasm_std::{entry_point, to_json_binary};
     use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
     use cw1155::execute::Cw1155Execute;
+    use cw1155::msg::TokenUpdate;
     use cw1155::query::Cw1155Query;
-    use cw2981_royalties::msg::QueryMsg as Cw2981QueryMsg;
-    use cw2981_royalties::{check_royalties, MetadataWithRoyalty};
 
     // This makes a conscious choice on the various generics used by the contract
     #[entry_point]
@@ -60,32 +63,50 @@ pub mod entry {
         info: MessageInfo,
         msg: Cw1155RoyaltiesExecuteMsg,
     ) -> Result<Response, Cw1155RoyaltiesContractError> {
-        if let Cw1155RoyaltiesExecuteMsg::Mint {
-            msg:
-                cw1155::msg::Cw1155MintMsg {
-                    extension:
-                        Some(MetadataWithRoyalty {
-                            royalty_percentage: Some(royalty_percentage),
-                            royalty_payment_address,
-                            ..
-                        }),
-                    ..
-                },
-            ..
-        } = &msg
-        {
-            // validate royalty_percentage to be between 0 and 100
-            // no need to check < 0 because royalty_percentage is u64
-            if *royalty_percentage > 100 {
-                return Err(Cw1155RoyaltiesContractError::InvalidRoyaltyPercentage);
+        // validate token royalties, the collection royalty is validated by cw721
+        let config = Cw1155RoyaltiesConfig::default();
+        match &msg {
+            // metadata is only stored on first mint, so royalties of further mints are ignored
+            Cw1155RoyaltiesExecuteMsg::Mint { msg, .. } => {
+                if !config.tokens.has(deps.storage, &msg.token_id) {
+                    validate_royalty_info(
+                        deps.as_ref(),
+                        &env,
+                        &info,
+                        &msg.token_id,
+                        msg.extension.as_ref(),
+                    )?;
+                }
             }
-
-            // validate royalty_payment_address to be a valid address
-            if let Some(royalty_payment_address) = royalty_payment_address {
-                deps.api.addr_validate(royalty_payment_address)?;
-            } else {
-                return Err(Cw1155RoyaltiesContractError::InvalidRoyaltyPaymentAddress);
+            Cw1155RoyaltiesExecuteMsg::MintBatch { msgs, .. } => {
+                for (i, msg) in msgs.iter().enumerate() {
+                    if !config.tokens.has(deps.storage, &msg.token_id)
+                        && !msgs[..i].iter().any(|other| other.token_id == msg.token_id)
+                    {
+                        validate_royalty_info(
+                            deps.as_ref(),
+                            &env,
+                            &info,
+                            &msg.token_id,
+                            msg.extension.as_ref(),
+                        )?;
+                    }
+                }
+            }
+            Cw1155RoyaltiesExecuteMsg::UpdateMetadata(TokenUpdate {
+                token_id, metadata, ..
+            }) => {
+                validate_royalty_info(deps.as_ref(), &env, &info, token_id, metadata.as_ref())?;
             }
+            Cw1155RoyaltiesExecuteMsg::UpdateMetadataBatch { updates } => {
+                for TokenUpdate {
+                    token_id, metadata, ..
+                } in updates
+                {
+                    validate_royalty_info(deps.as_ref(), &env, &info, token_id, metadata.as_ref())?;
+                }
+            }
+            _ => {}
         }
         Ok(Cw1155RoyaltiesContract::default().execute(deps, env, info, msg)?)
     }
@@ -94,12 +115,11 @@ pub mod entry {
     pub fn query(deps: Deps, env: Env, msg: Cw1155RoyaltiesQueryMsg) -> StdResult<Binary> {
         match msg {
             Cw1155RoyaltiesQueryMsg::Extension { msg: ext_msg, .. } => match ext_msg {
-                Cw2981QueryMsg::RoyaltyInfo {
+                QueryExtensionMsg::RoyaltyInfo {
                     token_id,
                     sale_price,
                 } => to_json_binary(&query_royalties_info(deps, token_id, sale_price)?),
-                Cw2981QueryMsg::CheckRoyalties {} => to_json_binary(&check_royalties(deps)?),
-                _ => unimplemented!(),
+                QueryExtensionMsg::CheckRoyalties {} => to_json_binary(&check_royalties(deps)?),
             },
             _ => Cw1155RoyaltiesContract::default().query(deps, env, msg),
         }
@@ -110,12 +130,14 @@ pub mod entry {
 mod tests {
     use super::*;
 
-    use cosmwasm_std::{from_json, Uint128};
+    use cosmwasm_std::{from_json, Decimal, Uint128};
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cw1155::msg::{Cw1155InstantiateMsg, Cw1155MintMsg};
+    use cw1155::error::Cw1155ContractError;
+    use cw1155::msg::{Cw1155InstantiateMsg, Cw1155MintMsg, TokenUpdate};
     use cw2981_royalties::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse};
-    use cw2981_royalties::{check_royalties, MetadataWithRoyalty};
+    use cw721::error::Cw721ContractError;
+    use cw721::msg::{CollectionExtensionMsg, CollectionInfoMsg, RoyaltyInfoResponse};
 
     const CREATOR: &str = "creator";
 
@@ -128,7 +150,9 @@ mod tests {
         let init_msg = Cw1155InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
             minter: None,
+            creator: None,
             default_uri: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
@@ -147,6 +171,7 @@ mod tests {
                 token_uri: token_uri.clone(),
                 extension: extension.clone(),
                 amount: Uint128::one(),
+                config: None,
             },
         };
         entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
@@ -165,7 +190,9 @@ mod tests {
         let init_msg = Cw1155InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
             minter: None,
+            creator: None,
             default_uri: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
@@ -180,14 +207,23 @@ mod tests {
                 extension: Some(MetadataWithRoyalty {
                     description: Some("Spaceship with Warp Drive".into()),
                     name: Some("Starship USS Enterprise".to_string()),
-                    royalty_percentage: Some(101),
+                    royalty_info: Some(RoyaltyInfoResponse {
+                        payment_address: "john".to_string(),
+                        share: Decimal::percent(11),
+                    }),
                     ..MetadataWithRoyalty::default()
                 }),
+                config: None,
             },
         };
         // mint will return StdError
         let err = entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap_err();
-        assert_eq!(err, Cw1155RoyaltiesContractError::InvalidRoyaltyPercentage);
+        assert_eq!(
+            err,
+            Cw1155RoyaltiesContractError::Cw721(Cw721ContractError::InvalidRoyalties(
+                "Share cannot be greater than 10%".to_string()
+            ))
+        );
     }
 
     #[test]
@@ -199,7 +235,9 @@ mod tests {
         let init_msg = Cw1155InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
             minter: None,
+            creator: None,
             default_uri: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
@@ -216,6 +254,7 @@ mod tests {
                     name: Some("Starship USS Enterprise".to_string()),
                     ..MetadataWithRoyalty::default()
                 }),
+                config: None,
             },
         };
         entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
@@ -228,7 +267,7 @@ mod tests {
 
         // also check the longhand way
         let query_msg = Cw1155RoyaltiesQueryMsg::Extension {
-            msg: Cw2981QueryMsg::CheckRoyalties {},
+            msg: QueryExtensionMsg::CheckRoyalties {},
             phantom: None,
         };
         let query_res: CheckRoyaltiesResponse =
@@ -244,7 +283,9 @@ mod tests {
         let init_msg = Cw1155InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
             minter: None,
+            creator: None,
             default_uri: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
@@ -260,10 +301,13 @@ mod tests {
                 extension: Some(MetadataWithRoyalty {
                     description: Some("Spaceship with Warp Drive".into()),
                     name: Some("Starship USS Enterprise".to_string()),
-                    royalty_payment_address: Some("jeanluc".to_string()),
-                    royalty_percentage: Some(10),
+                    royalty_info: Some(RoyaltyInfoResponse {
+                        payment_address: "jeanluc".to_string(),
+                        share: Decimal::percent(10),
+                    }),
                     ..MetadataWithRoyalty::default()
                 }),
+                config: None,
             },
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
@@ -278,7 +322,7 @@ mod tests {
 
         // also check the longhand way
         let query_msg = Cw1155RoyaltiesQueryMsg::Extension {
-            msg: Cw2981QueryMsg::RoyaltyInfo {
+            msg: QueryExtensionMsg::RoyaltyInfo {
                 token_id: token_id.to_string(),
                 sale_price: Uint128::new(100),
             },
@@ -301,10 +345,13 @@ mod tests {
                 extension: Some(MetadataWithRoyalty {
                     description: Some("Spaceship with Warp Drive".into()),
                     name: Some("Starship USS Voyager".to_string()),
-                    royalty_payment_address: Some("janeway".to_string()),
-                    royalty_percentage: Some(4),
+                    royalty_info: Some(RoyaltyInfoResponse {
+                        payment_address: "janeway".to_string(),
+                        share: Decimal::percent(4),
+                    }),
                     ..MetadataWithRoyalty::default()
                 }),
+                config: None,
             },
         };
         entry::execute(deps.as_mut(), mock_env(), info, voyager_exec_msg).unwrap();
@@ -324,4 +371,243 @@ mod tests {
         .unwrap();
         assert_eq!(res, voyager_expected);
     }
+
+    #[test]
+    fn update_token_royalties() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = Cw1155InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            collection_info_extension: None,
+            minter: None,
+            creator: None,
+            default_uri: None,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let token_id = "Enterprise";
+        let royalty_info = |share| RoyaltyInfoResponse {
+            payment_address: "jeanluc".to_string(),
+            share: Decimal::percent(share),
+        };
+        let exec_msg = Cw1155RoyaltiesExecuteMsg::Mint {
+            recipient: "jeanluc".into(),
+            msg: Cw1155MintMsg {
+                token_id: token_id.to_string(),
+                amount: Uint128::one(),
+                token_uri: None,
+                extension: Some(MetadataWithRoyalty {
+                    royalty_info: Some(royalty_info(5)),
+                    ..MetadataWithRoyalty::default()
+                }),
+                config: None,
+            },
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+
+        // share increase is limited to 2%
+        let update_msg = |share| {
+            Cw1155RoyaltiesExecuteMsg::UpdateMetadata(TokenUpdate {
+                token_id: token_id.to_string(),
+                token_uri: None,
+                metadata: Some(MetadataWithRoyalty {
+                    royalty_info: Some(royalty_info(share)),
+                    ..MetadataWithRoyalty::default()
+                }),
+            })
+        };
+        let err =
+            entry::execute(deps.as_mut(), mock_env(), info.clone(), update_msg(8)).unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155RoyaltiesContractError::Cw721(Cw721ContractError::InvalidRoyalties(
+                "Share increase cannot be greater than 2%".to_string()
+            ))
+        );
+        let err = entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            Cw1155RoyaltiesExecuteMsg::UpdateMetadataBatch {
+                updates: vec![TokenUpdate {
+                    token_id: token_id.to_string(),
+                    token_uri: None,
+                    metadata: Some(MetadataWithRoyalty {
+                        royalty_info: Some(royalty_info(8)),
+                        ..MetadataWithRoyalty::default()
+                    }),
+                }],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155RoyaltiesContractError::Cw721(Cw721ContractError::InvalidRoyalties(
+                "Share increase cannot be greater than 2%".to_string()
+            ))
+        );
+
+        entry::execute(deps.as_mut(), mock_env(), info, update_msg(7)).unwrap();
+        let res =
+            query_royalties_info(deps.as_ref(), token_id.to_string(), Uint128::new(100)).unwrap();
+        assert_eq!(
+            res,
+            RoyaltiesInfoResponse {
+                address: "jeanluc".to_string(),
+                royalty_amount: Uint128::new(7),
+            }
+        );
+    }
+
+    #[test]
+    fn collection_royalties() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let royalty_info = |share| RoyaltyInfoResponse {
+            payment_address: CREATOR.to_string(),
+            share: Decimal::percent(share),
+        };
+        let collection_extension = |share| CollectionExtensionMsg {
+            description: None,
+            image: None,
+            external_link: None,
+            explicit_content: None,
+            start_trading_time: None,
+            royalty_info: Some(royalty_info(share)),
+        };
+        let init_msg = Cw1155InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            collection_info_extension: Some(collection_extension(5)),
+            minter: None,
+            creator: None,
+            default_uri: None,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        // token without royalty uses the collection royalty, token royalty overrides it
+        for (token_id, royalty_info) in [("Enterprise", None), ("Voyager", Some(royalty_info(1)))] {
+            let exec_msg = Cw1155RoyaltiesExecuteMsg::Mint {
+                recipient: "jeanluc".into(),
+                msg: Cw1155MintMsg {
+                    token_id: token_id.to_string(),
+                    amount: Uint128::one(),
+                    token_uri: None,
+                    extension: Some(MetadataWithRoyalty {
+                        royalty_info,
+                        ..MetadataWithRoyalty::default()
+                    }),
+                    config: None,
+                },
+            };
+            entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        }
+        let res = query_royalties_info(deps.as_ref(), "Enterprise".to_string(), Uint128::new(100))
+            .unwrap();
+        assert_eq!(res.address, CREATOR);
+        assert_eq!(res.royalty_amount, Uint128::new(5));
+        let res =
+            query_royalties_info(deps.as_ref(), "Voyager".to_string(), Uint128::new(100)).unwrap();
+        assert_eq!(res.royalty_amount, Uint128::new(1));
+
+        // collection royalty is updated by the creator, with the same rules as token royalties
+        let update_msg = |share| Cw1155RoyaltiesExecuteMsg::UpdateCollectionInfo {
+            collection_info: CollectionInfoMsg {
+                name: None,
+                symbol: None,
+                extension: Some(collection_extension(share)),
+                max_supply: None,
+            },
+        };
+        let err =
+            entry::execute(deps.as_mut(), mock_env(), info.clone(), update_msg(8)).unwrap_err();
+        assert_eq!(
+            err,
+            Cw1155RoyaltiesContractError::Base(Cw1155ContractError::Cw721(
+                Cw721ContractError::InvalidRoyalties(
+                    "Share increase cannot be greater than 2%".to_string()
+                )
+            ))
+        );
+        entry::execute(deps.as_mut(), mock_env(), info, update_msg(7)).unwrap();
+        assert_eq!(
+            query_collection_royalty_info(deps.as_ref()).unwrap(),
+            Some(royalty_info(7))
+        );
+        let res = query_royalties_info(deps.as_ref(), "Enterprise".to_string(), Uint128::new(100))
+            .unwrap();
+        assert_eq!(res.royalty_amount, Uint128::new(7));
+    }
+
+    #[test]
+    fn token_royalties_validated_against_collection_royalty() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let royalty_info = |share| RoyaltyInfoResponse {
+            payment_address: CREATOR.to_string(),
+            share: Decimal::percent(share),
+        };
+        let init_msg = Cw1155InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            collection_info_extension: Some(CollectionExtensionMsg {
+                description: None,
+                image: None,
+                external_link: None,
+                explicit_content: None,
+                start_trading_time: None,
+                royalty_info: Some(royalty_info(5)),
+            }),
+            minter: None,
+            creator: None,
+            default_uri: None,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let token_id = "Enterprise";
+        let mint_msg = |share| Cw1155RoyaltiesExecuteMsg::Mint {
+            recipient: "jeanluc".into(),
+            msg: Cw1155MintMsg {
+                token_id: token_id.to_string(),
+                amount: Uint128::one(),
+                token_uri: None,
+                extension: Some(MetadataWithRoyalty {
+                    royalty_info: Some(royalty_info(share)),
+                    ..MetadataWithRoyalty::default()
+                }),
+                config: None,
+            },
+        };
+        let share_increase_err =
+            Cw1155RoyaltiesContractError::Cw721(Cw721ContractError::InvalidRoyalties(
+                "Share increase cannot be greater than 2%".to_string(),
+            ));
+
+        // first token royalty is compared to the collection royalty
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg(8)).unwrap_err();
+        assert_eq!(err, share_increase_err);
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg(1)).unwrap();
+
+        // royalty of further mints is ignored, hence not validated
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg(11)).unwrap();
+        let res =
+            query_royalties_info(deps.as_ref(), token_id.to_string(), Uint128::new(100)).unwrap();
+        assert_eq!(res.royalty_amount, Uint128::new(1));
+
+        // removing the token royalty is compared to the collection royalty, too
+        for metadata in [None, Some(MetadataWithRoyalty::default())] {
+            let update_msg = Cw1155RoyaltiesExecuteMsg::UpdateMetadata(TokenUpdate {
+                token_id: token_id.to_string(),
+                token_uri: None,
+                metadata,
+            });
+            let err =
+                entry::execute(deps.as_mut(), mock_env(), info.clone(), update_msg).unwrap_err();
+            assert_eq!(err, share_increase_err);
+        }
+    }
 }
@@ -0,0 +1,39 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+use cw721::msg::RoyaltyInfoResponse;
+use cw721::state::Trait;
+
+// see: https://docs.opensea.io/docs/metadata-standards
+#[cw_serde]
+#[derive(Default)]
+pub struct MetadataWithRoyalty {
+    pub image: Option<String>,
+    pub image_data: Option<String>,
+    pub external_url: Option<String>,
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+    pub background_color: Option<String>,
+    pub animation_url: Option<String>,
+    pub youtube_url: Option<String>,
+    /// Royalty owed on sale of this token, overrides the collection royalty.
+    /// If None, the collection royalty applies.
+    pub royalty_info: Option<RoyaltyInfoResponse>,
+}
+
+#[cw_serde]
+pub enum QueryExtensionMsg {
+    /// Should be called on sale to see if royalties are owed
+    /// by the marketplace selling the tokens, if CheckRoyalties
+    /// returns true
+    /// See https://eips.ethereum.org/EIPS/eip-2981
+    RoyaltyInfo {
+        token_id: String,
+        // the denom of this sale must also be the denom returned by RoyaltiesInfoResponse
+        sale_price: Uint128,
+    },
+    /// Called against contract to determine if this contract
+    /// implements royalties. Returns true, since royalties
+    /// are set at token and collection level.
+    CheckRoyalties {},
+}
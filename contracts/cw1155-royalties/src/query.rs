@@ -1,10 +1,13 @@
 use crate::Cw1155RoyaltiesConfig;
-use cosmwasm_std::{Decimal, Deps, StdResult, Uint128};
-use cw2981_royalties::msg::RoyaltiesInfoResponse;
+use cosmwasm_std::{Deps, StdError, StdResult, Uint128};
+use cw2981_royalties::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse};
+use cw721::msg::RoyaltyInfoResponse;
+use cw721::query::query_collection_info_and_extension;
+use cw721::DefaultOptionalCollectionExtension;
 
 /// NOTE: default behaviour here is to round down
 /// EIP2981 specifies that the rounding behaviour is at the discretion of the implementer
-/// NOTE: This implementation is copied from the cw2981-royalties contract, only difference is the TokenInfo struct (no owner field in cw1155)
+/// Royalty of the token is used if set, otherwise the collection royalty.
 pub fn query_royalties_info(
     deps: Deps,
     token_id: String,
@@ -13,22 +16,39 @@ pub fn query_royalties_info(
     let config = Cw1155RoyaltiesConfig::default();
     let token_info = config.tokens.load(deps.storage, &token_id)?;
 
-    let royalty_percentage = match token_info.extension {
-        Some(ref ext) => match ext.royalty_percentage {
-            Some(percentage) => Decimal::percent(percentage),
-            None => Decimal::percent(0),
-        },
-        None => Decimal::percent(0),
+    let royalty_info = match token_info.extension.and_then(|ext| ext.royalty_info) {
+        Some(royalty_info) => Some(royalty_info),
+        None => query_collection_royalty_info(deps)?,
     };
-    let royalty_from_sale_price = sale_price * royalty_percentage;
 
-    let royalty_address = match token_info.extension {
-        Some(ext) => ext.royalty_payment_address.unwrap_or_default(),
-        None => String::from(""),
-    };
+    Ok(match royalty_info {
+        Some(royalty_info) => RoyaltiesInfoResponse {
+            address: royalty_info.payment_address,
+            royalty_amount: sale_price * royalty_info.share,
+        },
+        None => RoyaltiesInfoResponse {
+            address: String::from(""),
+            royalty_amount: Uint128::zero(),
+        },
+    })
+}
+
+/// Default royalty of all tokens, set in the collection info extension.
+pub fn query_collection_royalty_info(deps: Deps) -> StdResult<Option<RoyaltyInfoResponse>> {
+    let collection_info =
+        query_collection_info_and_extension::<DefaultOptionalCollectionExtension>(deps)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(collection_info
+        .extension
+        .and_then(|extension| extension.royalty_info)
+        .map(Into::into))
+}
 
-    Ok(RoyaltiesInfoResponse {
-        address: royalty_address,
-        royalty_amount: royalty_from_sale_price,
+/// Royalties are set at token and collection level, so
+/// on sale, every token managed by this contract should be checked
+/// to see if royalties are owed, and to whom.
+pub fn check_royalties(_deps: Deps) -> StdResult<CheckRoyaltiesResponse> {
+    Ok(CheckRoyaltiesResponse {
+        royalty_payments: true,
     })
 }
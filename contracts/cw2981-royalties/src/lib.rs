@@ -162,6 +162,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -173,7 +174,7 @@ mod tests {
             ..MetadataWithRoyalty::default()
         });
         let exec_msg = ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: extension.clone(),
@@ -201,12 +202,13 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
         let token_id = "Enterprise";
         let exec_msg = ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(MetadataWithRoyalty {
@@ -234,12 +236,13 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
         let token_id = "Enterprise";
         let exec_msg = ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(MetadataWithRoyalty {
@@ -275,6 +278,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         let env = mock_env();
         entry::instantiate(deps.as_mut(), env.clone(), info.clone(), init_msg).unwrap();
@@ -282,7 +286,7 @@ mod tests {
         let token_id = "Enterprise";
         let owner = "jeanluc";
         let exec_msg = ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: owner.into(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(MetadataWithRoyalty {
@@ -317,7 +321,7 @@ mod tests {
         let voyager_token_id = "Voyager";
         let owner = "janeway";
         let voyager_exec_msg = ExecuteMsg::Mint {
-            token_id: voyager_token_id.to_string(),
+            token_id: Some(voyager_token_id.to_string()),
             owner: owner.into(),
             token_uri: Some("https://starships.example.com/Starship/Voyager.json".into()),
             extension: Some(MetadataWithRoyalty {
@@ -0,0 +1,503 @@
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionType, ExecuteMsg, InstantiateMsg, Payout, PayoutsResponse, QueryMsg, ReceiveMsg,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdResult, Uint128, WasmMsg,
+};
+use cw1155::msg::Cw1155QueryMsg;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw2981_royalties::msg::QueryMsg as Cw2981QueryMsg;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::RoyaltiesInfoResponse;
+use cw721::traits::Cw721Calls;
+use cw_utils::one_coin;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw2981-royalty-router";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Distribute {
+            collection,
+            collection_type,
+            token_id,
+            seller,
+        } => execute_distribute(deps, info, collection, collection_type, token_id, seller),
+        ExecuteMsg::Receive(Cw20ReceiveMsg { amount, msg, .. }) => {
+            execute_receive(deps, info, amount, msg)
+        }
+    }
+}
+
+pub fn execute_distribute(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    collection_type: CollectionType,
+    token_id: String,
+    seller: String,
+) -> Result<Response, ContractError> {
+    let payment = one_coin(&info)?;
+    distribute(
+        deps,
+        Denom::Native(payment.denom),
+        payment.amount,
+        collection,
+        collection_type,
+        token_id,
+        seller,
+    )
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let ReceiveMsg {
+        collection,
+        collection_type,
+        token_id,
+        seller,
+    } = from_json(&msg)?;
+    // the sender is the cw20 contract, proceeds are paid out in the same token
+    distribute(
+        deps,
+        Denom::Cw20(info.sender),
+        amount,
+        collection,
+        collection_type,
+        token_id,
+        seller,
+    )
+}
+
+fn distribute(
+    deps: DepsMut,
+    denom: Denom,
+    sale_price: Uint128,
+    collection: String,
+    collection_type: CollectionType,
+    token_id: String,
+    seller: String,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&seller)?;
+    let PayoutsResponse {
+        royalties,
+        seller_amount,
+    } = query_payouts(
+        deps.as_ref(),
+        collection.clone(),
+        collection_type,
+        token_id.clone(),
+        sale_price,
+    )?;
+    let royalty_amount = sale_price - seller_amount;
+
+    let mut messages = royalties
+        .into_iter()
+        .map(|royalty| payout_msg(&denom, royalty.address, royalty.amount))
+        .collect::<StdResult<Vec<_>>>()?;
+    if !seller_amount.is_zero() {
+        messages.push(payout_msg(&denom, seller.clone(), seller_amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "distribute")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("seller", seller)
+        .add_attribute("sale_price", sale_price)
+        .add_attribute("royalty_amount", royalty_amount))
+}
+
+fn payout_msg(denom: &Denom, recipient: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.into_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+        Denom::Cw20(cw20_address) => WasmMsg::Execute {
+            contract_addr: cw20_address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::GetPayouts {
+            collection,
+            collection_type,
+            token_id,
+            sale_price,
+        } => Ok(to_json_binary(&query_payouts(
+            deps,
+            collection,
+            collection_type,
+            token_id,
+            sale_price,
+        )?)?),
+    }
+}
+
+pub fn query_payouts(
+    deps: Deps,
+    collection: String,
+    collection_type: CollectionType,
+    token_id: String,
+    sale_price: Uint128,
+) -> Result<PayoutsResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let royalties: Vec<RoyaltiesInfoResponse> = match collection_type {
+        CollectionType::Cw721 => DefaultCw721Helper::new(collection).royalty_split_info(
+            &deps.querier,
+            token_id,
+            sale_price,
+        )?,
+        CollectionType::Cw2981 => vec![DefaultCw721Helper::new(collection).royalty_info(
+            &deps.querier,
+            token_id,
+            sale_price,
+        )?],
+        CollectionType::Cw1155 => vec![deps.querier.query_wasm_smart(
+            collection,
+            &Cw1155QueryMsg::<Empty, Cw2981QueryMsg>::Extension {
+                msg: Cw2981QueryMsg::RoyaltyInfo {
+                    token_id,
+                    sale_price,
+                },
+                phantom: None,
+            },
+        )?],
+    };
+
+    let mut payouts = vec![];
+    let mut royalty_amount = Uint128::zero();
+    for royalty in royalties {
+        // an empty address means no royalties are owed
+        if royalty.address.is_empty() || royalty.royalty_amount.is_zero() {
+            continue;
+        }
+        royalty_amount = royalty_amount
+            .checked_add(royalty.royalty_amount)
+            .map_err(|_| ContractError::RoyaltiesExceedSalePrice {})?;
+        payouts.push(Payout {
+            address: deps.api.addr_validate(&royalty.address)?,
+            amount: royalty.royalty_amount,
+        });
+    }
+    let seller_amount = sale_price
+        .checked_sub(royalty_amount)
+        .map_err(|_| ContractError::RoyaltiesExceedSalePrice {})?;
+
+    Ok(PayoutsResponse {
+        royalties: payouts,
+        seller_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, ContractResult, Decimal, OwnedDeps, SystemResult, WasmQuery};
+    use cw2981_royalties::MetadataWithRoyalty;
+    use cw721::msg::{Cw721QueryMsg, RoyaltySplitInfoResponse};
+    use cw_utils::PaymentError;
+
+    const CW721_ADDR: &str = "cw721";
+    const CW2981_ADDR: &str = "cw2981";
+    const CW1155_ADDR: &str = "cw1155";
+    const GREEDY_ADDR: &str = "greedy";
+    const CW20_ADDR: &str = "cw20";
+    const ARTIST: &str = "artist";
+    const GALLERY: &str = "gallery";
+    const SELLER: &str = "seller";
+
+    fn royalty(address: &str, percent: u64, sale_price: Uint128) -> RoyaltiesInfoResponse {
+        RoyaltiesInfoResponse {
+            address: address.to_string(),
+            royalty_amount: sale_price * Decimal::percent(percent),
+        }
+    }
+
+    fn mock_collections() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| {
+            let WasmQuery::Smart { contract_addr, msg } = query else {
+                panic!("unexpected query: {query:?}");
+            };
+            let res = match contract_addr.as_str() {
+                // royalties split between artist (10%) and gallery (5%)
+                CW721_ADDR => match from_json(msg).unwrap() {
+                    Cw721QueryMsg::<Empty, Empty, Empty>::RoyaltySplitInfo {
+                        sale_price, ..
+                    } => to_json_binary(&RoyaltySplitInfoResponse {
+                        royalties: vec![
+                            royalty(ARTIST, 10, sale_price),
+                            royalty(GALLERY, 5, sale_price),
+                        ],
+                    }),
+                    msg => panic!("unexpected query: {msg:?}"),
+                },
+                // token "1" pays 10% to artist, other tokens have no royalties
+                CW2981_ADDR => match from_json(msg).unwrap() {
+                    Cw2981QueryMsg::RoyaltyInfo {
+                        token_id,
+                        sale_price,
+                    } if token_id == "1" => to_json_binary(&royalty(ARTIST, 10, sale_price)),
+                    Cw2981QueryMsg::RoyaltyInfo { sale_price, .. } => {
+                        to_json_binary(&royalty("", 0, sale_price))
+                    }
+                    msg => panic!("unexpected query: {msg:?}"),
+                },
+                // artist takes 20%
+                CW1155_ADDR => match from_json(msg).unwrap() {
+                    Cw1155QueryMsg::<MetadataWithRoyalty, Cw2981QueryMsg>::Extension {
+                        msg: Cw2981QueryMsg::RoyaltyInfo { sale_price, .. },
+                        ..
+                    } => to_json_binary(&royalty(ARTIST, 20, sale_price)),
+                    msg => panic!("unexpected query: {msg:?}"),
+                },
+                // royalties exceed the sale price
+                GREEDY_ADDR => match from_json(msg).unwrap() {
+                    Cw2981QueryMsg::RoyaltyInfo { sale_price, .. } => {
+                        to_json_binary(&royalty(ARTIST, 101, sale_price))
+                    }
+                    msg => panic!("unexpected query: {msg:?}"),
+                },
+                addr => panic!("unexpected contract: {addr}"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        deps
+    }
+
+    fn bank_send(recipient: &str, amount: u128) -> CosmosMsg {
+        BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount, "uatom"),
+        }
+        .into()
+    }
+
+    fn cw20_transfer(recipient: &str, amount: u128) -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: CW20_ADDR.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    }
+
+    fn distribute_msg(collection: &str, collection_type: CollectionType) -> ExecuteMsg {
+        ExecuteMsg::Distribute {
+            collection: collection.to_string(),
+            collection_type,
+            token_id: "1".to_string(),
+            seller: SELLER.to_string(),
+        }
+    }
+
+    #[test]
+    fn distribute_native() {
+        let mut deps = mock_collections();
+
+        // all recipients of a royalty split are paid
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &coins(1000, "uatom")),
+            distribute_msg(CW721_ADDR, CollectionType::Cw721),
+        )
+        .unwrap();
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+        assert_eq!(
+            messages,
+            vec![
+                bank_send(ARTIST, 100),
+                bank_send(GALLERY, 50),
+                bank_send(SELLER, 850),
+            ]
+        );
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "royalty_amount" && attr.value == "150"));
+
+        // single recipient
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &coins(1000, "uatom")),
+            distribute_msg(CW2981_ADDR, CollectionType::Cw2981),
+        )
+        .unwrap();
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+        assert_eq!(
+            messages,
+            vec![bank_send(ARTIST, 100), bank_send(SELLER, 900)]
+        );
+
+        // no royalties owed, seller gets everything
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &coins(1000, "uatom")),
+            ExecuteMsg::Distribute {
+                collection: CW2981_ADDR.to_string(),
+                collection_type: CollectionType::Cw2981,
+                token_id: "2".to_string(),
+                seller: SELLER.to_string(),
+            },
+        )
+        .unwrap();
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+        assert_eq!(messages, vec![bank_send(SELLER, 1000)]);
+    }
+
+    #[test]
+    fn distribute_cw20() {
+        let mut deps = mock_collections();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CW20_ADDR, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "buyer".to_string(),
+                amount: Uint128::new(1000),
+                msg: to_json_binary(&ReceiveMsg {
+                    collection: CW1155_ADDR.to_string(),
+                    collection_type: CollectionType::Cw1155,
+                    token_id: "1".to_string(),
+                    seller: SELLER.to_string(),
+                })
+                .unwrap(),
+            }),
+        )
+        .unwrap();
+        let messages: Vec<CosmosMsg> = res.messages.into_iter().map(|msg| msg.msg).collect();
+        assert_eq!(
+            messages,
+            vec![cw20_transfer(ARTIST, 200), cw20_transfer(SELLER, 800)]
+        );
+    }
+
+    #[test]
+    fn distribute_errors() {
+        let mut deps = mock_collections();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &[]),
+            distribute_msg(CW721_ADDR, CollectionType::Cw721),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Payment(PaymentError::NoFunds {}));
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &[coin(1000, "uatom"), coin(1000, "ustars")]),
+            distribute_msg(CW721_ADDR, CollectionType::Cw721),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Payment(PaymentError::MultipleDenoms {}));
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("buyer", &coins(1000, "uatom")),
+            distribute_msg(GREEDY_ADDR, CollectionType::Cw2981),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::RoyaltiesExceedSalePrice {});
+    }
+
+    #[test]
+    fn get_payouts() {
+        let deps = mock_collections();
+
+        let res: PayoutsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPayouts {
+                    collection: CW721_ADDR.to_string(),
+                    collection_type: CollectionType::Cw721,
+                    token_id: "1".to_string(),
+                    sale_price: Uint128::new(1000),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            PayoutsResponse {
+                royalties: vec![
+                    Payout {
+                        address: Addr::unchecked(ARTIST),
+                        amount: Uint128::new(100),
+                    },
+                    Payout {
+                        address: Addr::unchecked(GALLERY),
+                        amount: Uint128::new(50),
+                    },
+                ],
+                seller_amount: Uint128::new(850),
+            }
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+/// Defines how royalties are queried from the NFT contract.
+#[cw_serde]
+pub enum CollectionType {
+    /// Contracts based on the cw721 package, e.g. cw721-base. Royalties are queried via
+    /// `RoyaltySplitInfo`, paying all recipients.
+    Cw721,
+    /// cw2981-royalties, royalties are queried via `RoyaltyInfo`.
+    Cw2981,
+    /// cw1155-royalties, royalties are queried via `Extension { msg: RoyaltyInfo }`.
+    Cw1155,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Distributes the sale proceeds sent along, in a single native denom. Royalties owed for
+    /// the NFT are paid to their recipients, the remainder is forwarded to the seller.
+    Distribute {
+        collection: String,
+        collection_type: CollectionType,
+        token_id: String,
+        seller: String,
+    },
+    /// Distributes CW20 sale proceeds, via CW20 `Send` with a `ReceiveMsg`.
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Message passed along a CW20 `Send`.
+#[cw_serde]
+pub struct ReceiveMsg {
+    pub collection: String,
+    pub collection_type: CollectionType,
+    pub token_id: String,
+    pub seller: String,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns how the given sale price would be distributed.
+    #[returns(PayoutsResponse)]
+    GetPayouts {
+        collection: String,
+        collection_type: CollectionType,
+        token_id: String,
+        sale_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct Payout {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PayoutsResponse {
+    /// Royalties owed, empty in case no royalties are owed.
+    pub royalties: Vec<Payout>,
+    /// Remainder of the sale price, forwarded to the seller.
+    pub seller_amount: Uint128,
+}
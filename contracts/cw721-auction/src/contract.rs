@@ -0,0 +1,437 @@
+use crate::error::ContractError;
+use crate::msg::{
+    AuctionMsg, AuctionsResponse, ExecuteMsg, InstantiateMsg, PriceResponse, QueryMsg,
+};
+use crate::state::{Auction, AuctionKind, Bid, AUCTIONS};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-auction";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => execute_receive_nft(deps, env, info, sender, token_id, msg),
+        ExecuteMsg::PlaceBid {
+            collection,
+            token_id,
+        } => execute_place_bid(deps, env, info, collection, token_id),
+        ExecuteMsg::Buy {
+            collection,
+            token_id,
+        } => execute_buy(deps, env, info, collection, token_id),
+        ExecuteMsg::Settle {
+            collection,
+            token_id,
+        } => execute_settle(deps, env, collection, token_id),
+        ExecuteMsg::Cancel {
+            collection,
+            token_id,
+        } => execute_cancel(deps, info, collection, token_id),
+    }
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let AuctionMsg {
+        denom,
+        kind,
+        start_time,
+        duration,
+    } = from_json(&msg)?;
+    if denom.is_empty() {
+        return Err(ContractError::InvalidDenom {});
+    }
+    match &kind {
+        AuctionKind::English { reserve_price, .. } => {
+            if reserve_price.is_zero() {
+                return Err(ContractError::InvalidPrice {});
+            }
+        }
+        AuctionKind::Dutch {
+            start_price,
+            end_price,
+        } => {
+            if end_price.is_zero() || start_price <= end_price {
+                return Err(ContractError::InvalidPrice {});
+            }
+        }
+    }
+    let start_time = start_time.unwrap_or(env.block.time);
+    if start_time < env.block.time {
+        return Err(ContractError::InvalidStartTime {});
+    }
+    if duration == 0 {
+        return Err(ContractError::InvalidDuration {});
+    }
+
+    // the sender is the cw721 contract, the NFT is already owned by this contract
+    let auction = Auction {
+        collection: info.sender,
+        token_id,
+        seller: deps.api.addr_validate(&sender)?,
+        denom,
+        kind,
+        start_time,
+        end_time: start_time.plus_seconds(duration),
+        highest_bid: None,
+    };
+    AUCTIONS.save(
+        deps.storage,
+        (&auction.collection, &auction.token_id),
+        &auction,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_auction")
+        .add_attribute("collection", auction.collection)
+        .add_attribute("token_id", auction.token_id)
+        .add_attribute("seller", auction.seller)
+        .add_attribute("start_time", auction.start_time.to_string())
+        .add_attribute("end_time", auction.end_time.to_string()))
+}
+
+pub fn execute_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let mut auction = AUCTIONS.load(deps.storage, (&collection, &token_id))?;
+    let AuctionKind::English { time_extension, .. } = auction.kind else {
+        return Err(ContractError::WrongAuctionKind {});
+    };
+    if env.block.time < auction.start_time {
+        return Err(ContractError::AuctionNotStarted {});
+    }
+    if env.block.time >= auction.end_time {
+        return Err(ContractError::AuctionEnded {});
+    }
+    let amount = must_pay(&info, &auction.denom)?;
+    let min_bid = min_bid(&auction)?;
+    if amount < min_bid {
+        return Err(ContractError::BidTooLow { min_bid });
+    }
+
+    // refund previous highest bidder
+    let mut messages = vec![];
+    if let Some(previous) = auction.highest_bid.take() {
+        messages.push(send_msg(&auction.denom, previous.bidder, previous.amount));
+    }
+    auction.highest_bid = Some(Bid {
+        bidder: info.sender.clone(),
+        amount,
+    });
+    // anti-sniping: bids shortly before the end extend the auction
+    let extended_end_time = env.block.time.plus_seconds(time_extension);
+    if extended_end_time > auction.end_time {
+        auction.end_time = extended_end_time;
+    }
+    AUCTIONS.save(deps.storage, (&collection, &token_id), &auction)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "place_bid")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("bidder", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("end_time", auction.end_time.to_string()))
+}
+
+pub fn execute_buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let auction = AUCTIONS.load(deps.storage, (&collection, &token_id))?;
+    let price = dutch_price(&auction, &env)?;
+    let amount = must_pay(&info, &auction.denom)?;
+    if amount < price {
+        return Err(ContractError::BidTooLow { min_bid: price });
+    }
+
+    let mut messages = settle_msgs(deps.as_ref(), &auction, &info.sender, price)?;
+    // refund excess funds, since the price decays with every block
+    if amount > price {
+        messages.push(send_msg(
+            &auction.denom,
+            info.sender.clone(),
+            amount - price,
+        ));
+    }
+    AUCTIONS.remove(deps.storage, (&collection, &token_id));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "buy")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("buyer", info.sender)
+        .add_attribute("price", price))
+}
+
+pub fn execute_settle(
+    deps: DepsMut,
+    env: Env,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let auction = AUCTIONS.load(deps.storage, (&collection, &token_id))?;
+    if !matches!(auction.kind, AuctionKind::English { .. }) {
+        return Err(ContractError::WrongAuctionKind {});
+    }
+    if env.block.time < auction.end_time {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+
+    let res = match &auction.highest_bid {
+        Some(bid) => Response::new()
+            .add_messages(settle_msgs(
+                deps.as_ref(),
+                &auction,
+                &bid.bidder,
+                bid.amount,
+            )?)
+            .add_attribute("winner", bid.bidder.to_string())
+            .add_attribute("price", bid.amount),
+        // no bids, return NFT to seller
+        None => Response::new().add_message(transfer_nft_msg(&auction, &auction.seller)?),
+    };
+    AUCTIONS.remove(deps.storage, (&collection, &token_id));
+
+    Ok(res
+        .add_attribute("action", "settle")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let auction = AUCTIONS.load(deps.storage, (&collection, &token_id))?;
+    if auction.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if auction.highest_bid.is_some() {
+        return Err(ContractError::AuctionHasBids {});
+    }
+    AUCTIONS.remove(deps.storage, (&collection, &token_id));
+
+    // return NFT to seller
+    Ok(Response::new()
+        .add_message(transfer_nft_msg(&auction, &auction.seller)?)
+        .add_attribute("action", "cancel")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id))
+}
+
+/// Returns the minimum amount of the next bid on an English auction.
+fn min_bid(auction: &Auction) -> Result<Uint128, ContractError> {
+    let AuctionKind::English {
+        reserve_price,
+        min_bid_increment,
+        ..
+    } = auction.kind
+    else {
+        return Err(ContractError::WrongAuctionKind {});
+    };
+    Ok(match &auction.highest_bid {
+        // a bid must always exceed the highest bid, even without increment
+        Some(bid) => bid.amount + min_bid_increment.max(Uint128::one()),
+        None => reserve_price,
+    })
+}
+
+/// Returns the current price of a Dutch auction.
+fn dutch_price(auction: &Auction, env: &Env) -> Result<Uint128, ContractError> {
+    let AuctionKind::Dutch {
+        start_price,
+        end_price,
+    } = auction.kind
+    else {
+        return Err(ContractError::WrongAuctionKind {});
+    };
+    if env.block.time < auction.start_time {
+        return Err(ContractError::AuctionNotStarted {});
+    }
+    if env.block.time >= auction.end_time {
+        return Ok(end_price);
+    }
+    let elapsed = env.block.time.seconds() - auction.start_time.seconds();
+    let duration = auction.end_time.seconds() - auction.start_time.seconds();
+    let decay = (start_price - end_price).multiply_ratio(elapsed, duration);
+    Ok(start_price - decay)
+}
+
+/// Pays royalties and the seller, and transfers the NFT to the buyer.
+fn settle_msgs(
+    deps: Deps,
+    auction: &Auction,
+    buyer: &Addr,
+    price: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let cw721 = DefaultCw721Helper::new(auction.collection.clone());
+    let mut messages = vec![];
+    let mut royalty_amount = Uint128::zero();
+    for (recipient, amount) in
+        cw721.royalty_payouts(deps.api, &deps.querier, &auction.token_id, price)?
+    {
+        royalty_amount = royalty_amount
+            .checked_add(amount)
+            .map_err(|_| ContractError::RoyaltiesExceedPrice {})?;
+        messages.push(send_msg(&auction.denom, recipient, amount));
+    }
+    let seller_amount = price
+        .checked_sub(royalty_amount)
+        .map_err(|_| ContractError::RoyaltiesExceedPrice {})?;
+    if !seller_amount.is_zero() {
+        messages.push(send_msg(
+            &auction.denom,
+            auction.seller.clone(),
+            seller_amount,
+        ));
+    }
+    messages.push(transfer_nft_msg(auction, buyer)?);
+    Ok(messages)
+}
+
+fn send_msg(denom: &str, recipient: Addr, amount: Uint128) -> CosmosMsg {
+    BankMsg::Send {
+        to_address: recipient.into_string(),
+        amount: vec![Coin {
+            denom: denom.to_string(),
+            amount,
+        }],
+    }
+    .into()
+}
+
+fn transfer_nft_msg(auction: &Auction, recipient: &Addr) -> StdResult<CosmosMsg> {
+    DefaultCw721Helper::new(auction.collection.clone()).call(Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: auction.token_id.clone(),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Auction {
+            collection,
+            token_id,
+        } => Ok(to_json_binary(&query_auction(deps, collection, token_id)?)?),
+        QueryMsg::Auctions {
+            collection,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_auctions(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::Price {
+            collection,
+            token_id,
+        } => Ok(to_json_binary(&query_price(
+            deps, env, collection, token_id,
+        )?)?),
+    }
+}
+
+pub fn query_auction(
+    deps: Deps,
+    collection: String,
+    token_id: String,
+) -> StdResult<Option<Auction>> {
+    let collection = deps.api.addr_validate(&collection)?;
+    AUCTIONS.may_load(deps.storage, (&collection, &token_id))
+}
+
+pub fn query_auctions(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let auctions = AUCTIONS
+        .prefix(&collection)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, auction)| auction))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AuctionsResponse { auctions })
+}
+
+pub fn query_price(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    token_id: String,
+) -> Result<PriceResponse, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let auction = AUCTIONS.load(deps.storage, (&collection, &token_id))?;
+    let price = match auction.kind {
+        AuctionKind::English { .. } => min_bid(&auction)?,
+        AuctionKind::Dutch { .. } => dutch_price(&auction, &env)?,
+    };
+    Ok(PriceResponse { price })
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("InvalidPrice")]
+    InvalidPrice {},
+
+    #[error("InvalidDenom")]
+    InvalidDenom {},
+
+    #[error("InvalidStartTime")]
+    InvalidStartTime {},
+
+    #[error("InvalidDuration")]
+    InvalidDuration {},
+
+    #[error("WrongAuctionKind")]
+    WrongAuctionKind {},
+
+    #[error("AuctionNotStarted")]
+    AuctionNotStarted {},
+
+    #[error("AuctionEnded")]
+    AuctionEnded {},
+
+    #[error("AuctionNotEnded")]
+    AuctionNotEnded {},
+
+    #[error("Bid too low, minimum bid is {min_bid}")]
+    BidTooLow { min_bid: Uint128 },
+
+    #[error("AuctionHasBids")]
+    AuctionHasBids {},
+
+    #[error("RoyaltiesExceedPrice")]
+    RoyaltiesExceedPrice {},
+}
@@ -0,0 +1,82 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Timestamp, Uint128};
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::{Auction, AuctionKind};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Start an auction, via cw721 `SendNft` with an `AuctionMsg`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Bid on an English auction, funds must be in the auction's denom. The previous highest bid
+    /// is refunded.
+    PlaceBid {
+        collection: String,
+        token_id: String,
+    },
+    /// Buy from a Dutch auction at the current price, any excess funds are refunded.
+    Buy {
+        collection: String,
+        token_id: String,
+    },
+    /// Settle an English auction once it has ended. The NFT is transferred to the highest bidder,
+    /// or returned to the seller in case there are no bids. Anyone can settle.
+    Settle {
+        collection: String,
+        token_id: String,
+    },
+    /// Only the seller can cancel an auction without bids, the NFT is returned to the seller.
+    Cancel {
+        collection: String,
+        token_id: String,
+    },
+}
+
+/// Auction passed along a cw721 `SendNft`.
+#[cw_serde]
+pub struct AuctionMsg {
+    pub denom: String,
+    pub kind: AuctionKind,
+    /// Defaults to the current block time.
+    pub start_time: Option<Timestamp>,
+    /// Duration in seconds.
+    pub duration: u64,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the auction for the given NFT, if any.
+    #[returns(Option<Auction>)]
+    Auction {
+        collection: String,
+        token_id: String,
+    },
+    /// Returns auctions of a collection, ordered by token id.
+    #[returns(AuctionsResponse)]
+    Auctions {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the minimum next bid of an English auction, or the current price of a Dutch
+    /// auction.
+    #[returns(PriceResponse)]
+    Price {
+        collection: String,
+        token_id: String,
+    },
+}
+
+#[cw_serde]
+pub struct AuctionsResponse {
+    pub auctions: Vec<Auction>,
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Uint128,
+}
@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::Map;
+
+#[cw_serde]
+pub enum AuctionKind {
+    /// Ascending auction, the highest bid wins once the auction has ended.
+    English {
+        /// Minimum amount of the first bid, must be non-zero.
+        reserve_price: Uint128,
+        /// Minimum amount a bid must exceed the current highest bid by.
+        min_bid_increment: Uint128,
+        /// Anti-sniping: a bid placed within this many seconds before the end extends the
+        /// auction to this many seconds after the bid.
+        time_extension: u64,
+    },
+    /// Descending auction, the price decays linearly from `start_price` at the start time to
+    /// `end_price` at the end time, and stays at `end_price` afterwards. The first buyer wins.
+    Dutch {
+        start_price: Uint128,
+        end_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+/// An NFT auctioned off. The contract holds custody of the NFT until the auction is settled or
+/// cancelled.
+#[cw_serde]
+pub struct Auction {
+    pub collection: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    /// Native denom bids and payments are made in.
+    pub denom: String,
+    pub kind: AuctionKind,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    /// Highest bid of an English auction, refunded when outbid.
+    pub highest_bid: Option<Bid>,
+}
+
+/// Auctions keyed by collection and token id.
+pub const AUCTIONS: Map<(&Addr, &str), Auction> = Map::new("auctions");
@@ -0,0 +1 @@
+mod multi_tests;
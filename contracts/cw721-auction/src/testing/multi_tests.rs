@@ -0,0 +1,477 @@
+use crate::{
+    contract::{execute, instantiate, query},
+    msg::{AuctionMsg, ExecuteMsg, InstantiateMsg, PriceResponse, QueryMsg},
+    state::{Auction, AuctionKind, Bid},
+    ContractError,
+};
+use cosmwasm_std::{coins, to_json_binary, Addr, Decimal, Empty, Uint128};
+use cw721::msg::{CollectionExtensionMsg, RoyaltyInfoResponse};
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_nfts_multi_test::{fund, instantiate_cw721, query_owner, DENOM};
+
+struct Contracts {
+    cw721: Addr,
+    auction: Addr,
+}
+
+struct Users {
+    seller: Addr,
+    artist: Addr,
+    bidder1: Addr,
+    bidder2: Addr,
+}
+
+fn auction_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn users(app: &App) -> Users {
+    Users {
+        seller: app.api().addr_make("seller"),
+        artist: app.api().addr_make("artist"),
+        bidder1: app.api().addr_make("bidder1"),
+        bidder2: app.api().addr_make("bidder2"),
+    }
+}
+
+/// Creates a collection with 5% royalties for the artist and token "1" owned by the seller, and
+/// the auction contract. Both bidders have a balance of 1000.
+fn setup(app: &mut App) -> Contracts {
+    let Users {
+        seller,
+        artist,
+        bidder1,
+        bidder2,
+    } = users(app);
+
+    let cw721 = instantiate_cw721(
+        app,
+        &seller,
+        Some(CollectionExtensionMsg {
+            description: None,
+            image: None,
+            external_link: None,
+            explicit_content: None,
+            start_trading_time: None,
+            royalty_info: Some(RoyaltyInfoResponse {
+                payment_address: artist.to_string(),
+                share: Decimal::percent(5),
+            }),
+        }),
+        &[("1", &seller)],
+    );
+
+    let auction_code_id = app.store_code(auction_contract());
+    let auction = app
+        .instantiate_contract(
+            auction_code_id,
+            seller,
+            &InstantiateMsg {},
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap();
+
+    for bidder in [bidder1, bidder2] {
+        fund(app, &bidder, 1000);
+    }
+
+    Contracts { cw721, auction }
+}
+
+fn start_auction(app: &mut App, contracts: &Contracts, kind: AuctionKind, duration: u64) {
+    let Users { seller, .. } = users(app);
+    app.execute_contract(
+        seller,
+        contracts.cw721.clone(),
+        &Cw721ExecuteMsg::SendNft {
+            contract: contracts.auction.to_string(),
+            token_id: "1".to_string(),
+            msg: to_json_binary(&AuctionMsg {
+                denom: DENOM.to_string(),
+                kind,
+                start_time: None,
+                duration,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn english(reserve_price: u128, min_bid_increment: u128, time_extension: u64) -> AuctionKind {
+    AuctionKind::English {
+        reserve_price: Uint128::new(reserve_price),
+        min_bid_increment: Uint128::new(min_bid_increment),
+        time_extension,
+    }
+}
+
+fn place_bid_msg(contracts: &Contracts) -> ExecuteMsg {
+    ExecuteMsg::PlaceBid {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+    }
+}
+
+fn settle_msg(contracts: &Contracts) -> ExecuteMsg {
+    ExecuteMsg::Settle {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+    }
+}
+
+fn query_auction(app: &App, contracts: &Contracts) -> Option<Auction> {
+    app.wrap()
+        .query_wasm_smart(
+            &contracts.auction,
+            &QueryMsg::Auction {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap()
+}
+
+fn query_price(app: &App, contracts: &Contracts) -> Uint128 {
+    let res: PriceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contracts.auction,
+            &QueryMsg::Price {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    res.price
+}
+
+fn balance(app: &App, address: &Addr) -> Uint128 {
+    app.wrap().query_balance(address, DENOM).unwrap().amount
+}
+
+#[test]
+fn test_english_auction() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users {
+        seller,
+        artist,
+        bidder1,
+        bidder2,
+    } = users(&app);
+
+    start_auction(&mut app, &contracts, english(100, 10, 60), 600);
+    let end_time = app.block_info().time.plus_seconds(600);
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), contracts.auction);
+    assert_eq!(query_price(&app, &contracts), Uint128::new(100));
+
+    // first bid must meet the reserve price
+    let err = app
+        .execute_contract(
+            bidder1.clone(),
+            contracts.auction.clone(),
+            &place_bid_msg(&contracts),
+            &coins(50, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BidTooLow {
+            min_bid: Uint128::new(100)
+        }
+    );
+    app.execute_contract(
+        bidder1.clone(),
+        contracts.auction.clone(),
+        &place_bid_msg(&contracts),
+        &coins(100, DENOM),
+    )
+    .unwrap();
+
+    // next bid must exceed the highest bid by the increment
+    let err = app
+        .execute_contract(
+            bidder2.clone(),
+            contracts.auction.clone(),
+            &place_bid_msg(&contracts),
+            &coins(105, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BidTooLow {
+            min_bid: Uint128::new(110)
+        }
+    );
+    app.execute_contract(
+        bidder2.clone(),
+        contracts.auction.clone(),
+        &place_bid_msg(&contracts),
+        &coins(200, DENOM),
+    )
+    .unwrap();
+    // outbid bidder is refunded
+    assert_eq!(balance(&app, &bidder1), Uint128::new(1000));
+    assert_eq!(balance(&app, &bidder2), Uint128::new(800));
+
+    // seller can't cancel an auction with bids
+    let err = app
+        .execute_contract(
+            seller.clone(),
+            contracts.auction.clone(),
+            &ExecuteMsg::Cancel {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AuctionHasBids {}
+    );
+
+    // anti-sniping: a bid shortly before the end extends the auction
+    app.update_block(|block| block.time = end_time.minus_seconds(30));
+    app.execute_contract(
+        bidder1.clone(),
+        contracts.auction.clone(),
+        &place_bid_msg(&contracts),
+        &coins(300, DENOM),
+    )
+    .unwrap();
+    let extended_end_time = end_time.plus_seconds(30);
+    assert_eq!(
+        query_auction(&app, &contracts).unwrap(),
+        Auction {
+            collection: contracts.cw721.clone(),
+            token_id: "1".to_string(),
+            seller: seller.clone(),
+            denom: DENOM.to_string(),
+            kind: english(100, 10, 60),
+            start_time: end_time.minus_seconds(600),
+            end_time: extended_end_time,
+            highest_bid: Some(Bid {
+                bidder: bidder1.clone(),
+                amount: Uint128::new(300),
+            }),
+        }
+    );
+
+    app.update_block(|block| block.time = end_time);
+    let err = app
+        .execute_contract(
+            bidder1.clone(),
+            contracts.auction.clone(),
+            &settle_msg(&contracts),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AuctionNotEnded {}
+    );
+
+    // anyone can settle once ended, royalties are paid to the artist
+    app.update_block(|block| block.time = extended_end_time);
+    let err = app
+        .execute_contract(
+            bidder2.clone(),
+            contracts.auction.clone(),
+            &place_bid_msg(&contracts),
+            &coins(400, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::AuctionEnded {}
+    );
+    app.execute_contract(
+        bidder2.clone(),
+        contracts.auction.clone(),
+        &settle_msg(&contracts),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), bidder1);
+    assert_eq!(query_auction(&app, &contracts), None);
+    assert_eq!(balance(&app, &artist), Uint128::new(15));
+    assert_eq!(balance(&app, &seller), Uint128::new(285));
+    assert_eq!(balance(&app, &bidder1), Uint128::new(700));
+    assert_eq!(balance(&app, &bidder2), Uint128::new(1000));
+    assert_eq!(balance(&app, &contracts.auction), Uint128::zero());
+}
+
+#[test]
+fn test_english_auction_without_bids() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users {
+        seller, bidder1, ..
+    } = users(&app);
+
+    start_auction(&mut app, &contracts, english(100, 10, 60), 600);
+    app.update_block(|block| block.time = block.time.plus_seconds(600));
+    app.execute_contract(
+        bidder1,
+        contracts.auction.clone(),
+        &settle_msg(&contracts),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), seller);
+    assert_eq!(query_auction(&app, &contracts), None);
+}
+
+#[test]
+fn test_dutch_auction() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users {
+        seller,
+        artist,
+        bidder1,
+        ..
+    } = users(&app);
+
+    start_auction(
+        &mut app,
+        &contracts,
+        AuctionKind::Dutch {
+            start_price: Uint128::new(1000),
+            end_price: Uint128::new(100),
+        },
+        900,
+    );
+    assert_eq!(query_price(&app, &contracts), Uint128::new(1000));
+
+    // bids are only for English auctions
+    let err = app
+        .execute_contract(
+            bidder1.clone(),
+            contracts.auction.clone(),
+            &place_bid_msg(&contracts),
+            &coins(1000, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WrongAuctionKind {}
+    );
+
+    // price decays linearly
+    app.update_block(|block| block.time = block.time.plus_seconds(300));
+    assert_eq!(query_price(&app, &contracts), Uint128::new(700));
+    let buy_msg = ExecuteMsg::Buy {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = app
+        .execute_contract(
+            bidder1.clone(),
+            contracts.auction.clone(),
+            &buy_msg,
+            &coins(600, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BidTooLow {
+            min_bid: Uint128::new(700)
+        }
+    );
+
+    // excess funds are refunded
+    app.execute_contract(
+        bidder1.clone(),
+        contracts.auction.clone(),
+        &buy_msg,
+        &coins(800, DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), bidder1);
+    assert_eq!(query_auction(&app, &contracts), None);
+    assert_eq!(balance(&app, &artist), Uint128::new(35));
+    assert_eq!(balance(&app, &seller), Uint128::new(665));
+    assert_eq!(balance(&app, &bidder1), Uint128::new(300));
+}
+
+#[test]
+fn test_invalid_auction() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users { seller, .. } = users(&app);
+
+    let send_nft_msg = |denom: &str, kind: AuctionKind| Cw721ExecuteMsg::SendNft {
+        contract: contracts.auction.to_string(),
+        token_id: "1".to_string(),
+        msg: to_json_binary(&AuctionMsg {
+            denom: denom.to_string(),
+            kind,
+            start_time: None,
+            duration: 600,
+        })
+        .unwrap(),
+    };
+
+    // denom must not be empty
+    let err = app
+        .execute_contract(
+            seller.clone(),
+            contracts.cw721.clone(),
+            &send_nft_msg("", english(100, 10, 60)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidDenom {}
+    );
+
+    // English auctions require a reserve price
+    let err = app
+        .execute_contract(
+            seller.clone(),
+            contracts.cw721.clone(),
+            &send_nft_msg(DENOM, english(0, 10, 60)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidPrice {}
+    );
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), seller);
+    assert_eq!(query_auction(&app, &contracts), None);
+}
+
+#[test]
+fn test_cancel() {
+    let mut app = App::default();
+    let contracts = setup(&mut app);
+    let Users {
+        seller, bidder1, ..
+    } = users(&app);
+
+    start_auction(&mut app, &contracts, english(100, 10, 60), 600);
+    let cancel_msg = ExecuteMsg::Cancel {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = app
+        .execute_contract(bidder1, contracts.auction.clone(), &cancel_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    app.execute_contract(seller.clone(), contracts.auction.clone(), &cancel_msg, &[])
+        .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), seller);
+    assert_eq!(query_auction(&app, &contracts), None);
+}
@@ -97,6 +97,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         contract
             .instantiate(deps.as_mut(), &mock_env(), &info.clone(), init_msg)
@@ -106,7 +107,7 @@ mod tests {
         let token_uri = Some("https://starships.example.com/Starship/Enterprise.json".into());
         let extension = Some(Empty {});
         let exec_msg = ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: extension.clone(),
@@ -1,7 +1,8 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, CosmosMsg, DepsMut, Response, StdError, WasmMsg,
+    coin, coins, from_json, to_json_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, Response,
+    StdError, WasmMsg,
 };
 
 use cw721::error::Cw721ContractError;
@@ -14,10 +15,12 @@ use cw721::receiver::Cw721ReceiveMsg;
 use cw721::state::{CREATOR, MINTER};
 use cw721::{traits::Cw721Query, Approval, Expiration};
 use cw_ownable::{Action, Ownership, OwnershipError};
+use cw_utils::PaymentError;
 
 use crate::state::DefaultCw721ExpirationContract;
 use crate::{
-    error::ContractError, msg::InstantiateMsg, msg::QueryMsg, DefaultOptionalNftExtension,
+    error::ContractError, msg::ExecuteExtensionMsg, msg::InstantiateMsg, msg::QueryMsg,
+    DefaultOptionalNftExtension,
 };
 
 const MINTER_ADDR: &str = "minter";
@@ -38,6 +41,8 @@ fn setup_contract(
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: None,
+        max_supply: None,
+        renewal_price: None,
     };
     let info = mock_info("creator", &[]);
     let res = contract.instantiate(deps, mock_env(), info, msg).unwrap();
@@ -58,6 +63,7 @@ fn proper_instantiation() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_supply: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -121,6 +127,7 @@ fn proper_instantiation_with_collection_info() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_supply: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -180,7 +187,7 @@ fn test_mint() {
     let token_uri = "https://www.merriam-webster.com/dictionary/atomize".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
@@ -251,7 +258,7 @@ fn test_mint() {
 
     // Cannot mint same token_id again
     let mint_msg2 = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("hercules"),
         token_uri: None,
         extension: None,
@@ -280,7 +287,7 @@ fn test_update_minter() {
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id,
+        token_id: Some(token_id),
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
@@ -345,7 +352,7 @@ fn test_update_minter() {
     assert_eq!(minter_ownership.owner, Some(random_info.sender.clone()));
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: "randoms_token".to_string(),
+        token_id: Some("randoms_token".to_string()),
         owner: String::from("medusa"),
         token_uri: Some(token_uri),
         extension: None,
@@ -372,7 +379,7 @@ fn test_burn() {
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: MINTER_ADDR.to_string(),
         token_uri: Some(token_uri),
         extension: None,
@@ -444,7 +451,7 @@ fn test_burn() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -460,7 +467,7 @@ fn test_transfer_nft() {
 
     let owner = "owner";
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from(owner),
         token_uri: Some(token_uri),
         extension: None,
@@ -525,7 +532,7 @@ fn test_transfer_nft() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -540,7 +547,7 @@ fn test_send_nft() {
     let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("venus"),
         token_uri: Some(token_uri),
         extension: None,
@@ -611,7 +618,7 @@ fn test_send_nft() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -626,7 +633,7 @@ fn test_approve_revoke() {
     let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri),
         extension: None,
@@ -785,7 +792,7 @@ fn test_approve_revoke() {
         ContractError::NftExpired {
             token_id: token_id.clone(),
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 
@@ -798,7 +805,7 @@ fn test_approve_revoke() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -816,7 +823,7 @@ fn test_approve_all_revoke_all() {
     let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
 
     let mint_msg1 = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri1),
         extension: None,
@@ -828,7 +835,7 @@ fn test_approve_all_revoke_all() {
         .unwrap();
 
     let mint_msg2 = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri2),
         extension: None,
@@ -1106,7 +1113,7 @@ fn test_tokens_by_owner() {
     let token_id3 = "sing".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
@@ -1116,7 +1123,7 @@ fn test_tokens_by_owner() {
         .unwrap();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: ceres.clone(),
         token_uri: None,
         extension: None,
@@ -1126,7 +1133,7 @@ fn test_tokens_by_owner() {
         .unwrap();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id3.clone(),
+        token_id: Some(token_id3.clone()),
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
@@ -1213,7 +1220,7 @@ fn test_nft_info() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner,
         token_uri: None,
         extension: None,
@@ -1239,7 +1246,7 @@ fn test_nft_info() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -1255,7 +1262,7 @@ fn test_all_nft_info() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner,
         token_uri: None,
         extension: None,
@@ -1287,7 +1294,7 @@ fn test_all_nft_info() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -1303,7 +1310,7 @@ fn test_owner_of() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner,
         token_uri: None,
         extension: None,
@@ -1335,7 +1342,7 @@ fn test_owner_of() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -1351,7 +1358,7 @@ fn test_approval() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: owner.clone(),
         token_uri: None,
         extension: None,
@@ -1391,7 +1398,7 @@ fn test_approval() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -1407,7 +1414,7 @@ fn test_approvals() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner,
         token_uri: None,
         extension: None,
@@ -1439,7 +1446,7 @@ fn test_approvals() {
         ContractError::NftExpired {
             token_id,
             mint_date,
-            expiration
+            expiration: Expiration::AtTime(expiration),
         }
     );
 }
@@ -1455,7 +1462,7 @@ fn test_tokens() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: owner.clone(),
         token_uri: None,
         extension: None,
@@ -1514,7 +1521,7 @@ fn test_all_tokens() {
 
     let mut env = mock_env();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: owner.clone(),
         token_uri: None,
         extension: None,
@@ -1547,3 +1554,428 @@ fn test_all_tokens() {
         }
     );
 }
+
+#[test]
+fn test_prune_expired() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let mut env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: Some(token_id.to_string()),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+    };
+    let prune = |start_after: Option<&str>, limit: Option<u32>| Cw721ExecuteMsg::UpdateExtension {
+        msg: ExecuteExtensionMsg::PruneExpired {
+            start_after: start_after.map(str::to_string),
+            limit,
+        },
+    };
+
+    contract
+        .execute(deps.as_mut(), env.clone(), minter.clone(), mint("grow"))
+        .unwrap();
+    env.block.time = env.block.time.plus_hours(12);
+    contract
+        .execute(deps.as_mut(), env.clone(), minter.clone(), mint("melt"))
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint("burn"))
+        .unwrap();
+    // burned NFTs are ignored
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "burn".to_string(),
+            },
+        )
+        .unwrap();
+
+    // nothing expired yet
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("random", &[]),
+            prune(None, None),
+        )
+        .unwrap();
+    assert_eq!(res.attributes[1], ("count", "0"));
+    assert_eq!(res.attributes.len(), 2);
+
+    // first NFT expired, anyone can prune
+    env.block.time = env.block.time.plus_hours(12);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("random", &[]),
+            prune(None, Some(1)),
+        )
+        .unwrap();
+    assert_eq!(res.attributes[1], ("token_id", "grow"));
+    assert_eq!(res.attributes[2], ("count", "1"));
+    assert_eq!(res.attributes[3], ("last_token_id", "grow"));
+
+    // next call continues after the last checked NFT, which is not expired yet
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("random", &[]),
+            prune(Some("grow"), Some(1)),
+        )
+        .unwrap();
+    assert_eq!(res.attributes[1], ("count", "0"));
+    assert_eq!(res.attributes[2], ("last_token_id", "melt"));
+    let count = contract
+        .base_contract
+        .query_num_tokens(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(count.count, 1);
+    contract
+        .query_nft_info_include_expired_nft(deps.as_ref(), env.clone(), "grow".to_string(), true)
+        .unwrap_err();
+
+    // limit is respected
+    env.block.time = env.block.time.plus_hours(12);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("random", &[]),
+            prune(None, Some(0)),
+        )
+        .unwrap();
+    assert_eq!(res.attributes[1], ("count", "0"));
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info("random", &[]),
+            prune(None, Some(1)),
+        )
+        .unwrap();
+    assert_eq!(res.attributes[1], ("token_id", "melt"));
+    let count = contract
+        .base_contract
+        .query_num_tokens(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(count.count, 0);
+}
+
+#[test]
+fn test_prune_expired_keeps_nft_on_failed_expiration_lookup() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let mut env = mock_env();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: Some("grow".to_string()),
+                owner: "venus".to_string(),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    // expiration can't be determined, so the NFT is not burned
+    contract.expiration_days.remove(deps.as_mut().storage);
+    env.block.time = env.block.time.plus_days(2);
+    contract
+        .execute(
+            deps.as_mut(),
+            env,
+            mock_info("random", &[]),
+            Cw721ExecuteMsg::UpdateExtension {
+                msg: ExecuteExtensionMsg::PruneExpired {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap_err();
+    let count = contract
+        .base_contract
+        .query_num_tokens(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(count.count, 1);
+}
+
+#[test]
+fn test_renew() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let mut env = mock_env();
+    let token_id = "grow".to_string();
+    let renew = Cw721ExecuteMsg::UpdateExtension {
+        msg: ExecuteExtensionMsg::Renew {
+            token_id: token_id.clone(),
+        },
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: Some(token_id.clone()),
+                owner: "venus".to_string(),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+
+    // only owner can renew
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("random", &[]),
+            renew.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Cw721(Cw721ContractError::Ownership(OwnershipError::NotOwner))
+    );
+
+    // renewal without price is free, and expiration starts from renewal
+    env.block.time = env.block.time.plus_hours(12);
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &[]),
+            renew.clone(),
+        )
+        .unwrap();
+    env.block.time = env.block.time.plus_hours(18);
+    contract
+        .assert_nft_expired(deps.as_ref(), &env, &token_id)
+        .unwrap();
+
+    // only creator can set renewal price
+    let set_price = |renewal_price: Option<Coin>| Cw721ExecuteMsg::UpdateExtension {
+        msg: ExecuteExtensionMsg::SetRenewalPrice { renewal_price },
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            set_price(Some(coin(100, "uatom"))),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Cw721(Cw721ContractError::NotCreator {}));
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR_ADDR, &[]),
+            set_price(Some(coin(0, "uatom"))),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidRenewalPrice {});
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR_ADDR, &[]),
+            set_price(Some(coin(100, "uatom"))),
+        )
+        .unwrap();
+    let price: Option<Coin> = from_json(
+        contract
+            .query(deps.as_ref(), env.clone(), QueryMsg::GetRenewalPrice {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(price, Some(coin(100, "uatom")));
+
+    // expired NFTs can be renewed, paying the renewal price
+    env.block.time = env.block.time.plus_days(1);
+    contract
+        .assert_nft_expired(deps.as_ref(), &env, &token_id)
+        .unwrap_err();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &[]),
+            renew.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Payment(PaymentError::NoFunds {}));
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &coins(50, "uatom")),
+            renew.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::WrongPaymentAmount {});
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &coins(100, "uatom")),
+            renew,
+        )
+        .unwrap();
+    contract
+        .assert_nft_expired(deps.as_ref(), &env, &token_id)
+        .unwrap();
+}
+
+#[test]
+fn test_nft_expiration() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 30);
+    let mut env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint = |token_id: &str, expiration: Option<Expiration>| Cw721ExecuteMsg::UpdateExtension {
+        msg: ExecuteExtensionMsg::Mint {
+            token_id: Some(token_id.to_string()),
+            owner: "venus".to_string(),
+            token_uri: None,
+            extension: None,
+            expiration,
+        },
+    };
+    let query_expiration = |deps: Deps, token_id: &str| -> Expiration {
+        let msg = QueryMsg::GetNftExpiration {
+            token_id: token_id.to_string(),
+        };
+        from_json(contract.query(deps, mock_env(), msg).unwrap()).unwrap()
+    };
+
+    // expiration must be in the future
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            mint("grow", Some(Expiration::AtHeight(env.block.height))),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidExpiration {});
+
+    // falls back to collection's expiration days
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            mint("grow", None),
+        )
+        .unwrap();
+    assert_eq!(
+        query_expiration(deps.as_ref(), "grow"),
+        Expiration::AtTime(env.block.time.plus_days(30))
+    );
+
+    // standard mint is handled the same way, without an expiration of its own
+    let standard_res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            Cw721ExecuteMsg::Mint {
+                token_id: Some("plant".to_string()),
+                owner: "venus".to_string(),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        query_expiration(deps.as_ref(), "plant"),
+        query_expiration(deps.as_ref(), "grow")
+    );
+    let expiration_attribute = |res: &Response| {
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == "expiration")
+            .cloned()
+    };
+    assert_eq!(
+        expiration_attribute(&standard_res),
+        expiration_attribute(&res)
+    );
+
+    // per token expiration by height and time
+    let monthly = Expiration::AtHeight(env.block.height + 100);
+    let daily = Expiration::AtTime(env.block.time.plus_days(1));
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter.clone(),
+            mint("melt", Some(monthly)),
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            minter,
+            mint("burn", Some(daily)),
+        )
+        .unwrap();
+    assert_eq!(query_expiration(deps.as_ref(), "melt"), monthly);
+    assert_eq!(query_expiration(deps.as_ref(), "burn"), daily);
+
+    env.block.time = env.block.time.plus_days(1);
+    let err = contract
+        .assert_nft_expired(deps.as_ref(), &env, "burn")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NftExpired {
+            token_id: "burn".to_string(),
+            mint_date: mock_env().block.time,
+            expiration: daily,
+        }
+    );
+    contract
+        .assert_nft_expired(deps.as_ref(), &env, "melt")
+        .unwrap();
+    env.block.height += 100;
+    contract
+        .assert_nft_expired(deps.as_ref(), &env, "melt")
+        .unwrap_err();
+    let tokens = contract
+        .query_all_tokens_include_expired_nft(deps.as_ref(), env.clone(), None, None, false)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["grow".to_string()]);
+
+    // renewal extends by the period between mint and expiration
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &[]),
+            Cw721ExecuteMsg::UpdateExtension {
+                msg: ExecuteExtensionMsg::Renew {
+                    token_id: "melt".to_string(),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        query_expiration(deps.as_ref(), "melt"),
+        Expiration::AtHeight(env.block.height + 100)
+    );
+}
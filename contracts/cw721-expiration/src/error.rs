@@ -1,6 +1,7 @@
 use cosmwasm_std::Timestamp;
 
 use cw721::error::Cw721ContractError;
+use cw_utils::{Expiration, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -11,13 +12,25 @@ pub enum ContractError {
     #[error(transparent)]
     Cw721(#[from] Cw721ContractError),
 
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
     #[error("A minimum expiration day of 1 must be set")]
     MinExpiration {},
 
-    #[error("Token {token_id} minted at {mint_date} expired at {expiration}")]
+    #[error("Renewal price must be greater than 0")]
+    InvalidRenewalPrice {},
+
+    #[error("Wrong payment amount for renewal")]
+    WrongPaymentAmount {},
+
+    #[error("Token {token_id} minted at {mint_date} expired ({expiration})")]
     NftExpired {
         token_id: String,
         mint_date: Timestamp,
-        expiration: Timestamp,
+        expiration: Expiration,
     },
+
+    #[error("Expiration must be in the future")]
+    InvalidExpiration {},
 }
@@ -1,14 +1,24 @@
 use crate::{
-    error::ContractError, msg::InstantiateMsg, state::DefaultCw721ExpirationContract,
+    error::ContractError,
+    msg::{ExecuteExtensionMsg, InstantiateMsg},
+    state::DefaultCw721ExpirationContract,
     CONTRACT_NAME, CONTRACT_VERSION,
 };
-use cosmwasm_std::{Binary, DepsMut, Empty, Env, MessageInfo, Response};
+use cosmwasm_std::{
+    Binary, Coin, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, Storage,
+};
 use cw721::{
-    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg},
+    error::Cw721ContractError,
+    execute::{assert_creator, assign_token_id, remove_nft},
+    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, MintMsg, TransferMsg},
+    query::{DEFAULT_LIMIT, MAX_LIMIT},
     traits::Cw721Execute,
     Expiration,
 };
 use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
+use cw_ownable::OwnershipError;
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, Duration};
 
 impl DefaultCw721ExpirationContract<'static> {
     // -- instantiate --
@@ -26,6 +36,12 @@ impl DefaultCw721ExpirationContract<'static> {
         contract
             .expiration_days
             .save(deps.storage, &msg.expiration_days)?;
+        if let Some(renewal_price) = msg.renewal_price {
+            if renewal_price.amount.is_zero() {
+                return Err(ContractError::InvalidRenewalPrice {});
+            }
+            contract.renewal_price.save(deps.storage, &renewal_price)?;
+        }
         Ok(contract.base_contract.instantiate_with_version(
             deps,
             &env,
@@ -37,6 +53,7 @@ impl DefaultCw721ExpirationContract<'static> {
                 minter: msg.minter,
                 creator: msg.creator,
                 withdraw_address: msg.withdraw_address,
+                max_supply: msg.max_supply,
             },
             CONTRACT_NAME,
             CONTRACT_VERSION,
@@ -52,7 +69,7 @@ impl DefaultCw721ExpirationContract<'static> {
         msg: Cw721ExecuteMsg<
             DefaultOptionalNftExtensionMsg,
             DefaultOptionalCollectionExtensionMsg,
-            Empty,
+            ExecuteExtensionMsg,
         >,
     ) -> Result<Response<Empty>, ContractError> {
         let contract = DefaultCw721ExpirationContract::default();
@@ -62,9 +79,8 @@ impl DefaultCw721ExpirationContract<'static> {
                 owner,
                 token_uri,
                 extension,
-            } => {
-                contract.mint_with_timestamp(deps, env, info, token_id, owner, token_uri, extension)
-            }
+            } => contract
+                .mint_with_expiration(deps, env, info, token_id, owner, token_uri, extension, None),
             Cw721ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -85,6 +101,41 @@ impl DefaultCw721ExpirationContract<'static> {
             Cw721ExecuteMsg::Burn { token_id } => {
                 contract.burn_nft_include_nft_expired(deps, env, info, token_id)
             }
+            Cw721ExecuteMsg::BatchMint { mints } => {
+                contract.batch_mint_with_timestamp(deps, env, info, mints)
+            }
+            Cw721ExecuteMsg::BatchTransfer { transfers } => {
+                contract.batch_transfer_nft_include_nft_expired(deps, env, info, transfers)
+            }
+            Cw721ExecuteMsg::BatchSend {
+                contract: recipient,
+                token_ids,
+                msg,
+            } => contract
+                .batch_send_nft_include_nft_expired(deps, env, info, recipient, token_ids, msg),
+            Cw721ExecuteMsg::BatchBurn { token_ids } => {
+                contract.batch_burn_nft_include_nft_expired(deps, env, info, token_ids)
+            }
+            Cw721ExecuteMsg::UpdateExtension { msg } => match msg {
+                ExecuteExtensionMsg::Mint {
+                    token_id,
+                    owner,
+                    token_uri,
+                    extension,
+                    expiration,
+                } => contract.mint_with_expiration(
+                    deps, env, info, token_id, owner, token_uri, extension, expiration,
+                ),
+                ExecuteExtensionMsg::PruneExpired { start_after, limit } => {
+                    contract.prune_expired(deps, env, start_after, limit)
+                }
+                ExecuteExtensionMsg::Renew { token_id } => {
+                    contract.renew(deps, env, info, token_id)
+                }
+                ExecuteExtensionMsg::SetRenewalPrice { renewal_price } => {
+                    contract.set_renewal_price(deps, info, renewal_price)
+                }
+            },
             _ => {
                 let response = contract.base_contract.execute(deps, &env, &info, msg)?;
                 Ok(response)
@@ -92,25 +143,69 @@ impl DefaultCw721ExpirationContract<'static> {
         }
     }
 
+    /// Mints an NFT, with an optional expiration overriding the collection's `expiration_days`.
+    /// Standard `Mint` is handled here as well, without an expiration of its own.
     #[allow(clippy::too_many_arguments)]
-    pub fn mint_with_timestamp(
+    pub fn mint_with_expiration(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        token_id: String,
+        token_id: Option<String>,
         owner: String,
         token_uri: Option<String>,
         extension: DefaultOptionalNftExtensionMsg,
+        expiration: Option<Expiration>,
     ) -> Result<Response<Empty>, ContractError> {
-        let mint_timstamp = env.block.time;
+        let token_id = assign_token_id(deps.storage, token_id)?;
+        self.save_mint_expiration(deps.storage, &env, &token_id, expiration)?;
+        let res = self.base_contract.mint(
+            deps.branch(),
+            &env,
+            &info,
+            Some(token_id.clone()),
+            owner,
+            token_uri,
+            extension,
+        )?;
+        let expiration = self.query_nft_expiration(deps.storage, &token_id)?;
+        Ok(res
+            .add_attribute("mint_timestamp", env.block.time.to_string())
+            .add_attribute("expiration", expiration.to_string()))
+    }
+
+    /// Stores the mint timestamp of a new NFT and, if given, its own expiration. Used by all mints,
+    /// so NFTs expire the same way, no matter whether minted by standard or extension `Mint`.
+    fn save_mint_expiration(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        expiration: Option<Expiration>,
+    ) -> Result<(), ContractError> {
         self.mint_timestamps
-            .save(deps.storage, &token_id, &mint_timstamp)?;
-        let res = self
-            .base_contract
-            .mint(deps, &env, &info, token_id, owner, token_uri, extension)?
-            .add_attribute("mint_timestamp", mint_timstamp.to_string());
-        Ok(res)
+            .save(storage, token_id, &env.block.time)?;
+        let expiration = match expiration {
+            Some(expiration) if expiration.is_expired(&env.block) => {
+                return Err(ContractError::InvalidExpiration {})
+            }
+            Some(expiration) => expiration,
+            None => return Ok(()),
+        };
+        self.token_expirations
+            .save(storage, token_id, &expiration)?;
+        let renewal_period = match expiration {
+            Expiration::AtHeight(height) => Some(Duration::Height(height - env.block.height)),
+            Expiration::AtTime(time) => {
+                Some(Duration::Time(time.seconds() - env.block.time.seconds()))
+            }
+            Expiration::Never {} => None,
+        };
+        if let Some(renewal_period) = renewal_period {
+            self.renewal_periods
+                .save(storage, token_id, &renewal_period)?;
+        }
+        Ok(())
     }
 
     pub fn approve_include_nft_expired(
@@ -173,13 +268,201 @@ impl DefaultCw721ExpirationContract<'static> {
 
     pub fn burn_nft_include_nft_expired(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         token_id: String,
     ) -> Result<Response<Empty>, ContractError> {
         self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
-        Ok(self.base_contract.burn_nft(deps, &env, &info, token_id)?)
+        let res = self
+            .base_contract
+            .burn_nft(deps.branch(), &env, &info, token_id.clone())?;
+        self.remove_expiration(deps.storage, &token_id);
+        Ok(res)
+    }
+
+    pub fn batch_mint_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        mut mints: Vec<MintMsg<DefaultOptionalNftExtensionMsg>>,
+    ) -> Result<Response<Empty>, ContractError> {
+        for mint in &mut mints {
+            let token_id = assign_token_id(deps.storage, mint.token_id.take())?;
+            self.save_mint_expiration(deps.storage, &env, &token_id, None)?;
+            mint.token_id = Some(token_id);
+        }
+        let res = self
+            .base_contract
+            .batch_mint(deps, &env, &info, mints)?
+            .add_attribute("mint_timestamp", env.block.time.to_string());
+        Ok(res)
+    }
+
+    pub fn batch_transfer_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        transfers: Vec<TransferMsg>,
+    ) -> Result<Response<Empty>, ContractError> {
+        for transfer in &transfers {
+            self.assert_nft_expired(deps.as_ref(), &env, transfer.token_id.as_str())?;
+        }
+        Ok(self
+            .base_contract
+            .batch_transfer_nft(deps, &env, &info, transfers)?)
+    }
+
+    pub fn batch_send_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+    ) -> Result<Response<Empty>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        }
+        Ok(self
+            .base_contract
+            .batch_send_nft(deps, &env, &info, contract, token_ids, msg)?)
+    }
+
+    pub fn batch_burn_nft_include_nft_expired(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<Empty>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        }
+        let res =
+            self.base_contract
+                .batch_burn_nft(deps.branch(), &env, &info, token_ids.clone())?;
+        for token_id in &token_ids {
+            self.remove_expiration(deps.storage, token_id);
+        }
+        Ok(res)
+    }
+
+    /// Checks up to `limit` NFTs in order of their token id, starting after `start_after`, and burns the
+    /// expired ones. Anyone can call this. In case `limit` NFTs were checked, the last checked token id
+    /// is returned in the `last_token_id` attribute, for continuing with the next call.
+    pub fn prune_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<Response<Empty>, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+        let token_ids = self
+            .mint_timestamps
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<String>>>()?;
+
+        let mut res = Response::new().add_attribute("action", "prune_expired");
+        let mut count = 0;
+        for token_id in &token_ids {
+            let expiration = self.query_nft_expiration(deps.storage, token_id)?;
+            if !expiration.is_expired(&env.block) {
+                continue;
+            }
+            self.remove_expiration(deps.storage, token_id);
+            // NFTs burned by previous versions of this contract left their mint timestamp behind
+            let config = &self.base_contract.config;
+            if config.nft_info.may_load(deps.storage, token_id)?.is_none() {
+                continue;
+            }
+            remove_nft(deps.storage, &env, token_id)?;
+            res = res.add_attribute("token_id", token_id);
+            count += 1;
+        }
+        res = res.add_attribute("count", count.to_string());
+        if token_ids.len() == limit {
+            if let Some(last_token_id) = token_ids.last() {
+                res = res.add_attribute("last_token_id", last_token_id);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Resets the expiration of an NFT, starting from now. Only the owner can renew an NFT.
+    pub fn renew(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<Empty>, ContractError> {
+        let token = self
+            .base_contract
+            .config
+            .nft_info
+            .load(deps.storage, &token_id)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Cw721(Cw721ContractError::Ownership(
+                OwnershipError::NotOwner,
+            )));
+        }
+        if let Some(renewal_price) = self.renewal_price.may_load(deps.storage)? {
+            let amount = must_pay(&info, &renewal_price.denom)?;
+            if amount != renewal_price.amount {
+                return Err(ContractError::WrongPaymentAmount {});
+            }
+        }
+        let renewal_timestamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &token_id, &renewal_timestamp)?;
+        if let Some(renewal_period) = self.renewal_periods.may_load(deps.storage, &token_id)? {
+            self.token_expirations.save(
+                deps.storage,
+                &token_id,
+                &renewal_period.after(&env.block),
+            )?;
+        }
+        Ok(Response::new()
+            .add_attribute("action", "renew")
+            .add_attribute("token_id", token_id)
+            .add_attribute("renewal_timestamp", renewal_timestamp.to_string()))
+    }
+
+    /// The creator is the only one eligible to set the renewal price. None makes renewal free.
+    pub fn set_renewal_price(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        renewal_price: Option<Coin>,
+    ) -> Result<Response<Empty>, ContractError> {
+        assert_creator(deps.storage, &info.sender)?;
+        let res = Response::new().add_attribute("action", "set_renewal_price");
+        match renewal_price {
+            Some(renewal_price) => {
+                if renewal_price.amount.is_zero() {
+                    return Err(ContractError::InvalidRenewalPrice {});
+                }
+                self.renewal_price.save(deps.storage, &renewal_price)?;
+                Ok(res.add_attribute("renewal_price", renewal_price.to_string()))
+            }
+            None => {
+                self.renewal_price.remove(deps.storage);
+                Ok(res)
+            }
+        }
+    }
+
+    fn remove_expiration(&self, storage: &mut dyn Storage, token_id: &str) {
+        self.mint_timestamps.remove(storage, token_id);
+        self.token_expirations.remove(storage, token_id);
+        self.renewal_periods.remove(storage, token_id);
     }
 
     // -- migrate --
@@ -22,7 +22,7 @@ pub type NftInfo = cw721::state::NftInfo<DefaultOptionalNftExtension>;
 pub mod entry {
     use crate::{
         error::ContractError,
-        msg::{InstantiateMsg, QueryMsg},
+        msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
         state::DefaultCw721ExpirationContract,
     };
 
@@ -31,9 +31,6 @@ pub mod entry {
     #[cfg(not(feature = "library"))]
     use cosmwasm_std::entry_point;
     use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
-    use cw721::{
-        msg::Cw721ExecuteMsg, DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg,
-    };
 
     // This makes a conscious choice on the various generics used by the contract
     #[cfg_attr(not(feature = "library"), entry_point)]
@@ -52,11 +49,7 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721ExecuteMsg<
-            DefaultOptionalNftExtensionMsg,
-            DefaultOptionalCollectionExtensionMsg,
-            Empty,
-        >,
+        msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
         let contract = DefaultCw721ExpirationContract::default();
         contract.execute(deps, env, info, msg)
@@ -105,6 +98,8 @@ mod tests {
                 minter: Some("minter".into()),
                 creator: Some("creator".into()),
                 withdraw_address: None,
+                max_supply: None,
+                renewal_price: None,
             },
         )
         .unwrap_err();
@@ -123,6 +118,8 @@ mod tests {
                 minter: Some("minter".into()),
                 creator: Some("creator".into()),
                 withdraw_address: None,
+                max_supply: None,
+                renewal_price: None,
             },
         )
         .unwrap();
@@ -1,17 +1,52 @@
 use crate::{DefaultOptionalNftExtension, MinterResponse};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Empty};
+use cosmwasm_std::{Addr, Coin, Empty, Uint128};
 use cw721::{
-    msg::{CollectionInfoAndExtensionResponse, Cw721ExecuteMsg, Cw721MigrateMsg},
+    msg::{
+        CollectionInfoAndExtensionResponse, Cw721ExecuteMsg, Cw721MigrateMsg, TradingStatusResponse,
+    },
     DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
-    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalNftExtensionMsg, Expiration,
 };
 use cw_ownable::Ownership;
 
-pub type ExecuteMsg =
-    Cw721ExecuteMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg, Empty>;
+pub type ExecuteMsg = Cw721ExecuteMsg<
+    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtensionMsg,
+    ExecuteExtensionMsg,
+>;
 pub type MigrateMsg = Cw721MigrateMsg;
 
+/// Expiration specific messages, executed via `ExecuteMsg::UpdateExtension { msg }`.
+#[cw_serde]
+pub enum ExecuteExtensionMsg {
+    /// Same as `ExecuteMsg::Mint`, with an optional expiration of the NFT (time or height),
+    /// overriding the collection's `expiration_days`. Only the minter can call this.
+    /// It is an extension message, since `ExecuteMsg::Mint` is shared by all cw721 contracts.
+    /// Standard `Mint` and `BatchMint` are handled the same way, without an expiration.
+    Mint {
+        token_id: Option<String>,
+        owner: String,
+        token_uri: Option<String>,
+        extension: DefaultOptionalNftExtensionMsg,
+        expiration: Option<Expiration>,
+    },
+    /// Checks up to `limit` NFTs in order of their token id, starting after `start_after`, and burns
+    /// the expired ones, so they no longer count in `NumTokens`. Anyone can call this.
+    /// In case `limit` NFTs were checked, the response has a `last_token_id` attribute to be passed
+    /// as `start_after` for checking the next NFTs.
+    PruneExpired {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Resets the expiration of an NFT, starting from now. Expired NFTs can be renewed too, unless pruned.
+    /// NFTs with their own expiration are extended by the period between mint and their expiration.
+    /// Only the owner can renew an NFT, paying the renewal price, if set.
+    Renew { token_id: String },
+    /// The creator is the only one eligible to set the renewal price. None makes renewal free.
+    SetRenewalPrice { renewal_price: Option<Coin> },
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// max 65535 days
@@ -34,6 +69,13 @@ pub struct InstantiateMsg {
     pub creator: Option<String>,
 
     pub withdraw_address: Option<String>,
+
+    /// Max number of NFTs that can ever be minted, including burned ones. None means unlimited.
+    pub max_supply: Option<u64>,
+
+    /// Optional price for renewing an NFT. Renewal is free if not set.
+    /// Renewal fees are kept by the contract and can be withdrawn using `WithdrawFunds`.
+    pub renewal_price: Option<Coin>,
 }
 
 #[cw_serde]
@@ -186,4 +228,43 @@ pub enum QueryMsg<TExtensionQueryMsg> {
 
     #[returns(Option<String>)]
     GetWithdrawAddress {},
+
+    #[returns(TradingStatusResponse)]
+    GetTradingStatus {},
+
+    #[returns(cw721::msg::RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+
+    #[returns(cw721::msg::RoyaltySplitInfoResponse)]
+    RoyaltySplitInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+
+    #[returns(cw721::msg::CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+
+    #[returns(cw721::TransferPolicy)]
+    GetTransferPolicy {},
+
+    #[returns(Option<cw721::TokenIdSequence>)]
+    GetTokenIdSequence {},
+
+    /// Returns the exact expiration of an NFT, regardless of whether it already expired.
+    #[returns(cw721::Expiration)]
+    GetNftExpiration { token_id: String },
+
+    /// Returns the price for renewing an NFT, if any.
+    #[returns(Option<Coin>)]
+    GetRenewalPrice {},
+
+    #[returns(cw721::msg::TokenHistoryResponse)]
+    TokenHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
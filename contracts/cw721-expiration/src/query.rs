@@ -1,10 +1,10 @@
-use cosmwasm_std::{to_json_binary, Binary, Deps, Empty, Env, StdResult};
+use cosmwasm_std::{to_json_binary, Binary, Deps, Empty, Env, StdResult, Storage};
 use cw721::msg::{
     AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, NftInfoResponse, OwnerOfResponse,
     TokensResponse,
 };
 use cw721::traits::Cw721Query;
-use cw721::DefaultOptionalNftExtension;
+use cw721::{DefaultOptionalNftExtension, Expiration};
 
 use crate::state::DefaultCw721ExpirationContract;
 use crate::{error::ContractError, msg::QueryMsg};
@@ -198,6 +198,49 @@ impl DefaultCw721ExpirationContract<'static> {
             QueryMsg::GetWithdrawAddress {} => Ok(to_json_binary(
                 &contract.base_contract.query_withdraw_address(deps)?,
             )?),
+            QueryMsg::GetTradingStatus {} => Ok(to_json_binary(
+                &contract.base_contract.query_trading_status(deps, &env)?,
+            )?),
+            QueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_royalty_info(deps, token_id, sale_price)?,
+            )?),
+            QueryMsg::RoyaltySplitInfo {
+                token_id,
+                sale_price,
+            } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_royalty_split_info(deps, token_id, sale_price)?,
+            )?),
+            QueryMsg::CheckRoyalties {} => Ok(to_json_binary(
+                &contract.base_contract.query_check_royalties(deps)?,
+            )?),
+            QueryMsg::GetNftExpiration { token_id } => Ok(to_json_binary(
+                &contract.query_nft_expiration(deps.storage, &token_id)?,
+            )?),
+            QueryMsg::GetRenewalPrice {} => Ok(to_json_binary(
+                &contract.renewal_price.may_load(deps.storage)?,
+            )?),
+            QueryMsg::GetTransferPolicy {} => Ok(to_json_binary(
+                &contract.base_contract.query_transfer_policy(deps)?,
+            )?),
+            QueryMsg::GetTokenIdSequence {} => Ok(to_json_binary(
+                &contract.base_contract.query_token_id_sequence(deps)?,
+            )?),
+            QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_token_history(deps, token_id, start_after, limit)?,
+            )?),
         }
     }
 
@@ -350,12 +393,26 @@ impl DefaultCw721ExpirationContract<'static> {
     }
 
     // --- helpers ---
+    /// Returns the NFT's own expiration if set on mint, otherwise mint timestamp + expiration days.
+    pub fn query_nft_expiration(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> StdResult<Expiration> {
+        if let Some(expiration) = self.token_expirations.may_load(storage, token_id)? {
+            return Ok(expiration);
+        }
+        let mint_date = self.mint_timestamps.load(storage, token_id)?;
+        let expiration_days = self.expiration_days.load(storage)?;
+        Ok(Expiration::AtTime(
+            mint_date.plus_days(expiration_days.into()),
+        ))
+    }
+
     pub fn is_nft_expired(&self, deps: Deps, env: &Env, token_id: &str) -> StdResult<bool> {
         // any non-expired token approval can send
-        let mint_date = self.mint_timestamps.load(deps.storage, token_id)?;
-        let expiration_days = self.expiration_days.load(deps.storage)?;
-        let expiration = mint_date.plus_days(expiration_days.into());
-        if env.block.time >= expiration {
+        let expiration = self.query_nft_expiration(deps.storage, token_id)?;
+        if expiration.is_expired(&env.block) {
             return Ok(false);
         }
         Ok(true)
@@ -370,9 +427,8 @@ impl DefaultCw721ExpirationContract<'static> {
     ) -> Result<(), ContractError> {
         // any non-expired token approval can send
         let mint_date = self.mint_timestamps.load(deps.storage, token_id)?;
-        let expiration_days = self.expiration_days.load(deps.storage)?;
-        let expiration = mint_date.plus_days(expiration_days.into());
-        if env.block.time >= expiration {
+        let expiration = self.query_nft_expiration(deps.storage, token_id)?;
+        if expiration.is_expired(&env.block) {
             return Err(ContractError::NftExpired {
                 token_id: token_id.to_string(),
                 mint_date,
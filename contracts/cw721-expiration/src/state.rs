@@ -1,20 +1,46 @@
-use cosmwasm_std::Timestamp;
+use cosmwasm_std::{Coin, Empty, Timestamp};
 
-use cw721::extension::Cw721OnchainExtensions;
+use cw721::{
+    extension::Cw721Extensions, DefaultOptionalCollectionExtension,
+    DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtension,
+    DefaultOptionalNftExtensionMsg, Expiration,
+};
 use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+
+use crate::msg::ExecuteExtensionMsg;
+
+/// Base cw721 contract of `DefaultCw721ExpirationContract`, which handles `ExecuteExtensionMsg` itself.
+pub type Cw721ExpirationBaseContract<'a> = Cw721Extensions<
+    'a,
+    DefaultOptionalNftExtension,
+    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtension,
+    DefaultOptionalCollectionExtensionMsg,
+    ExecuteExtensionMsg,
+    Empty,
+    Empty,
+>;
 
 /// Opionated version of generic `Cw721ExpirationContract` with default onchain nft and collection extensions using:
 /// - `DefaultOptionalNftExtension` for NftInfo extension (onchain metadata).
 /// - `DefaultOptionalNftExtensionMsg` for NftInfo extension msg for onchain metadata.
 /// - `DefaultOptionalCollectionExtension` for CollectionInfo extension (onchain attributes).
 /// - `DefaultOptionalCollectionExtensionMsg` for CollectionInfo extension msg for onchain collection attributes.
-/// - `Empty` for custom extension msg for custom contract logic.
+/// - `ExecuteExtensionMsg` for custom extension msg for pruning and renewing NFTs.
 /// - `Empty` for custom query msg for custom contract logic.
 /// - `Empty` for custom response msg for custom contract logic.
 pub struct DefaultCw721ExpirationContract<'a> {
     pub expiration_days: Item<'a, u16>, // max 65535 days
+    /// Timestamp from which expiration is calculated: mint or, if renewed, last renewal.
     pub mint_timestamps: Map<'a, &'a str, Timestamp>,
-    pub base_contract: Cw721OnchainExtensions<'a>,
+    /// Optional expiration of an NFT set on mint, overriding `expiration_days`.
+    pub token_expirations: Map<'a, &'a str, Expiration>,
+    /// Period by which an NFT with its own expiration is extended on renewal.
+    pub renewal_periods: Map<'a, &'a str, Duration>,
+    /// Optional price for renewing an NFT. Renewal is free if not set.
+    pub renewal_price: Item<'a, Coin>,
+    pub base_contract: Cw721ExpirationBaseContract<'a>,
 }
 
 impl Default for DefaultCw721ExpirationContract<'static> {
@@ -22,7 +48,10 @@ impl Default for DefaultCw721ExpirationContract<'static> {
         Self {
             expiration_days: Item::new("expiration_days"),
             mint_timestamps: Map::new("mint_timestamps"),
-            base_contract: Cw721OnchainExtensions::default(),
+            token_expirations: Map::new("token_expirations"),
+            renewal_periods: Map::new("renewal_periods"),
+            renewal_price: Item::new("renewal_price"),
+            base_contract: Cw721ExpirationBaseContract::default(),
         }
     }
 }
@@ -1,12 +1,13 @@
 use cosmwasm_schema::write_api;
 
 use cw721::DefaultOptionalCollectionExtension;
-use cw721_fixed_price::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw721_fixed_price::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg<DefaultOptionalCollectionExtension>,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
 }
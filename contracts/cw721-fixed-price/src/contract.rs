@@ -1,11 +1,16 @@
 use crate::error::ContractError;
-use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG};
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, MintPhasesResponse, QueryMsg,
+    ReceiveMsg, RemainingMintsResponse, UpdateConfigMsg,
+};
+use crate::state::{
+    Config, MintPhase, CONFIG, LEGACY_CONFIG, MINTED_PER_ADDRESS, MINTED_PER_PHASE, MINT_PHASES,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, ReplyOn, Response,
-    StdResult, SubMsg, Uint128, WasmMsg,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env, HexBinary,
+    MessageInfo, Reply, ReplyOn, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
@@ -16,7 +21,8 @@ use cw721::{
     DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
     DefaultOptionalNftExtensionMsg,
 };
-use cw_utils::parse_reply_instantiate_data;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
+use sha2::{Digest, Sha256};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw721-fixed-price";
@@ -33,10 +39,18 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    if msg.unit_price == Uint128::new(0) {
+    if msg.cw20_address.is_none() && msg.native_price.is_none() {
+        return Err(ContractError::NoPaymentMethod {});
+    }
+
+    if msg.cw20_address.is_some() && msg.unit_price == Uint128::new(0) {
         return Err(ContractError::InvalidUnitPrice {});
     }
 
+    if let Some(native_price) = &msg.native_price {
+        validate_native_price(native_price)?;
+    }
+
     if msg.max_tokens == 0 {
         return Err(ContractError::InvalidMaxTokens {});
     }
@@ -45,6 +59,9 @@ pub fn instantiate(
         cw721_address: None,
         cw20_address: msg.cw20_address,
         unit_price: msg.unit_price,
+        native_price: msg.native_price,
+        mint_limit_per_address: msg.mint_limit_per_address,
+        paused: false,
         max_tokens: msg.max_tokens,
         owner: info.sender,
         name: msg.name.clone(),
@@ -66,6 +83,7 @@ pub fn instantiate(
                 minter: None,
                 creator: None,
                 withdraw_address: msg.withdraw_address,
+                max_supply: None,
             })?,
             funds: vec![],
             admin: None,
@@ -101,9 +119,32 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let from_version = cw2::get_contract_version(deps.storage)?.version;
+    let mut response = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", CONTRACT_VERSION);
+
+    // config prior to native payments is stored under the same key, but in a different layout
+    if CONFIG.load(deps.storage).is_err() {
+        let legacy_config = LEGACY_CONFIG.load(deps.storage)?;
+        CONFIG.save(deps.storage, &legacy_config.into())?;
+        response = response.add_attribute("migrated", "config");
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(response)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetMintPhases {} => to_json_binary(&query_mint_phases(deps, env)?),
+        QueryMsg::GetRemainingMints { address } => {
+            to_json_binary(&query_remaining_mints(deps, env, address)?)
+        }
     }
 }
 
@@ -115,6 +156,9 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         cw721_address: config.cw721_address,
         max_tokens: config.max_tokens,
         unit_price: config.unit_price,
+        native_price: config.native_price,
+        mint_limit_per_address: config.mint_limit_per_address,
+        paused: config.paused,
         name: config.name,
         symbol: config.symbol,
         token_uri: config.token_uri,
@@ -123,10 +167,63 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     })
 }
 
+fn query_mint_phases(deps: Deps, env: Env) -> StdResult<MintPhasesResponse> {
+    let phases = MINT_PHASES.may_load(deps.storage)?.unwrap_or_default();
+    let current_phase = phases
+        .iter()
+        .position(|phase| is_phase_active(phase, &env))
+        .map(|index| index as u32);
+    Ok(MintPhasesResponse {
+        phases,
+        current_phase,
+    })
+}
+
+fn query_remaining_mints(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<RemainingMintsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let phases = MINT_PHASES.may_load(deps.storage)?.unwrap_or_default();
+    let current_phase = phases.iter().position(|phase| is_phase_active(phase, &env));
+
+    // phases are configured, but none is active
+    if !phases.is_empty() && current_phase.is_none() {
+        return Ok(RemainingMintsResponse {
+            current_phase: None,
+            remaining: 0,
+        });
+    }
+
+    let mut remaining = config.max_tokens.saturating_sub(config.unused_token_id);
+    if let Some(mint_limit) = config.mint_limit_per_address {
+        let minted = MINTED_PER_ADDRESS
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default();
+        remaining = remaining.min(mint_limit.saturating_sub(minted));
+    }
+    if let Some(index) = current_phase {
+        let phase = &phases[index];
+        if let Some(phase_limit) = phase.per_address_limit {
+            let minted = MINTED_PER_PHASE
+                .may_load(deps.storage, (phase.start_time.nanos(), &address))?
+                .unwrap_or_default();
+            remaining = remaining.min(phase_limit.saturating_sub(minted));
+        }
+    }
+
+    Ok(RemainingMintsResponse {
+        current_phase: current_phase.map(|index| index as u32),
+        remaining,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -135,22 +232,90 @@ pub fn execute(
             sender,
             amount,
             msg,
-        }) => execute_receive(deps, info, sender, amount, msg),
+        }) => execute_receive(deps, env, info, sender, amount, msg),
+        ExecuteMsg::Mint { proof } => execute_mint(deps, env, info, proof),
+        ExecuteMsg::UpdateConfig(msg) => execute_update_config(deps, info, msg),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
+        ExecuteMsg::SetMintPhases { phases } => execute_set_mint_phases(deps, info, phases),
     }
 }
 
 pub fn execute_receive(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     sender: String,
     amount: Uint128,
-    _msg: Binary,
+    msg: Binary,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if config.cw20_address != info.sender {
+    let config = CONFIG.load(deps.storage)?;
+    if config.cw20_address != Some(info.sender) {
         return Err(ContractError::UnauthorizedTokenContract {});
     }
 
+    let phase = load_active_phase(deps.storage, &env)?;
+    let unit_price = phase
+        .as_ref()
+        .and_then(|phase| phase.unit_price)
+        .unwrap_or(config.unit_price);
+    if amount != unit_price {
+        return Err(ContractError::WrongPaymentAmount {});
+    }
+
+    // proof is optional, so an empty message is accepted
+    let proof = if msg.is_empty() {
+        None
+    } else {
+        from_json::<ReceiveMsg>(&msg)?.proof
+    };
+
+    let sender = deps.api.addr_validate(&sender)?;
+    mint_to(deps, config, phase, sender, proof)
+}
+
+/// Mints an NFT with native payment. Payment is forwarded to the owner.
+pub fn execute_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proof: Option<Vec<HexBinary>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let phase = load_active_phase(deps.storage, &env)?;
+    let native_price = match phase
+        .as_ref()
+        .and_then(|phase| phase.native_price.clone())
+        .or_else(|| config.native_price.clone())
+    {
+        Some(native_price) => native_price,
+        None => return Err(ContractError::NativePaymentDisabled {}),
+    };
+
+    let amount = must_pay(&info, &native_price.denom)?;
+    if amount != native_price.amount {
+        return Err(ContractError::WrongPaymentAmount {});
+    }
+
+    let payment = BankMsg::Send {
+        to_address: config.owner.to_string(),
+        amount: vec![native_price],
+    };
+    Ok(mint_to(deps, config, phase, info.sender, proof)?.add_message(payment))
+}
+
+/// Checks sale and phase conditions and mints the next token id to the given recipient.
+fn mint_to(
+    deps: DepsMut,
+    mut config: Config,
+    phase: Option<MintPhase>,
+    recipient: Addr,
+    proof: Option<Vec<HexBinary>>,
+) -> Result<Response, ContractError> {
+    if config.paused {
+        return Err(ContractError::SalePaused {});
+    }
+
     if config.cw721_address.is_none() {
         return Err(ContractError::Uninitialized {});
     }
@@ -159,18 +324,40 @@ pub fn execute_receive(
         return Err(ContractError::SoldOut {});
     }
 
-    if amount != config.unit_price {
-        return Err(ContractError::WrongPaymentAmount {});
+    let minted = MINTED_PER_ADDRESS
+        .may_load(deps.storage, &recipient)?
+        .unwrap_or_default();
+    if let Some(mint_limit) = config.mint_limit_per_address {
+        if minted >= mint_limit {
+            return Err(ContractError::MintLimitReached {});
+        }
     }
 
+    if let Some(phase) = phase {
+        if let Some(merkle_root) = &phase.merkle_root {
+            verify_merkle_proof(merkle_root, &recipient, &proof.unwrap_or_default())?;
+        }
+        let key = (phase.start_time.nanos(), &recipient);
+        let minted_in_phase = MINTED_PER_PHASE
+            .may_load(deps.storage, key)?
+            .unwrap_or_default();
+        if let Some(phase_limit) = phase.per_address_limit {
+            if minted_in_phase >= phase_limit {
+                return Err(ContractError::PhaseMintLimitReached {});
+            }
+        }
+        MINTED_PER_PHASE.save(deps.storage, key, &(minted_in_phase + 1))?;
+    }
+    MINTED_PER_ADDRESS.save(deps.storage, &recipient, &(minted + 1))?;
+
     let extension: Option<NftExtensionMsg> = config.extension.clone().map(|e| e.into());
     let mint_msg = Cw721ExecuteMsg::<
         DefaultOptionalNftExtensionMsg,
         DefaultOptionalCollectionExtensionMsg,
         Empty,
     >::Mint {
-        token_id: config.unused_token_id.to_string(),
-        owner: sender,
+        token_id: Some(config.unused_token_id.to_string()),
+        owner: recipient.to_string(),
         token_uri: config.token_uri.clone().into(),
         extension,
     };
@@ -187,9 +374,170 @@ pub fn execute_receive(
     }
 }
 
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if (msg.clear_native_price && msg.native_price.is_some())
+        || (msg.clear_cw20_address && msg.cw20_address.is_some())
+        || (msg.clear_mint_limit_per_address && msg.mint_limit_per_address.is_some())
+    {
+        return Err(ContractError::ConflictingConfigUpdate {});
+    }
+
+    if let Some(unit_price) = msg.unit_price {
+        if unit_price == Uint128::new(0) {
+            return Err(ContractError::InvalidUnitPrice {});
+        }
+        config.unit_price = unit_price;
+    }
+    if let Some(native_price) = msg.native_price {
+        validate_native_price(&native_price)?;
+        config.native_price = Some(native_price);
+    }
+    if msg.clear_native_price {
+        config.native_price = None;
+    }
+    if let Some(cw20_address) = msg.cw20_address {
+        config.cw20_address = Some(deps.api.addr_validate(&cw20_address)?);
+    }
+    if msg.clear_cw20_address {
+        config.cw20_address = None;
+    }
+    if let Some(max_tokens) = msg.max_tokens {
+        // supply cap cannot be lower than already minted tokens
+        if max_tokens == 0 || max_tokens < config.unused_token_id {
+            return Err(ContractError::InvalidMaxTokens {});
+        }
+        config.max_tokens = max_tokens;
+    }
+    if let Some(mint_limit_per_address) = msg.mint_limit_per_address {
+        config.mint_limit_per_address = Some(mint_limit_per_address);
+    }
+    if msg.clear_mint_limit_per_address {
+        config.mint_limit_per_address = None;
+    }
+
+    // same rules as on instantiate
+    if config.cw20_address.is_none() && config.native_price.is_none() {
+        return Err(ContractError::NoPaymentMethod {});
+    }
+    if config.cw20_address.is_some() && config.unit_price == Uint128::new(0) {
+        return Err(ContractError::InvalidUnitPrice {});
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+
+    let action = if paused { "pause" } else { "unpause" };
+    Ok(Response::new().add_attribute("action", action))
+}
+
+pub fn execute_set_mint_phases(
+    deps: DepsMut,
+    info: MessageInfo,
+    phases: Vec<MintPhase>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for phase in phases.iter() {
+        if phase.start_time >= phase.end_time {
+            return Err(ContractError::InvalidMintPhases {});
+        }
+        if let Some(native_price) = &phase.native_price {
+            validate_native_price(native_price)?;
+        }
+        if phase.unit_price == Some(Uint128::zero()) {
+            return Err(ContractError::InvalidUnitPrice {});
+        }
+    }
+    // phases must be ordered and must not overlap
+    if phases
+        .windows(2)
+        .any(|pair| pair[0].end_time > pair[1].start_time)
+    {
+        return Err(ContractError::InvalidMintPhases {});
+    }
+    MINT_PHASES.save(deps.storage, &phases)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_mint_phases")
+        .add_attribute("phases", phases.len().to_string()))
+}
+
+fn is_phase_active(phase: &MintPhase, env: &Env) -> bool {
+    phase.start_time <= env.block.time && env.block.time < phase.end_time
+}
+
+/// Returns the currently active phase, or None in case no phases are configured.
+/// Fails in case phases are configured, but none is active.
+fn load_active_phase(storage: &dyn Storage, env: &Env) -> Result<Option<MintPhase>, ContractError> {
+    let phases = MINT_PHASES.may_load(storage)?.unwrap_or_default();
+    if phases.is_empty() {
+        return Ok(None);
+    }
+    match phases.into_iter().find(|phase| is_phase_active(phase, env)) {
+        Some(phase) => Ok(Some(phase)),
+        None => Err(ContractError::NoActivePhase {}),
+    }
+}
+
+/// Verifies the address is part of the merkle tree. Leaves are the sha256 hash of the address,
+/// and each pair of nodes is sorted before hashing.
+fn verify_merkle_proof(
+    merkle_root: &HexBinary,
+    address: &Addr,
+    proof: &[HexBinary],
+) -> Result<(), ContractError> {
+    let mut hash: [u8; 32] = Sha256::digest(address.as_bytes()).into();
+    for node in proof {
+        let node: [u8; 32] = node
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::InvalidMerkleProof {})?;
+        let mut pair = [hash, node];
+        pair.sort_unstable();
+        hash = Sha256::digest(pair.concat()).into();
+    }
+    if merkle_root.as_slice() != hash {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+    Ok(())
+}
+
+fn validate_native_price(native_price: &Coin) -> Result<(), ContractError> {
+    if native_price.amount.is_zero() || native_price.denom.is_empty() {
+        return Err(ContractError::InvalidUnitPrice {});
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::LegacyConfig;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
     use cosmwasm_std::{from_json, to_json_binary, CosmosMsg, SubMsgResponse, SubMsgResult};
     use cw721::DefaultOptionalNftExtensionMsg;
@@ -213,11 +561,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -240,6 +590,7 @@ mod tests {
                         minter: None,
                         creator: None,
                         withdraw_address: None,
+                        max_supply: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -283,6 +634,9 @@ mod tests {
                 cw721_address: Some(Addr::unchecked(NFT_CONTRACT_ADDR)),
                 max_tokens: msg.max_tokens,
                 unit_price: msg.unit_price,
+                native_price: None,
+                mint_limit_per_address: None,
+                paused: false,
                 name: msg.name,
                 symbol: msg.symbol,
                 token_uri: msg.token_uri,
@@ -299,11 +653,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(0),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -325,11 +681,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 0,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -351,11 +709,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -396,7 +756,7 @@ mod tests {
             DefaultOptionalCollectionExtensionMsg,
             Empty,
         >::Mint {
-            token_id: String::from("0"),
+            token_id: Some(String::from("0")),
             owner: String::from("minter"),
             token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
             extension: None,
@@ -424,11 +784,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -467,11 +829,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -512,11 +876,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -568,11 +934,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -604,11 +972,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -660,11 +1030,13 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
             token_code_id: 10u64,
-            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw20_address: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
@@ -708,4 +1080,625 @@ mod tests {
             e => panic!("unexpected error: {e}"),
         }
     }
+
+    fn setup_native_sale(deps: DepsMut, mint_limit_per_address: Option<u32>) {
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 2,
+            unit_price: Uint128::new(0),
+            native_price: Some(Coin::new(100, "ujuno")),
+            mint_limit_per_address,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: None,
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+        };
+        let mut deps = deps;
+        instantiate(deps.branch(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps, mock_env(), reply_msg).unwrap();
+    }
+
+    #[test]
+    fn no_payment_method() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 1,
+            unit_price: Uint128::new(1),
+            native_price: None,
+            mint_limit_per_address: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: None,
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+        };
+
+        let info = mock_info("owner", &[]);
+        let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+        match err {
+            ContractError::NoPaymentMethod {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn native_mint() {
+        let mut deps = mock_dependencies();
+        setup_native_sale(deps.as_mut(), None);
+
+        // cw20 payment is disabled
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(0),
+            msg: [].into(),
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::UnauthorizedTokenContract {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // wrong denom
+        let info = mock_info("minter", &[Coin::new(100, "uatom")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::Payment(_) => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // wrong amount
+        let info = mock_info("minter", &[Coin::new(99, "ujuno")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::WrongPaymentAmount {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let info = mock_info("minter", &[Coin::new(100, "ujuno")]);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap();
+
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: Some(String::from("0")),
+            owner: String::from("minter"),
+            token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+            extension: None,
+        };
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&mint_msg).unwrap(),
+                    funds: vec![],
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: String::from("owner"),
+                    amount: vec![Coin::new(100, "ujuno")],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn mint_limit_per_address() {
+        let mut deps = mock_dependencies();
+        setup_native_sale(deps.as_mut(), Some(1));
+
+        let info = mock_info("minter", &[Coin::new(100, "ujuno")]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::MintLimitReached {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // other address can still mint
+        let info = mock_info("other", &[Coin::new(100, "ujuno")]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn pause_and_update_config() {
+        let mut deps = mock_dependencies();
+        setup_native_sale(deps.as_mut(), None);
+
+        // only owner can pause
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            ExecuteMsg::Pause {},
+        )
+        .unwrap_err();
+        match err {
+            ContractError::Unauthorized {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Pause {},
+        )
+        .unwrap();
+
+        let info = mock_info("minter", &[Coin::new(100, "ujuno")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::SalePaused {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // only owner can update config
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            unit_price: Some(Uint128::new(5)),
+            native_price: Some(Coin::new(200, "uatom")),
+            cw20_address: Some(String::from(MOCK_CONTRACT_ADDR)),
+            max_tokens: Some(10),
+            mint_limit_per_address: Some(3),
+            ..UpdateConfigMsg::default()
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+        match err {
+            ContractError::Unauthorized {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            update_msg,
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::Unpause {},
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
+        let config: ConfigResponse = from_json(res).unwrap();
+        assert_eq!(config.unit_price, Uint128::new(5));
+        assert_eq!(config.native_price, Some(Coin::new(200, "uatom")));
+        assert_eq!(
+            config.cw20_address,
+            Some(Addr::unchecked(MOCK_CONTRACT_ADDR))
+        );
+        assert_eq!(config.max_tokens, 10);
+        assert_eq!(config.mint_limit_per_address, Some(3));
+        assert!(!config.paused);
+
+        // old price is rejected, new price is accepted
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::Payment(_) => {}
+            e => panic!("unexpected error: {e}"),
+        }
+        let info = mock_info("minter", &[Coin::new(200, "uatom")]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap();
+
+        // cw20 payment is enabled
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(5),
+            msg: [].into(),
+        });
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            msg,
+        )
+        .unwrap();
+
+        // supply cap cannot be lower than minted tokens
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            max_tokens: Some(1),
+            ..UpdateConfigMsg::default()
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            update_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidMaxTokens {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // a field cannot be set and cleared at once
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            native_price: Some(Coin::new(300, "uatom")),
+            clear_native_price: true,
+            ..UpdateConfigMsg::default()
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            update_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::ConflictingConfigUpdate {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // native price, cw20 address and mint limit can be cleared, but not all payment methods
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            clear_native_price: true,
+            clear_cw20_address: true,
+            ..UpdateConfigMsg::default()
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            update_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::NoPaymentMethod {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            clear_native_price: true,
+            clear_mint_limit_per_address: true,
+            ..UpdateConfigMsg::default()
+        });
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            update_msg,
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
+        let config: ConfigResponse = from_json(res).unwrap();
+        assert_eq!(config.native_price, None);
+        assert_eq!(config.mint_limit_per_address, None);
+        assert_eq!(
+            config.cw20_address,
+            Some(Addr::unchecked(MOCK_CONTRACT_ADDR))
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("minter", &[Coin::new(200, "uatom")]),
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::NativePaymentDisabled {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn migrate_legacy_config() {
+        let mut deps = mock_dependencies();
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.18.0").unwrap();
+        let legacy_config = LegacyConfig {
+            owner: Addr::unchecked("owner"),
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            cw721_address: Some(Addr::unchecked("cw721")),
+            max_tokens: 5,
+            unit_price: Uint128::new(10),
+            name: String::from("name"),
+            symbol: String::from("symbol"),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            unused_token_id: 2,
+        };
+        LEGACY_CONFIG
+            .save(deps.as_mut().storage, &legacy_config)
+            .unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "migrated" && attr.value == "config"));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap();
+        let config: ConfigResponse = from_json(res).unwrap();
+        assert_eq!(
+            config.cw20_address,
+            Some(Addr::unchecked(MOCK_CONTRACT_ADDR))
+        );
+        assert_eq!(config.unit_price, Uint128::new(10));
+        assert_eq!(config.native_price, None);
+        assert_eq!(config.mint_limit_per_address, None);
+        assert!(!config.paused);
+        assert_eq!(config.unused_token_id, 2);
+        assert_eq!(
+            cw2::get_contract_version(deps.as_ref().storage)
+                .unwrap()
+                .version,
+            CONTRACT_VERSION
+        );
+
+        // migrating again leaves the config as is
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(!res.attributes.iter().any(|attr| attr.key == "migrated"));
+    }
+
+    #[test]
+    fn mint_phases() {
+        let mut deps = mock_dependencies();
+        setup_native_sale(deps.as_mut(), None);
+
+        // allowlist with "minter" and "other"
+        let minter_leaf: [u8; 32] = Sha256::digest("minter".as_bytes()).into();
+        let other_leaf: [u8; 32] = Sha256::digest("other".as_bytes()).into();
+        let mut pair = [minter_leaf, other_leaf];
+        pair.sort_unstable();
+        let merkle_root: [u8; 32] = Sha256::digest(pair.concat()).into();
+
+        let now = mock_env().block.time;
+        let allowlist_phase = MintPhase {
+            start_time: now,
+            end_time: now.plus_seconds(100),
+            unit_price: None,
+            native_price: Some(Coin::new(50, "ujuno")),
+            per_address_limit: Some(1),
+            merkle_root: Some(HexBinary::from(merkle_root)),
+        };
+        let public_phase = MintPhase {
+            start_time: now.plus_seconds(100),
+            end_time: now.plus_seconds(200),
+            unit_price: None,
+            native_price: None,
+            per_address_limit: None,
+            merkle_root: None,
+        };
+
+        // only owner can set phases
+        let msg = ExecuteMsg::SetMintPhases {
+            phases: vec![allowlist_phase.clone(), public_phase.clone()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        match err {
+            ContractError::Unauthorized {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // phases must not overlap
+        let overlapping = ExecuteMsg::SetMintPhases {
+            phases: vec![public_phase.clone(), allowlist_phase.clone()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            overlapping,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidMintPhases {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetMintPhases {}).unwrap();
+        let phases: MintPhasesResponse = from_json(res).unwrap();
+        assert_eq!(
+            phases,
+            MintPhasesResponse {
+                phases: vec![allowlist_phase, public_phase],
+                current_phase: Some(0),
+            }
+        );
+
+        // address not in allowlist
+        let info = mock_info("random", &[Coin::new(50, "ujuno")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidMerkleProof {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // phase price applies
+        let proof = Some(vec![HexBinary::from(other_leaf)]);
+        let info = mock_info("minter", &[Coin::new(100, "ujuno")]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint {
+                proof: proof.clone(),
+            },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::WrongPaymentAmount {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let info = mock_info("minter", &[Coin::new(50, "ujuno")]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Mint {
+                proof: proof.clone(),
+            },
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Mint { proof }).unwrap_err();
+        match err {
+            ContractError::PhaseMintLimitReached {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let query_msg = QueryMsg::GetRemainingMints {
+            address: String::from("minter"),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let remaining: RemainingMintsResponse = from_json(res).unwrap();
+        assert_eq!(
+            remaining,
+            RemainingMintsResponse {
+                current_phase: Some(0),
+                remaining: 0,
+            }
+        );
+        let query_msg = QueryMsg::GetRemainingMints {
+            address: String::from("other"),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let remaining: RemainingMintsResponse = from_json(res).unwrap();
+        assert_eq!(remaining.remaining, 1);
+
+        // public phase, anyone can mint at the default price
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(100);
+        let info = mock_info("random", &[Coin::new(100, "ujuno")]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap();
+
+        // all phases ended
+        env.block.time = now.plus_seconds(200);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::Mint { proof: None },
+        )
+        .unwrap_err();
+        match err {
+            ContractError::NoActivePhase {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+        let query_msg = QueryMsg::GetRemainingMints {
+            address: String::from("random"),
+        };
+        let res = query(deps.as_ref(), env, query_msg).unwrap();
+        let remaining: RemainingMintsResponse = from_json(res).unwrap();
+        assert_eq!(
+            remaining,
+            RemainingMintsResponse {
+                current_phase: None,
+                remaining: 0,
+            }
+        );
+    }
 }
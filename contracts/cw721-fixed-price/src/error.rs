@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -35,4 +39,31 @@ pub enum ContractError {
 
     #[error("Cw721AlreadyLinked")]
     Cw721AlreadyLinked {},
+
+    #[error("NoPaymentMethod")]
+    NoPaymentMethod {},
+
+    #[error("ConflictingConfigUpdate")]
+    ConflictingConfigUpdate {},
+
+    #[error("NativePaymentDisabled")]
+    NativePaymentDisabled {},
+
+    #[error("SalePaused")]
+    SalePaused {},
+
+    #[error("MintLimitReached")]
+    MintLimitReached {},
+
+    #[error("PhaseMintLimitReached")]
+    PhaseMintLimitReached {},
+
+    #[error("NoActivePhase")]
+    NoActivePhase {},
+
+    #[error("InvalidMintPhases")]
+    InvalidMintPhases {},
+
+    #[error("InvalidMerkleProof")]
+    InvalidMerkleProof {},
 }
@@ -1,13 +1,20 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, HexBinary, Uint128};
 use cw20::Cw20ReceiveMsg;
 use cw721::DefaultOptionalNftExtension;
 
+use crate::state::MintPhase;
+
 #[cw_serde]
 pub struct InstantiateMsg<TCollectionExtensionMsg> {
     pub owner: Addr,
     pub max_tokens: u32,
+    /// Price in CW20 tokens, required in case `cw20_address` is set.
     pub unit_price: Uint128,
+    /// Price in native coins. At least one of `native_price` and `cw20_address` must be set.
+    pub native_price: Option<Coin>,
+    /// Max number of NFTs a single address can mint. None means unlimited.
+    pub mint_limit_per_address: Option<u32>,
     /// Name of the NFT contract
     pub name: String,
     /// Symbol of the NFT contract
@@ -15,7 +22,7 @@ pub struct InstantiateMsg<TCollectionExtensionMsg> {
     /// Optional extension of the collection metadata
     pub collection_info_extension: TCollectionExtensionMsg,
     pub token_code_id: u64,
-    pub cw20_address: Addr,
+    pub cw20_address: Option<Addr>,
     pub token_uri: String,
     pub extension: DefaultOptionalNftExtension,
     pub withdraw_address: Option<String>,
@@ -23,7 +30,49 @@ pub struct InstantiateMsg<TCollectionExtensionMsg> {
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Mint with CW20 payment, via CW20 `Send`. A merkle proof can be passed as `ReceiveMsg`.
     Receive(Cw20ReceiveMsg),
+    /// Mint with native payment, funds must match `native_price`.
+    /// A merkle proof is required in case the current phase has an allowlist.
+    Mint { proof: Option<Vec<HexBinary>> },
+    /// Only owner can update config. Fields not set are left unchanged.
+    UpdateConfig(UpdateConfigMsg),
+    /// Only owner can pause sales.
+    Pause {},
+    /// Only owner can resume sales.
+    Unpause {},
+    /// Only owner can set mint phases, replacing existing ones.
+    /// Phases must be ordered by start time and must not overlap.
+    SetMintPhases { phases: Vec<MintPhase> },
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct UpdateConfigMsg {
+    pub unit_price: Option<Uint128>,
+    pub native_price: Option<Coin>,
+    pub cw20_address: Option<String>,
+    pub max_tokens: Option<u32>,
+    pub mint_limit_per_address: Option<u32>,
+    /// Removes the native price, disabling native payments. Cannot be combined with `native_price`.
+    #[serde(default)]
+    pub clear_native_price: bool,
+    /// Removes the CW20 token, disabling CW20 payments. Cannot be combined with `cw20_address`.
+    #[serde(default)]
+    pub clear_cw20_address: bool,
+    /// Removes the per-address mint limit. Cannot be combined with `mint_limit_per_address`.
+    #[serde(default)]
+    pub clear_mint_limit_per_address: bool,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Optional message passed along a CW20 `Send`.
+#[cw_serde]
+pub struct ReceiveMsg {
+    /// Merkle proof, required in case the current phase has an allowlist.
+    pub proof: Option<Vec<HexBinary>>,
 }
 
 #[cw_serde]
@@ -31,18 +80,42 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     GetConfig {},
+    #[returns(MintPhasesResponse)]
+    GetMintPhases {},
+    /// Returns how many NFTs an address can still mint in the current phase.
+    /// Allowlist membership is not checked, since it requires a proof.
+    #[returns(RemainingMintsResponse)]
+    GetRemainingMints { address: String },
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     pub owner: Addr,
-    pub cw20_address: Addr,
+    pub cw20_address: Option<Addr>,
     pub cw721_address: Option<Addr>,
     pub max_tokens: u32,
     pub unit_price: Uint128,
+    pub native_price: Option<Coin>,
+    pub mint_limit_per_address: Option<u32>,
+    pub paused: bool,
     pub name: String,
     pub symbol: String,
     pub token_uri: String,
     pub extension: DefaultOptionalNftExtension,
     pub unused_token_id: u32,
 }
+
+#[cw_serde]
+pub struct MintPhasesResponse {
+    pub phases: Vec<MintPhase>,
+    /// Index of the currently active phase, if any.
+    pub current_phase: Option<u32>,
+}
+
+#[cw_serde]
+pub struct RemainingMintsResponse {
+    /// Index of the currently active phase, if any.
+    pub current_phase: Option<u32>,
+    /// Limited by per address limits and the remaining supply.
+    pub remaining: u32,
+}
@@ -1,15 +1,22 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, HexBinary, Timestamp, Uint128};
 use cw721::DefaultOptionalNftExtension;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
     pub owner: Addr,
-    pub cw20_address: Addr,
+    /// CW20 token accepted as payment via `Receive`. None disables CW20 payments.
+    pub cw20_address: Option<Addr>,
     pub cw721_address: Option<Addr>,
     pub max_tokens: u32,
+    /// Price in CW20 tokens.
     pub unit_price: Uint128,
+    /// Price in native coins, used by `Mint {}`. None disables native payments.
+    pub native_price: Option<Coin>,
+    /// Max number of NFTs a single address can mint. None means unlimited.
+    pub mint_limit_per_address: Option<u32>,
+    pub paused: bool,
     pub name: String,
     pub symbol: String,
     pub token_uri: String,
@@ -18,3 +25,63 @@ pub struct Config {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Config prior to native payments: CW20 payments only, no mint limit, and sales cannot be paused.
+/// Stored under the same key as `Config`, converted by `migrate`.
+#[cw_serde]
+pub struct LegacyConfig {
+    pub owner: Addr,
+    pub cw20_address: Addr,
+    pub cw721_address: Option<Addr>,
+    pub max_tokens: u32,
+    pub unit_price: Uint128,
+    pub name: String,
+    pub symbol: String,
+    pub token_uri: String,
+    pub extension: DefaultOptionalNftExtension,
+    pub unused_token_id: u32,
+}
+
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy_config: LegacyConfig) -> Self {
+        Config {
+            owner: legacy_config.owner,
+            cw20_address: Some(legacy_config.cw20_address),
+            cw721_address: legacy_config.cw721_address,
+            max_tokens: legacy_config.max_tokens,
+            unit_price: legacy_config.unit_price,
+            native_price: None,
+            mint_limit_per_address: None,
+            paused: false,
+            name: legacy_config.name,
+            symbol: legacy_config.symbol,
+            token_uri: legacy_config.token_uri,
+            extension: legacy_config.extension,
+            unused_token_id: legacy_config.unused_token_id,
+        }
+    }
+}
+/// Number of NFTs minted by each address, checked against `Config::mint_limit_per_address`.
+pub const MINTED_PER_ADDRESS: Map<&Addr, u32> = Map::new("minted_per_address");
+
+/// A mint phase, active from `start_time` (inclusive) until `end_time` (exclusive).
+#[cw_serde]
+pub struct MintPhase {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    /// Price in CW20 tokens during this phase. None falls back to `Config::unit_price`.
+    pub unit_price: Option<Uint128>,
+    /// Price in native coins during this phase. None falls back to `Config::native_price`.
+    pub native_price: Option<Coin>,
+    /// Max number of NFTs a single address can mint in this phase. None means unlimited.
+    pub per_address_limit: Option<u32>,
+    /// Merkle root of allowed addresses. None means anyone can mint in this phase.
+    pub merkle_root: Option<HexBinary>,
+}
+
+/// Mint phases, ordered by start time and non-overlapping. No phases means public sale.
+pub const MINT_PHASES: Item<Vec<MintPhase>> = Item::new("mint_phases");
+/// Number of NFTs minted by each address per phase, keyed by the phase start time in nanos.
+pub const MINTED_PER_PHASE: Map<(u64, &Addr), u32> = Map::new("minted_per_phase");
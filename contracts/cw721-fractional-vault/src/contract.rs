@@ -0,0 +1,345 @@
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, FractionalizeMsg, InstantiateMsg, QueryMsg, ReceiveMsg, SharesMsg};
+use crate::state::{Config, Shares, Status, Vault, CONFIG, VAULT};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw1155::msg::{Cw1155ExecuteMsg, Cw1155InstantiateMsg, Cw1155MintMsg};
+use cw1155::receiver::Cw1155ReceiveMsg;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw_utils::{one_coin, parse_reply_instantiate_data};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-fractional-vault";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_SHARES_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.supply.is_zero() {
+        return Err(ContractError::InvalidSupply {});
+    }
+
+    // the vault instantiates the share token, becoming its minter
+    let (shares, code_id, instantiate_msg) = match msg.shares {
+        SharesMsg::Cw1155 {
+            code_id,
+            name,
+            symbol,
+            token_id,
+        } => (
+            Shares::Cw1155 { token_id },
+            code_id,
+            to_json_binary(&Cw1155InstantiateMsg {
+                name,
+                symbol,
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                default_uri: None,
+            })?,
+        ),
+        SharesMsg::Cw20 {
+            code_id,
+            name,
+            symbol,
+            decimals,
+        } => (
+            Shares::Cw20 {},
+            code_id,
+            to_json_binary(&cw20_base::msg::InstantiateMsg {
+                name,
+                symbol,
+                decimals,
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: env.contract.address.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            })?,
+        ),
+    };
+    let config = Config {
+        collection: deps.api.addr_validate(&msg.collection)?,
+        shares,
+        share_address: None,
+        supply: msg.supply,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    let sub_msg = SubMsg {
+        msg: WasmMsg::Instantiate {
+            code_id,
+            msg: instantiate_msg,
+            funds: vec![],
+            admin: None,
+            label: String::from("Instantiate fractional vault shares"),
+        }
+        .into(),
+        id: INSTANTIATE_SHARES_REPLY_ID,
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    };
+
+    Ok(Response::new().add_submessage(sub_msg))
+}
+
+// Reply callback triggered from share token instantiation
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_SHARES_REPLY_ID {
+        return Err(ContractError::InvalidReplyId {});
+    }
+
+    let reply = parse_reply_instantiate_data(msg)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    config.share_address = Some(deps.api.addr_validate(&reply.contract_address)?);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("share_address", reply.contract_address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => execute_fractionalize(deps, info, sender, token_id, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(Cw1155ReceiveMsg {
+            operator,
+            from,
+            token_id,
+            amount,
+            ..
+        })) => {
+            let config = CONFIG.load(deps.storage)?;
+            let is_share = matches!(
+                &config.shares,
+                Shares::Cw1155 { token_id: share_token_id } if *share_token_id == token_id
+            );
+            if !is_share || config.share_address.as_ref() != Some(&info.sender) {
+                return Err(ContractError::WrongShares {});
+            }
+            let holder = deps.api.addr_validate(&from.unwrap_or(operator))?;
+            execute_receive_shares(deps, config, info.sender, holder, amount)
+        }
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg { sender, amount, .. })) => {
+            let config = CONFIG.load(deps.storage)?;
+            if config.shares != (Shares::Cw20 {})
+                || config.share_address.as_ref() != Some(&info.sender)
+            {
+                return Err(ContractError::WrongShares {});
+            }
+            let holder = deps.api.addr_validate(&sender)?;
+            execute_receive_shares(deps, config, info.sender, holder, amount)
+        }
+        ExecuteMsg::Buyout {} => execute_buyout(deps, info),
+    }
+}
+
+pub fn execute_fractionalize(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let FractionalizeMsg { reserve_price } = from_json(&msg)?;
+    // the sender is the cw721 contract, the NFT is already owned by this contract
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.collection {
+        return Err(ContractError::WrongCollection {});
+    }
+    let Some(share_address) = &config.share_address else {
+        return Err(ContractError::Uninitialized {});
+    };
+    if VAULT.exists(deps.storage) {
+        return Err(ContractError::VaultInUse {});
+    }
+    if reserve_price.amount.is_zero() {
+        return Err(ContractError::InvalidReservePrice {});
+    }
+
+    let vault = Vault {
+        token_id,
+        curator: deps.api.addr_validate(&sender)?,
+        reserve_price,
+        outstanding: config.supply,
+        status: Status::Locked,
+    };
+    VAULT.save(deps.storage, &vault)?;
+
+    let mint_msg = match &config.shares {
+        Shares::Cw1155 { token_id } => to_json_binary(&Cw1155ExecuteMsg::<Empty, Empty>::Mint {
+            recipient: vault.curator.to_string(),
+            msg: Cw1155MintMsg {
+                token_id: token_id.clone(),
+                amount: config.supply,
+                token_uri: None,
+                extension: None,
+                config: None,
+            },
+        })?,
+        Shares::Cw20 {} => to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: vault.curator.to_string(),
+            amount: config.supply,
+        })?,
+    };
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: share_address.to_string(),
+            msg: mint_msg,
+            funds: vec![],
+        })
+        .add_attribute("action", "fractionalize")
+        .add_attribute("token_id", vault.token_id)
+        .add_attribute("curator", vault.curator)
+        .add_attribute("supply", config.supply))
+}
+
+/// Burns shares received from the holder. While locked, the whole supply redeems the NFT. After
+/// a buyout, shares are exchanged for their part of the remaining proceeds.
+pub fn execute_receive_shares(
+    deps: DepsMut,
+    config: Config,
+    share_address: Addr,
+    holder: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut vault = VAULT.load(deps.storage)?;
+    let burn_msg = burn_msg(&config, share_address, amount)?;
+    let res = Response::new()
+        .add_message(burn_msg)
+        .add_attribute("holder", holder.to_string())
+        .add_attribute("shares", amount);
+
+    match vault.status {
+        Status::Locked => {
+            if amount != config.supply {
+                return Err(ContractError::NotWholeSupply {
+                    supply: config.supply,
+                });
+            }
+            VAULT.remove(deps.storage);
+            Ok(res
+                .add_message(transfer_nft_msg(&config, &holder, &vault.token_id)?)
+                .add_attribute("action", "redeem")
+                .add_attribute("token_id", vault.token_id))
+        }
+        Status::BoughtOut {
+            ref buyer,
+            ref mut proceeds,
+        } => {
+            // pro-rata of the remaining proceeds, the last shares receive any rounding remainder
+            let payout = proceeds.amount.multiply_ratio(amount, vault.outstanding);
+            proceeds.amount -= payout;
+            let payout = Coin {
+                denom: proceeds.denom.clone(),
+                amount: payout,
+            };
+            let res = res
+                .add_attribute("action", "claim")
+                .add_attribute("buyer", buyer.to_string())
+                .add_attribute("payout", payout.to_string());
+            vault.outstanding -= amount;
+            if vault.outstanding.is_zero() {
+                VAULT.remove(deps.storage);
+            } else {
+                VAULT.save(deps.storage, &vault)?;
+            }
+            if payout.amount.is_zero() {
+                return Ok(res);
+            }
+            Ok(res.add_message(BankMsg::Send {
+                to_address: holder.into_string(),
+                amount: vec![payout],
+            }))
+        }
+    }
+}
+
+pub fn execute_buyout(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut vault = VAULT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotLocked {})?;
+    if vault.status != Status::Locked {
+        return Err(ContractError::NotLocked {});
+    }
+    let price = one_coin(&info)?;
+    if price.denom != vault.reserve_price.denom || price.amount < vault.reserve_price.amount {
+        return Err(ContractError::BuyoutPriceTooLow {
+            reserve_price: vault.reserve_price,
+        });
+    }
+
+    vault.status = Status::BoughtOut {
+        buyer: info.sender.clone(),
+        proceeds: price.clone(),
+    };
+    VAULT.save(deps.storage, &vault)?;
+
+    Ok(Response::new()
+        .add_message(transfer_nft_msg(&config, &info.sender, &vault.token_id)?)
+        .add_attribute("action", "buyout")
+        .add_attribute("token_id", vault.token_id)
+        .add_attribute("buyer", info.sender)
+        .add_attribute("price", price.to_string()))
+}
+
+fn burn_msg(config: &Config, share_address: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match &config.shares {
+        Shares::Cw1155 { token_id } => to_json_binary(&Cw1155ExecuteMsg::<Empty, Empty>::Burn {
+            from: None,
+            token_id: token_id.clone(),
+            amount,
+        })?,
+        Shares::Cw20 {} => to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: share_address.into_string(),
+        msg,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn transfer_nft_msg(config: &Config, recipient: &Addr, token_id: &str) -> StdResult<CosmosMsg> {
+    DefaultCw721Helper::new(config.collection.clone()).call(Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: token_id.to_string(),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Vault {} => to_json_binary(&VAULT.may_load(deps.storage)?),
+    }
+}
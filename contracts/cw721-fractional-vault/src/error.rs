@@ -0,0 +1,45 @@
+use cosmwasm_std::{Coin, StdError, Uint128};
+use cw_utils::{ParseReplyError, PaymentError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("InvalidSupply")]
+    InvalidSupply {},
+
+    #[error("InvalidReservePrice")]
+    InvalidReservePrice {},
+
+    #[error("InvalidReplyId")]
+    InvalidReplyId {},
+
+    #[error("Share token not instantiated yet")]
+    Uninitialized {},
+
+    #[error("Only NFTs of the configured collection can be fractionalized")]
+    WrongCollection {},
+
+    #[error("Only the vault's shares are accepted")]
+    WrongShares {},
+
+    #[error("Vault already holds an NFT, or proceeds of a buyout")]
+    VaultInUse {},
+
+    #[error("NFT is not locked in the vault")]
+    NotLocked {},
+
+    #[error("Redeeming the NFT requires the whole supply of {supply} shares")]
+    NotWholeSupply { supply: Uint128 },
+
+    #[error("Buyout price must be at least {reserve_price}")]
+    BuyoutPriceTooLow { reserve_price: Coin },
+}
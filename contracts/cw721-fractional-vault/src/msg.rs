@@ -0,0 +1,70 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, Uint128};
+use cw1155::receiver::Cw1155ReceiveMsg;
+use cw20::Cw20ReceiveMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::{Config, Vault};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub collection: String,
+    /// Share token instantiated by the vault.
+    pub shares: SharesMsg,
+    /// Number of shares minted per fractionalized NFT.
+    pub supply: Uint128,
+}
+
+#[cw_serde]
+pub enum SharesMsg {
+    Cw1155 {
+        code_id: u64,
+        name: String,
+        symbol: String,
+        /// Token id of the shares.
+        token_id: String,
+    },
+    Cw20 {
+        code_id: u64,
+        name: String,
+        symbol: String,
+        decimals: u8,
+    },
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Fractionalize an NFT, via cw721 `SendNft` with a `FractionalizeMsg`. The whole share supply
+    /// is minted to the sender.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Receive shares, via cw1155 or cw20 `Send`. Received shares are burned. While the NFT is
+    /// locked, the whole supply redeems the NFT. After a buyout, shares are exchanged pro-rata for
+    /// the proceeds.
+    Receive(ReceiveMsg),
+    /// Buy out the NFT, funds must be at least the reserve price.
+    Buyout {},
+}
+
+/// Message passed along a cw721 `SendNft`.
+#[cw_serde]
+pub struct FractionalizeMsg {
+    pub reserve_price: Coin,
+}
+
+/// cw1155 and cw20 both send tokens via a `Receive` message, distinguished by their fields.
+#[cw_serde]
+#[serde(untagged)]
+pub enum ReceiveMsg {
+    Cw1155(Cw1155ReceiveMsg),
+    Cw20(Cw20ReceiveMsg),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    /// Returns the fractionalized NFT, if any.
+    #[returns(Option<Vault>)]
+    Vault {},
+}
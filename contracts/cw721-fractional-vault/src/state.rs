@@ -0,0 +1,47 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_storage_plus::Item;
+
+#[cw_serde]
+pub struct Config {
+    /// Only NFTs of this collection can be fractionalized.
+    pub collection: Addr,
+    pub shares: Shares,
+    /// Share token contract, instantiated by the vault. The vault is its minter.
+    pub share_address: Option<Addr>,
+    /// Number of shares minted per fractionalized NFT.
+    pub supply: Uint128,
+}
+
+/// Standard of the share token.
+#[cw_serde]
+pub enum Shares {
+    /// Shares are minted as the given token id of a cw1155 contract.
+    Cw1155 { token_id: String },
+    /// Shares are minted as cw20 tokens.
+    Cw20 {},
+}
+
+#[cw_serde]
+pub enum Status {
+    /// The vault holds the NFT, the whole share supply can redeem it.
+    Locked,
+    /// The NFT has been bought out, shares can be exchanged pro-rata for the remaining proceeds.
+    BoughtOut { buyer: Addr, proceeds: Coin },
+}
+
+/// The NFT fractionalized by the vault.
+#[cw_serde]
+pub struct Vault {
+    pub token_id: String,
+    /// Owner of the NFT when fractionalized, receiving the minted shares.
+    pub curator: Addr,
+    /// Minimum price for buying out the NFT.
+    pub reserve_price: Coin,
+    /// Shares not yet burned.
+    pub outstanding: Uint128,
+    pub status: Status,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const VAULT: Item<Vault> = Item::new("vault");
@@ -0,0 +1,306 @@
+use crate::{
+    contract::{execute, instantiate, query, reply},
+    msg::{ExecuteMsg, FractionalizeMsg, InstantiateMsg, QueryMsg, SharesMsg},
+    state::{Config, Status, Vault},
+    ContractError,
+};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Empty, Uint128};
+use cw1155_base::Cw1155BaseExecuteMsg;
+use cw20::Cw20ExecuteMsg;
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_nfts_multi_test::{
+    cw1155_base_contract, cw20_base_contract, fund, instantiate_cw721, query_cw1155_balance,
+    query_cw20_balance, query_owner, DENOM,
+};
+
+const SHARE_TOKEN_ID: &str = "shares";
+
+struct Contracts {
+    cw721: Addr,
+    vault: Addr,
+    shares: Addr,
+}
+
+struct Users {
+    curator: Addr,
+    holder: Addr,
+    buyer: Addr,
+}
+
+fn vault_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+fn users(app: &App) -> Users {
+    Users {
+        curator: app.api().addr_make("curator"),
+        holder: app.api().addr_make("holder"),
+        buyer: app.api().addr_make("buyer"),
+    }
+}
+
+/// Creates a collection with tokens "1" and "2" owned by the curator, and the vault with a supply
+/// of 100 cw1155 or cw20 shares. The buyer has a balance of 1000.
+fn setup(app: &mut App, cw20_shares: bool) -> Contracts {
+    let Users { curator, buyer, .. } = users(app);
+
+    let cw721 = instantiate_cw721(app, &curator, None, &[("1", &curator), ("2", &curator)]);
+
+    let shares = if cw20_shares {
+        SharesMsg::Cw20 {
+            code_id: app.store_code(cw20_base_contract()),
+            name: "shares".to_string(),
+            symbol: "SHARES".to_string(),
+            decimals: 0,
+        }
+    } else {
+        SharesMsg::Cw1155 {
+            code_id: app.store_code(cw1155_base_contract()),
+            name: "shares".to_string(),
+            symbol: "SHARES".to_string(),
+            token_id: SHARE_TOKEN_ID.to_string(),
+        }
+    };
+    let vault_code_id = app.store_code(vault_contract());
+    let vault = app
+        .instantiate_contract(
+            vault_code_id,
+            curator,
+            &InstantiateMsg {
+                collection: cw721.to_string(),
+                shares,
+                supply: Uint128::new(100),
+            },
+            &[],
+            "vault",
+            None,
+        )
+        .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&vault, &QueryMsg::Config {})
+        .unwrap();
+
+    fund(app, &buyer, 1000);
+
+    Contracts {
+        cw721,
+        vault,
+        shares: config.share_address.unwrap(),
+    }
+}
+
+fn fractionalize(
+    app: &mut App,
+    contracts: &Contracts,
+    token_id: &str,
+    reserve_price: u128,
+) -> Result<(), ContractError> {
+    let Users { curator, .. } = users(app);
+    app.execute_contract(
+        curator,
+        contracts.cw721.clone(),
+        &Cw721ExecuteMsg::SendNft {
+            contract: contracts.vault.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&FractionalizeMsg {
+                reserve_price: coin(reserve_price, DENOM),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+/// Sends cw1155 shares to the recipient, calling the receiver interface in case of the vault.
+fn send_cw1155_shares(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    recipient: &Addr,
+    amount: u128,
+) -> Result<(), ContractError> {
+    let msg = (*recipient == contracts.vault).then(|| to_json_binary(&Empty {}).unwrap());
+    app.execute_contract(
+        sender.clone(),
+        contracts.shares.clone(),
+        &Cw1155BaseExecuteMsg::Send {
+            from: None,
+            to: recipient.to_string(),
+            token_id: SHARE_TOKEN_ID.to_string(),
+            amount: Uint128::new(amount),
+            msg,
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn send_cw20_shares(
+    app: &mut App,
+    contracts: &Contracts,
+    sender: &Addr,
+    amount: u128,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        sender.clone(),
+        contracts.shares.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: contracts.vault.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&Empty {}).unwrap(),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn query_vault(app: &App, contracts: &Contracts) -> Option<Vault> {
+    app.wrap()
+        .query_wasm_smart(&contracts.vault, &QueryMsg::Vault {})
+        .unwrap()
+}
+
+#[test]
+fn test_fractionalize_and_redeem_cw1155() {
+    let mut app = App::default();
+    let contracts = setup(&mut app, false);
+    let Users {
+        curator, holder, ..
+    } = users(&app);
+
+    fractionalize(&mut app, &contracts, "1", 1000).unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), contracts.vault);
+    assert_eq!(
+        query_vault(&app, &contracts).unwrap(),
+        Vault {
+            token_id: "1".to_string(),
+            curator: curator.clone(),
+            reserve_price: coin(1000, DENOM),
+            outstanding: Uint128::new(100),
+            status: Status::Locked,
+        }
+    );
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.shares, &curator, SHARE_TOKEN_ID),
+        Uint128::new(100)
+    );
+
+    // a vault holds a single NFT
+    let err = fractionalize(&mut app, &contracts, "2", 1000).unwrap_err();
+    assert_eq!(err, ContractError::VaultInUse {});
+
+    // redeeming requires the whole supply
+    send_cw1155_shares(&mut app, &contracts, &curator, &holder, 40).unwrap();
+    let err = send_cw1155_shares(&mut app, &contracts, &curator, &contracts.vault, 60).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotWholeSupply {
+            supply: Uint128::new(100)
+        }
+    );
+    send_cw1155_shares(&mut app, &contracts, &holder, &curator, 40).unwrap();
+    send_cw1155_shares(&mut app, &contracts, &curator, &contracts.vault, 100).unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), curator);
+    assert_eq!(query_vault(&app, &contracts), None);
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.shares, &curator, SHARE_TOKEN_ID),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.shares, &contracts.vault, SHARE_TOKEN_ID),
+        Uint128::zero()
+    );
+
+    // the vault can be reused
+    fractionalize(&mut app, &contracts, "2", 1000).unwrap();
+    assert_eq!(
+        query_cw1155_balance(&app, &contracts.shares, &curator, SHARE_TOKEN_ID),
+        Uint128::new(100)
+    );
+}
+
+#[test]
+fn test_buyout_cw20() {
+    let mut app = App::default();
+    let contracts = setup(&mut app, true);
+    let Users {
+        curator,
+        holder,
+        buyer,
+    } = users(&app);
+
+    fractionalize(&mut app, &contracts, "1", 1000).unwrap();
+    app.execute_contract(
+        curator.clone(),
+        contracts.shares.clone(),
+        &Cw20ExecuteMsg::Transfer {
+            recipient: holder.to_string(),
+            amount: Uint128::new(30),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // buyout requires the reserve price
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            contracts.vault.clone(),
+            &ExecuteMsg::Buyout {},
+            &coins(500, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BuyoutPriceTooLow {
+            reserve_price: coin(1000, DENOM)
+        }
+    );
+    app.execute_contract(
+        buyer.clone(),
+        contracts.vault.clone(),
+        &ExecuteMsg::Buyout {},
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), buyer);
+    assert_eq!(
+        query_vault(&app, &contracts).unwrap().status,
+        Status::BoughtOut {
+            buyer: buyer.clone(),
+            proceeds: coin(1000, DENOM),
+        }
+    );
+
+    // shares are exchanged pro-rata for the proceeds
+    send_cw20_shares(&mut app, &contracts, &holder, 30).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&holder, DENOM).unwrap().amount,
+        Uint128::new(300)
+    );
+    let err = fractionalize(&mut app, &contracts, "2", 1000).unwrap_err();
+    assert_eq!(err, ContractError::VaultInUse {});
+    send_cw20_shares(&mut app, &contracts, &curator, 70).unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&curator, DENOM).unwrap().amount,
+        Uint128::new(700)
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &contracts.shares, &curator),
+        Uint128::zero()
+    );
+    assert_eq!(query_vault(&app, &contracts), None);
+    assert_eq!(
+        app.wrap()
+            .query_balance(&contracts.vault, DENOM)
+            .unwrap()
+            .amount,
+        Uint128::zero()
+    );
+}
@@ -0,0 +1,406 @@
+use crate::error::ContractError;
+use crate::msg::{
+    AskMsg, AskOffset, AsksResponse, BuyMsg, ExecuteMsg, InstantiateMsg, PriceOffset, QueryMsg,
+};
+use crate::state::{asks, denom_key, Ask};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::{Cw721ExecuteMsg, TradingStatusResponse};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw_storage_plus::Bound;
+use cw_utils::one_coin;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-marketplace";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => execute_receive_nft(deps, info, sender, token_id, msg),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender,
+            amount,
+            msg,
+        }) => execute_receive(deps, info, sender, amount, msg),
+        ExecuteMsg::Buy {
+            collection,
+            token_id,
+        } => execute_buy(deps, info, collection, token_id),
+        ExecuteMsg::UpdateAsk {
+            collection,
+            token_id,
+            denom,
+            price,
+        } => execute_update_ask(deps, info, collection, token_id, denom, price),
+        ExecuteMsg::CancelAsk {
+            collection,
+            token_id,
+        } => execute_cancel_ask(deps, info, collection, token_id),
+    }
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let AskMsg { denom, price } = from_json(&msg)?;
+    // the sender is the cw721 contract, the NFT is already owned by this contract
+    let ask = Ask {
+        collection: info.sender,
+        token_id,
+        seller: deps.api.addr_validate(&sender)?,
+        denom: validate_denom(deps.as_ref(), denom)?,
+        price: validate_price(price)?,
+    };
+    asks().save(
+        deps.storage,
+        (ask.collection.clone(), ask.token_id.clone()),
+        &ask,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_ask")
+        .add_attribute("collection", ask.collection)
+        .add_attribute("token_id", ask.token_id)
+        .add_attribute("seller", ask.seller)
+        .add_attribute("denom", denom_key(&ask.denom))
+        .add_attribute("price", ask.price))
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let BuyMsg {
+        collection,
+        token_id,
+    } = from_json(&msg)?;
+    let buyer = deps.api.addr_validate(&sender)?;
+    // the sender is the cw20 contract, checked against the ask's denom
+    buy(
+        deps,
+        buyer,
+        collection,
+        token_id,
+        Denom::Cw20(info.sender),
+        amount,
+    )
+}
+
+pub fn execute_buy(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let payment = one_coin(&info)?;
+    buy(
+        deps,
+        info.sender,
+        collection,
+        token_id,
+        Denom::Native(payment.denom),
+        payment.amount,
+    )
+}
+
+fn buy(
+    deps: DepsMut,
+    buyer: Addr,
+    collection: String,
+    token_id: String,
+    denom: Denom,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let ask = asks().load(deps.storage, (collection.clone(), token_id.clone()))?;
+    if ask.denom != denom || ask.price != amount {
+        return Err(ContractError::WrongPayment {});
+    }
+
+    let cw721 = DefaultCw721Helper::new(collection.clone());
+    // collections not supporting the trading status query are considered open
+    if let Ok(TradingStatusResponse {
+        start_trading_time: Some(start_trading_time),
+        is_open: false,
+    }) = cw721.trading_status(&deps.querier)
+    {
+        return Err(ContractError::TradingNotStarted { start_trading_time });
+    }
+
+    let mut messages = vec![];
+    let mut royalty_amount = Uint128::zero();
+    for (recipient, amount) in
+        cw721.royalty_payouts(deps.api, &deps.querier, &token_id, ask.price)?
+    {
+        royalty_amount = royalty_amount
+            .checked_add(amount)
+            .map_err(|_| ContractError::RoyaltiesExceedPrice {})?;
+        messages.push(payout_msg(&ask.denom, recipient, amount)?);
+    }
+    let seller_amount = ask
+        .price
+        .checked_sub(royalty_amount)
+        .map_err(|_| ContractError::RoyaltiesExceedPrice {})?;
+    if !seller_amount.is_zero() {
+        messages.push(payout_msg(&ask.denom, ask.seller.clone(), seller_amount)?);
+    }
+    messages.push(cw721.call(Cw721ExecuteMsg::TransferNft {
+        recipient: buyer.to_string(),
+        token_id: token_id.clone(),
+    })?);
+
+    asks().remove(deps.storage, (collection.clone(), token_id.clone()))?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "buy")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("seller", ask.seller)
+        .add_attribute("buyer", buyer)
+        .add_attribute("price", ask.price)
+        .add_attribute("royalty_amount", royalty_amount))
+}
+
+fn payout_msg(denom: &Denom, recipient: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.into_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+        Denom::Cw20(cw20_address) => WasmMsg::Execute {
+            contract_addr: cw20_address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+pub fn execute_update_ask(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+    denom: Denom,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let key = (collection, token_id);
+    let mut ask = asks().load(deps.storage, key.clone())?;
+    if ask.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    ask.denom = validate_denom(deps.as_ref(), denom)?;
+    ask.price = validate_price(price)?;
+    asks().save(deps.storage, key, &ask)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_ask")
+        .add_attribute("collection", ask.collection)
+        .add_attribute("token_id", ask.token_id)
+        .add_attribute("denom", denom_key(&ask.denom))
+        .add_attribute("price", ask.price))
+}
+
+pub fn execute_cancel_ask(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let key = (collection, token_id);
+    let ask = asks().load(deps.storage, key.clone())?;
+    if ask.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    asks().remove(deps.storage, key)?;
+
+    // return NFT to seller
+    let msg =
+        DefaultCw721Helper::new(ask.collection.clone()).call(Cw721ExecuteMsg::TransferNft {
+            recipient: ask.seller.to_string(),
+            token_id: ask.token_id.clone(),
+        })?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "cancel_ask")
+        .add_attribute("collection", ask.collection)
+        .add_attribute("token_id", ask.token_id))
+}
+
+fn validate_denom(deps: Deps, denom: Denom) -> StdResult<Denom> {
+    Ok(match denom {
+        Denom::Native(denom) => Denom::Native(denom),
+        Denom::Cw20(address) => Denom::Cw20(deps.api.addr_validate(address.as_str())?),
+    })
+}
+
+fn validate_price(price: Uint128) -> Result<Uint128, ContractError> {
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    Ok(price)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Ask {
+            collection,
+            token_id,
+        } => to_json_binary(&query_ask(deps, collection, token_id)?),
+        QueryMsg::AsksByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_json_binary(&query_asks_by_collection(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AsksBySeller {
+            seller,
+            start_after,
+            limit,
+        } => to_json_binary(&query_asks_by_seller(deps, seller, start_after, limit)?),
+        QueryMsg::AsksByPrice {
+            collection,
+            denom,
+            start_after,
+            limit,
+        } => to_json_binary(&query_asks_by_price(
+            deps,
+            collection,
+            denom,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+pub fn query_ask(deps: Deps, collection: String, token_id: String) -> StdResult<Option<Ask>> {
+    let collection = deps.api.addr_validate(&collection)?;
+    asks().may_load(deps.storage, (collection, token_id))
+}
+
+pub fn query_asks_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AsksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let asks = asks()
+        .prefix(collection)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, ask)| ask))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AsksResponse { asks })
+}
+
+pub fn query_asks_by_seller(
+    deps: Deps,
+    seller: String,
+    start_after: Option<AskOffset>,
+    limit: Option<u32>,
+) -> StdResult<AsksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|offset| {
+            let collection = deps.api.addr_validate(&offset.collection)?;
+            StdResult::Ok(Bound::exclusive((collection, offset.token_id)))
+        })
+        .transpose()?;
+
+    let seller = deps.api.addr_validate(&seller)?;
+    let asks = asks()
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, ask)| ask))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AsksResponse { asks })
+}
+
+pub fn query_asks_by_price(
+    deps: Deps,
+    collection: String,
+    denom: Denom,
+    start_after: Option<PriceOffset>,
+    limit: Option<u32>,
+) -> StdResult<AsksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let collection = deps.api.addr_validate(&collection)?;
+    let start = start_after.map(|offset| {
+        Bound::exclusive((offset.price.u128(), (collection.clone(), offset.token_id)))
+    });
+
+    let asks = asks()
+        .idx
+        .price
+        .sub_prefix((collection, denom_key(&denom)))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, ask)| ask))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AsksResponse { asks })
+}
@@ -0,0 +1,27 @@
+use cosmwasm_std::{StdError, Timestamp};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("InvalidPrice")]
+    InvalidPrice {},
+
+    #[error("WrongPayment")]
+    WrongPayment {},
+
+    #[error("Trading not started yet. Trading starts at {start_trading_time}")]
+    TradingNotStarted { start_trading_time: Timestamp },
+
+    #[error("RoyaltiesExceedPrice")]
+    RoyaltiesExceedPrice {},
+}
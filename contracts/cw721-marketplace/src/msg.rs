@@ -0,0 +1,98 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::Ask;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// List an NFT for sale, via cw721 `SendNft` with an `AskMsg`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Buy an NFT with CW20 payment, via CW20 `Send` with a `BuyMsg`.
+    Receive(Cw20ReceiveMsg),
+    /// Buy an NFT with native payment, funds must match the ask.
+    Buy {
+        collection: String,
+        token_id: String,
+    },
+    /// Only the seller can update the price of an ask.
+    UpdateAsk {
+        collection: String,
+        token_id: String,
+        denom: Denom,
+        price: Uint128,
+    },
+    /// Only the seller can cancel an ask, the NFT is returned to the seller.
+    CancelAsk {
+        collection: String,
+        token_id: String,
+    },
+}
+
+/// Ask passed along a cw721 `SendNft`.
+#[cw_serde]
+pub struct AskMsg {
+    pub denom: Denom,
+    pub price: Uint128,
+}
+
+/// Message passed along a CW20 `Send`.
+#[cw_serde]
+pub struct BuyMsg {
+    pub collection: String,
+    pub token_id: String,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the ask for the given NFT, if listed.
+    #[returns(Option<Ask>)]
+    Ask {
+        collection: String,
+        token_id: String,
+    },
+    /// Returns asks of a collection, ordered by token id.
+    #[returns(AsksResponse)]
+    AsksByCollection {
+        collection: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns asks of a seller, ordered by collection and token id.
+    #[returns(AsksResponse)]
+    AsksBySeller {
+        seller: String,
+        start_after: Option<AskOffset>,
+        limit: Option<u32>,
+    },
+    /// Returns asks of a collection in the given denom, ordered by price.
+    #[returns(AsksResponse)]
+    AsksByPrice {
+        collection: String,
+        denom: Denom,
+        start_after: Option<PriceOffset>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct AskOffset {
+    pub collection: String,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub struct PriceOffset {
+    pub price: Uint128,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub struct AsksResponse {
+    pub asks: Vec<Ask>,
+}
@@ -0,0 +1,58 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Denom;
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+
+/// An NFT listed for sale. The marketplace holds custody of the NFT until it is sold or the ask
+/// is cancelled.
+#[cw_serde]
+pub struct Ask {
+    pub collection: Addr,
+    pub token_id: String,
+    pub seller: Addr,
+    /// Native denom or CW20 token the buyer has to pay in.
+    pub denom: Denom,
+    pub price: Uint128,
+}
+
+/// Asks are keyed by collection and token id.
+pub type AskKey = (Addr, String);
+
+pub struct AskIndexes<'a> {
+    pub seller: MultiIndex<'a, Addr, Ask, AskKey>,
+    /// Asks of a collection in a given denom, ordered by price.
+    pub price: MultiIndex<'a, (Addr, String, u128), Ask, AskKey>,
+}
+
+impl<'a> IndexList<Ask> for AskIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Ask>> + '_> {
+        let v: Vec<&dyn Index<Ask>> = vec![&self.seller, &self.price];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn asks<'a>() -> IndexedMap<'a, AskKey, Ask, AskIndexes<'a>> {
+    let indexes = AskIndexes {
+        seller: MultiIndex::new(|_, ask| ask.seller.clone(), "asks", "asks__seller"),
+        price: MultiIndex::new(
+            |_, ask| {
+                (
+                    ask.collection.clone(),
+                    denom_key(&ask.denom),
+                    ask.price.u128(),
+                )
+            },
+            "asks",
+            "asks__price",
+        ),
+    };
+    IndexedMap::new("asks", indexes)
+}
+
+/// Storage key of a denom, used by the price index.
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{denom}"),
+        Denom::Cw20(address) => format!("cw20:{address}"),
+    }
+}
@@ -0,0 +1,456 @@
+use crate::{
+    contract::{execute, instantiate, query},
+    msg::{AskMsg, AsksResponse, BuyMsg, ExecuteMsg, InstantiateMsg, PriceOffset, QueryMsg},
+    state::Ask,
+    ContractError,
+};
+use cosmwasm_std::{coins, to_json_binary, Addr, Decimal, Empty, Timestamp, Uint128};
+use cw20::{Cw20ExecuteMsg, Denom};
+use cw721::msg::{CollectionExtensionMsg, RoyaltyInfoResponse};
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_nfts_multi_test::{
+    fund, instantiate_cw20, instantiate_cw721, query_cw20_balance, query_owner, DENOM,
+};
+
+struct Contracts {
+    cw721: Addr,
+    cw20: Addr,
+    marketplace: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    artist: Addr,
+    buyer: Addr,
+}
+
+fn marketplace_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn users(app: &App) -> Users {
+    Users {
+        creator: app.api().addr_make("creator"),
+        artist: app.api().addr_make("artist"),
+        buyer: app.api().addr_make("buyer"),
+    }
+}
+
+/// Creates a collection with 5% royalties for the artist and tokens "1" to "3" owned by the creator,
+/// a cw20 token with a balance for the buyer, and the marketplace.
+fn setup(app: &mut App, start_trading_time: Option<Timestamp>) -> Contracts {
+    let Users {
+        creator,
+        artist,
+        buyer,
+    } = users(app);
+
+    let cw721 = instantiate_cw721(
+        app,
+        &creator,
+        Some(CollectionExtensionMsg {
+            description: None,
+            image: None,
+            external_link: None,
+            explicit_content: None,
+            start_trading_time,
+            royalty_info: Some(RoyaltyInfoResponse {
+                payment_address: artist.to_string(),
+                share: Decimal::percent(5),
+            }),
+        }),
+        &[("1", &creator), ("2", &creator), ("3", &creator)],
+    );
+    let cw20 = instantiate_cw20(app, &creator, &[(&buyer, 1000)]);
+
+    let marketplace_code_id = app.store_code(marketplace_contract());
+    let marketplace = app
+        .instantiate_contract(
+            marketplace_code_id,
+            creator,
+            &InstantiateMsg {},
+            &[],
+            "marketplace",
+            None,
+        )
+        .unwrap();
+
+    fund(app, &buyer, 1000);
+
+    Contracts {
+        cw721,
+        cw20,
+        marketplace,
+    }
+}
+
+fn list(app: &mut App, contracts: &Contracts, seller: &Addr, token_id: &str, ask: AskMsg) {
+    app.execute_contract(
+        seller.clone(),
+        contracts.cw721.clone(),
+        &Cw721ExecuteMsg::SendNft {
+            contract: contracts.marketplace.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(&ask).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn native_ask(price: u128) -> AskMsg {
+    AskMsg {
+        denom: Denom::Native(DENOM.to_string()),
+        price: Uint128::new(price),
+    }
+}
+
+fn query_ask(app: &App, contracts: &Contracts, token_id: &str) -> Option<Ask> {
+    app.wrap()
+        .query_wasm_smart(
+            &contracts.marketplace,
+            &QueryMsg::Ask {
+                collection: contracts.cw721.to_string(),
+                token_id: token_id.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_buy_native() {
+    let mut app = App::default();
+    let contracts = setup(&mut app, None);
+    let Users {
+        creator,
+        artist,
+        buyer,
+    } = users(&app);
+
+    list(&mut app, &contracts, &creator, "1", native_ask(1000));
+    assert_eq!(
+        query_owner(&app, &contracts.cw721, "1"),
+        contracts.marketplace
+    );
+    assert_eq!(
+        query_ask(&app, &contracts, "1"),
+        Some(Ask {
+            collection: contracts.cw721.clone(),
+            token_id: "1".to_string(),
+            seller: creator.clone(),
+            denom: Denom::Native(DENOM.to_string()),
+            price: Uint128::new(1000),
+        })
+    );
+
+    // payment must match the ask
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            contracts.marketplace.clone(),
+            &ExecuteMsg::Buy {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+            },
+            &coins(999, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WrongPayment {}
+    );
+
+    // royalties are paid to the artist, the remainder to the seller
+    app.execute_contract(
+        buyer.clone(),
+        contracts.marketplace.clone(),
+        &ExecuteMsg::Buy {
+            collection: contracts.cw721.to_string(),
+            token_id: "1".to_string(),
+        },
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), buyer);
+    assert_eq!(query_ask(&app, &contracts, "1"), None);
+    let balance = |address: &Addr| app.wrap().query_balance(address, DENOM).unwrap().amount;
+    assert_eq!(balance(&artist), Uint128::new(50));
+    assert_eq!(balance(&creator), Uint128::new(950));
+    assert_eq!(balance(&buyer), Uint128::zero());
+    assert_eq!(balance(&contracts.marketplace), Uint128::zero());
+}
+
+#[test]
+fn test_buy_cw20() {
+    let mut app = App::default();
+    let contracts = setup(&mut app, None);
+    let Users {
+        creator,
+        artist,
+        buyer,
+    } = users(&app);
+
+    list(
+        &mut app,
+        &contracts,
+        &creator,
+        "1",
+        AskMsg {
+            denom: Denom::Cw20(contracts.cw20.clone()),
+            price: Uint128::new(500),
+        },
+    );
+
+    // native payment is rejected for a cw20 ask
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            contracts.marketplace.clone(),
+            &ExecuteMsg::Buy {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+            },
+            &coins(500, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WrongPayment {}
+    );
+
+    app.execute_contract(
+        buyer.clone(),
+        contracts.cw20.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: contracts.marketplace.to_string(),
+            amount: Uint128::new(500),
+            msg: to_json_binary(&BuyMsg {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), buyer);
+    assert_eq!(
+        query_cw20_balance(&app, &contracts.cw20, &artist),
+        Uint128::new(25)
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &contracts.cw20, &creator),
+        Uint128::new(475)
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &contracts.cw20, &buyer),
+        Uint128::new(500)
+    );
+}
+
+#[test]
+fn test_update_and_cancel_ask() {
+    let mut app = App::default();
+    let contracts = setup(&mut app, None);
+    let Users { creator, buyer, .. } = users(&app);
+
+    list(&mut app, &contracts, &creator, "1", native_ask(1000));
+
+    // only seller can update
+    let update_msg = ExecuteMsg::UpdateAsk {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+        denom: Denom::Native(DENOM.to_string()),
+        price: Uint128::new(500),
+    };
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            contracts.marketplace.clone(),
+            &update_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    app.execute_contract(
+        creator.clone(),
+        contracts.marketplace.clone(),
+        &update_msg,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_ask(&app, &contracts, "1").unwrap().price,
+        Uint128::new(500)
+    );
+
+    // price must not be zero
+    let err = app
+        .execute_contract(
+            creator.clone(),
+            contracts.marketplace.clone(),
+            &ExecuteMsg::UpdateAsk {
+                collection: contracts.cw721.to_string(),
+                token_id: "1".to_string(),
+                denom: Denom::Native(DENOM.to_string()),
+                price: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidPrice {}
+    );
+
+    // only seller can cancel, NFT is returned
+    let cancel_msg = ExecuteMsg::CancelAsk {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            contracts.marketplace.clone(),
+            &cancel_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    app.execute_contract(
+        creator.clone(),
+        contracts.marketplace.clone(),
+        &cancel_msg,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), creator);
+    assert_eq!(query_ask(&app, &contracts, "1"), None);
+}
+
+#[test]
+fn test_start_trading_time() {
+    let mut app = App::default();
+    let start_trading_time = app.block_info().time.plus_seconds(100);
+    let contracts = setup(&mut app, Some(start_trading_time));
+    let Users { creator, buyer, .. } = users(&app);
+
+    // minter can list before trading starts
+    list(&mut app, &contracts, &creator, "1", native_ask(1000));
+
+    let buy_msg = ExecuteMsg::Buy {
+        collection: contracts.cw721.to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            contracts.marketplace.clone(),
+            &buy_msg,
+            &coins(1000, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::TradingNotStarted { start_trading_time }
+    );
+
+    app.update_block(|block| block.time = start_trading_time);
+    app.execute_contract(
+        buyer.clone(),
+        contracts.marketplace.clone(),
+        &buy_msg,
+        &coins(1000, DENOM),
+    )
+    .unwrap();
+    assert_eq!(query_owner(&app, &contracts.cw721, "1"), buyer);
+}
+
+#[test]
+fn test_queries() {
+    let mut app = App::default();
+    let contracts = setup(&mut app, None);
+    let Users { creator, .. } = users(&app);
+
+    list(&mut app, &contracts, &creator, "1", native_ask(300));
+    list(&mut app, &contracts, &creator, "2", native_ask(100));
+    list(
+        &mut app,
+        &contracts,
+        &creator,
+        "3",
+        AskMsg {
+            denom: Denom::Cw20(contracts.cw20.clone()),
+            price: Uint128::new(200),
+        },
+    );
+    let token_ids = |res: AsksResponse| -> Vec<String> {
+        res.asks.into_iter().map(|ask| ask.token_id).collect()
+    };
+
+    // by collection
+    let res: AsksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contracts.marketplace,
+            &QueryMsg::AsksByCollection {
+                collection: contracts.cw721.to_string(),
+                start_after: Some("1".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(token_ids(res), vec!["2", "3"]);
+
+    // by seller
+    let res: AsksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contracts.marketplace,
+            &QueryMsg::AsksBySeller {
+                seller: creator.to_string(),
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    assert_eq!(token_ids(res), vec!["1", "2"]);
+
+    // by price, only asks in the given denom
+    let res: AsksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contracts.marketplace,
+            &QueryMsg::AsksByPrice {
+                collection: contracts.cw721.to_string(),
+                denom: Denom::Native(DENOM.to_string()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(token_ids(res), vec!["2", "1"]);
+    let res: AsksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &contracts.marketplace,
+            &QueryMsg::AsksByPrice {
+                collection: contracts.cw721.to_string(),
+                denom: Denom::Native(DENOM.to_string()),
+                start_after: Some(PriceOffset {
+                    price: Uint128::new(100),
+                    token_id: "2".to_string(),
+                }),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(token_ids(res), vec!["1"]);
+}
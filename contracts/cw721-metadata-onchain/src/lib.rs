@@ -24,6 +24,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 ///     minter: None,
 ///     creator: None,
 ///     withdraw_address: None,
+///     max_supply: None,
 /// };
 /// // ...
 /// // mint:
@@ -40,7 +41,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 ///     ..NftExtensionMsg::default()
 /// });
 /// let exec_msg = ExecuteMsg::Mint {
-///     token_id: token_id.to_string(),
+///     token_id: Some(token_id.to_string()),
 ///     owner: "john".to_string(),
 ///     token_uri: token_uri.clone(),
 ///     extension: extension.clone(),
@@ -129,6 +130,7 @@ mod tests {
                 minter: None,
                 creator: None,
                 withdraw_address: None,
+                max_supply: None,
             },
         )
         .unwrap();
@@ -150,6 +152,7 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         contract
             .instantiate(deps.as_mut(), &mock_env(), &info.clone(), init_msg)
@@ -168,7 +171,7 @@ mod tests {
             ..NftExtensionMsg::default()
         });
         let exec_msg = ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: extension.clone(),
@@ -4,8 +4,6 @@ use cw721::extension::Cw721EmptyExtensions;
 
 #[allow(deprecated)]
 pub mod msg;
-pub mod query;
-pub mod state;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw721-non-transferable";
@@ -16,15 +14,18 @@ pub type Cw721NonTransferableContract<'a> = Cw721EmptyExtensions<'a>;
 #[cfg(not(feature = "library"))]
 pub mod entry {
     use super::*;
-    use crate::query::admin;
-    use crate::state::{Config, CONFIG};
+    use cosmwasm_schema::cw_serde;
     use cosmwasm_std::{
-        entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+        entry_point, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, Storage,
     };
     use cw721::error::Cw721ContractError;
-    use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg};
+    use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg};
+    use cw721::state::Cw721Config;
     use cw721::traits::{Cw721Execute, Cw721Query};
-    use cw721::{EmptyOptionalCollectionExtensionMsg, EmptyOptionalNftExtensionMsg};
+    use cw721::{
+        EmptyOptionalCollectionExtensionMsg, EmptyOptionalNftExtensionMsg, TransferPolicy,
+    };
+    use cw_storage_plus::Item;
 
     #[entry_point]
     pub fn instantiate(
@@ -33,15 +34,10 @@ pub mod entry {
         info: MessageInfo,
         msg: InstantiateMsg<EmptyOptionalCollectionExtensionMsg>,
     ) -> Result<Response, Cw721ContractError> {
-        let admin_addr: Option<Addr> = msg
-            .admin
-            .as_deref()
-            .map(|s| deps.api.addr_validate(s))
-            .transpose()?;
-
-        let config = Config { admin: admin_addr };
-
-        CONFIG.save(deps.storage, &config)?;
+        let transfer_policy = msg.transfer_policy.unwrap_or(TransferPolicy::Soulbound);
+        if transfer_policy == TransferPolicy::Transferable {
+            return Err(Cw721ContractError::NonTransferable {});
+        }
 
         let cw721_instantiate_msg = Cw721InstantiateMsg {
             name: msg.name,
@@ -50,6 +46,7 @@ pub mod entry {
             minter: msg.minter,
             creator: msg.creator,
             withdraw_address: msg.withdraw_address,
+            max_supply: msg.max_supply,
         };
 
         Cw721NonTransferableContract::default().instantiate_with_version(
@@ -60,6 +57,9 @@ pub mod entry {
             "contract_name",
             "contract_version",
         )?;
+        Cw721Config::<Option<Empty>>::default()
+            .transfer_policy
+            .save(deps.storage, &transfer_policy)?;
 
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -79,47 +79,65 @@ pub mod entry {
             Empty,
         >,
     ) -> Result<Response, Cw721ContractError> {
-        let config = CONFIG.load(deps.storage)?;
-        match config.admin {
-            Some(admin) => {
-                if admin == info.sender {
-                    Cw721EmptyExtensions::default().execute(deps, &env, &info, msg)
-                } else {
-                    Err(Cw721ContractError::Ownership(
-                        cw721::OwnershipError::NotOwner,
-                    ))
-                }
-            }
-            None => match msg {
-                Cw721ExecuteMsg::Mint {
-                    token_id,
-                    owner,
-                    token_uri,
-                    extension,
-                } => Cw721NonTransferableContract::default()
-                    .mint(deps, &env, &info, token_id, owner, token_uri, extension),
-                _ => Err(Cw721ContractError::Ownership(
-                    cw721::OwnershipError::NotOwner,
-                )),
-            },
+        match msg {
+            // creator may switch between soulbound and revocable, but never make NFTs transferable
+            Cw721ExecuteMsg::SetTransferPolicy {
+                transfer_policy: TransferPolicy::Transferable,
+            } => Err(Cw721ContractError::NonTransferable {}),
+            _ => Cw721NonTransferableContract::default().execute(deps, &env, &info, msg),
         }
     }
 
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, Cw721ContractError> {
-        match msg {
-            QueryMsg::Admin {} => Ok(to_json_binary(&admin(deps)?)?),
-            _ => Cw721EmptyExtensions::default().query(deps, &env, msg.into()),
-        }
+        Cw721NonTransferableContract::default().query(deps, &env, msg.into())
     }
 
     #[entry_point]
     pub fn migrate(
         deps: DepsMut,
         env: Env,
-        msg: cw721::msg::Cw721MigrateMsg,
+        msg: Cw721MigrateMsg,
     ) -> Result<Response, Cw721ContractError> {
-        let contract = Cw721EmptyExtensions::default();
-        contract.migrate(deps, env, msg, CONTRACT_NAME, CONTRACT_VERSION)
+        let (response, legacy_admin) = migrate_legacy_config(deps.storage)?;
+        // the legacy admin keeps transferring NFTs as creator, unless another creator is set
+        let Cw721MigrateMsg::WithUpdate { minter, creator } = msg;
+        let msg = Cw721MigrateMsg::WithUpdate {
+            minter,
+            creator: creator.or(legacy_admin.map(String::from)),
+        };
+        let contract = Cw721NonTransferableContract::default();
+        let migrate_response = contract.migrate(deps, env, msg, CONTRACT_NAME, CONTRACT_VERSION)?;
+        Ok(response.add_attributes(migrate_response.attributes))
+    }
+
+    /// Contracts prior to transfer policies stored an optional admin in a legacy config. With an admin,
+    /// only the admin could transfer NFTs, which maps to `Revocable` (transfers by minter and creator),
+    /// and the admin is returned to be set as creator. Without an admin, NFTs could not be transferred
+    /// at all, which maps to `Soulbound`. The legacy config is removed.
+    fn migrate_legacy_config(
+        storage: &mut dyn Storage,
+    ) -> Result<(Response, Option<Addr>), Cw721ContractError> {
+        #[cw_serde]
+        struct LegacyConfig {
+            admin: Option<Addr>,
+        }
+        let legacy_config_store: Item<LegacyConfig> = Item::new("config");
+        let config = Cw721Config::<Option<Empty>>::default();
+        if config.transfer_policy.may_load(storage)?.is_some() {
+            return Ok((Response::default(), None));
+        }
+        let admin = legacy_config_store
+            .may_load(storage)?
+            .and_then(|legacy_config| legacy_config.admin);
+        let transfer_policy = match admin {
+            Some(_) => TransferPolicy::Revocable,
+            None => TransferPolicy::Soulbound,
+        };
+        legacy_config_store.remove(storage);
+        config.transfer_policy.save(storage, &transfer_policy)?;
+        let response =
+            Response::default().add_attribute("transfer_policy", format!("{:?}", transfer_policy));
+        Ok((response, admin))
     }
 }
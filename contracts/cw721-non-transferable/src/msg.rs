@@ -1,11 +1,14 @@
 use cosmwasm_schema::cw_serde;
 
 use cosmwasm_std::Empty;
-use cw721::{msg::Cw721QueryMsg, EmptyOptionalCollectionExtension, EmptyOptionalNftExtension};
+use cw721::{
+    msg::Cw721QueryMsg, EmptyOptionalCollectionExtension, EmptyOptionalNftExtension, TransferPolicy,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg<TCollectionExtension> {
-    pub admin: Option<String>,
+    /// Defaults to `TransferPolicy::Soulbound`. `TransferPolicy::Transferable` is not allowed.
+    pub transfer_policy: Option<TransferPolicy>,
     /// Name of the NFT contract
     pub name: String,
     /// Symbol of the NFT contract
@@ -15,11 +18,12 @@ pub struct InstantiateMsg<TCollectionExtension> {
     pub minter: Option<String>,
     pub creator: Option<String>,
     pub withdraw_address: Option<String>,
+    pub max_supply: Option<u64>,
 }
 
 #[cw_serde]
 pub enum QueryMsg {
-    Admin {},
+    GetTransferPolicy {},
 
     // -- below copied from Cw721QueryMsg
     OwnerOf {
@@ -124,15 +128,34 @@ impl From<QueryMsg>
             QueryMsg::GetMinterOwnership {} => Cw721QueryMsg::GetMinterOwnership {},
             QueryMsg::GetCreatorOwnership {} => Cw721QueryMsg::GetCreatorOwnership {},
             QueryMsg::GetWithdrawAddress {} => Cw721QueryMsg::GetWithdrawAddress {},
-            QueryMsg::AllOperators { .. } => unreachable!("AllOperators is not supported!"),
-            QueryMsg::Approval { .. } => unreachable!("Approval is not supported!"),
-            QueryMsg::Approvals { .. } => unreachable!("Approvals is not supported!"),
-            QueryMsg::Admin { .. } => unreachable!("Approvals is not supported!"),
+            QueryMsg::GetTransferPolicy {} => Cw721QueryMsg::GetTransferPolicy {},
+            QueryMsg::AllOperators {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::AllOperators {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            },
+            QueryMsg::Approval {
+                token_id,
+                spender,
+                include_expired,
+            } => Cw721QueryMsg::Approval {
+                token_id,
+                spender,
+                include_expired,
+            },
+            QueryMsg::Approvals {
+                token_id,
+                include_expired,
+            } => Cw721QueryMsg::Approvals {
+                token_id,
+                include_expired,
+            },
         }
     }
 }
-
-#[cw_serde]
-pub struct AdminResponse {
-    pub admin: Option<String>,
-}
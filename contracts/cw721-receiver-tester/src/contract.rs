@@ -1,54 +1,118 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{from_json, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+};
+use cw1155::receiver::ReceivedTokens;
+use cw721::receiver::{ReceivedNft, ReceiverAllowlist};
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InnerMsg, InstantiateMsg, QueryMsg};
+use crate::state::{ALLOWLIST, ALLOWLIST_ENABLED};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
     _info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    ALLOWLIST_ENABLED.save(deps.storage, &msg.allowed_collections.is_some())?;
+    for collection in msg.allowed_collections.unwrap_or_default() {
+        let collection = deps.api.addr_validate(&collection)?;
+        ALLOWLIST.allow(deps.storage, &collection)?;
+    }
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let allowlist = allowlist(deps.storage)?;
     match msg {
         ExecuteMsg::ReceiveNft(receive_msg) => {
-            let inner: InnerMsg = from_json(&receive_msg.msg)?;
-            match inner {
-                InnerMsg::Succeed => Ok(Response::new()
-                    .add_attributes([
-                        ("action", "receive_nft"),
-                        ("token_id", receive_msg.token_id.as_str()),
-                        ("sender", receive_msg.sender.as_str()),
-                        ("msg", receive_msg.msg.to_base64().as_str()),
-                    ])
-                    .set_data(
-                        [
-                            receive_msg.token_id,
-                            receive_msg.sender,
-                            receive_msg.msg.to_base64(),
-                        ]
-                        .concat()
-                        .as_bytes(),
-                    )),
-                InnerMsg::Fail => Err(ContractError::Failed {}),
-            }
+            let msg = receive_msg.msg.to_base64();
+            let received = receive_msg.into_received(deps.as_ref(), &info, allowlist)?;
+            execute_receive_nft(received, msg)
+        }
+        ExecuteMsg::Receive(receive_msg) => {
+            let received = receive_msg.into_received(deps.as_ref(), &info, allowlist)?;
+            execute_receive_tokens(received)
+        }
+        ExecuteMsg::BatchReceive(receive_msg) => {
+            let received = receive_msg.into_received(deps.as_ref(), &info, allowlist)?;
+            execute_receive_tokens(received)
         }
     }
 }
 
+fn execute_receive_nft(
+    received: ReceivedNft<InnerMsg>,
+    msg: String,
+) -> Result<Response, ContractError> {
+    match received.msg {
+        InnerMsg::Succeed => Ok(Response::new()
+            .add_attributes([
+                ("action", "receive_nft"),
+                ("token_id", received.token_id.as_str()),
+                ("sender", received.sender.as_str()),
+                ("msg", msg.as_str()),
+            ])
+            .set_data(
+                [received.token_id, received.sender.into_string(), msg]
+                    .concat()
+                    .as_bytes(),
+            )),
+        InnerMsg::Fail => Err(ContractError::Failed {}),
+        // `sender` may be an operator, so the NFT is rejected instead of being sent back to it
+        InnerMsg::Bounce => Err(ContractError::CannotBounceNft {}),
+    }
+}
+
+fn execute_receive_tokens(received: ReceivedTokens<InnerMsg>) -> Result<Response, ContractError> {
+    let tokens = received
+        .tokens
+        .iter()
+        .map(|token| format!("{}:{}", token.token_id, token.amount))
+        .collect::<Vec<_>>()
+        .join(",");
+    match received.msg {
+        InnerMsg::Succeed => Ok(Response::new().add_attributes([
+            ("action", "receive_tokens"),
+            ("operator", received.operator.as_str()),
+            ("tokens", tokens.as_str()),
+        ])),
+        InnerMsg::Fail => Err(ContractError::Failed {}),
+        InnerMsg::Bounce => Ok(Response::new()
+            .add_message(received.bounce_msg()?)
+            .add_attributes([
+                ("action", "bounce_tokens"),
+                ("recipient", received.bounce_recipient().as_str()),
+                ("tokens", tokens.as_str()),
+            ])),
+    }
+}
+
+fn allowlist(storage: &dyn Storage) -> StdResult<Option<&'static ReceiverAllowlist<'static>>> {
+    Ok(ALLOWLIST_ENABLED.load(storage)?.then_some(&ALLOWLIST))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-    unimplemented!()
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::AllowedCollections { start_after, limit } => {
+            if !ALLOWLIST_ENABLED.load(deps.storage)? {
+                return to_json_binary(&None::<Vec<String>>);
+            }
+            let start_after = start_after
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?;
+            let collections = ALLOWLIST.collections(deps.storage, start_after.as_ref(), limit)?;
+            to_json_binary(&Some(collections))
+        }
+    }
 }
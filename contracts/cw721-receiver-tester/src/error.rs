@@ -1,4 +1,6 @@
 use cosmwasm_std::StdError;
+use cw1155::error::Cw1155ContractError;
+use cw721::error::Cw721ContractError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,8 +8,17 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Cw721(#[from] Cw721ContractError),
+
+    #[error("{0}")]
+    Cw1155(#[from] Cw1155ContractError),
+
     #[error("I failed because you asked me to do so")]
     Failed {},
+
+    #[error("NFTs can't be bounced, since the sender is not necessarily their owner")]
+    CannotBounceNft {},
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
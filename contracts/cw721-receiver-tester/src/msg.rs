@@ -1,23 +1,40 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+use cw1155::receiver::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
 use cw721_base::receiver::Cw721ReceiveMsg;
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    /// Collections tokens are accepted from. If `None`, tokens of any collection are accepted.
+    pub allowed_collections: Option<Vec<String>>,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
     ReceiveNft(Cw721ReceiveMsg),
+    Receive(Cw1155ReceiveMsg),
+    BatchReceive(Cw1155BatchReceiveMsg),
 }
 
 #[cw_serde]
 pub enum InnerMsg {
     Succeed,
     Fail,
+    /// Accept the hook, but send cw1155 tokens back. cw721 NFTs are rejected instead, since the
+    /// hook doesn't carry their owner.
+    Bounce,
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    /// Returns the allowlist, `None` if tokens of any collection are accepted.
+    #[returns(Option<Vec<Addr>>)]
+    AllowedCollections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
 
 #[cfg(test)]
 mod tests {
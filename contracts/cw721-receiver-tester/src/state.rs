@@ -0,0 +1,6 @@
+use cw721::receiver::ReceiverAllowlist;
+use cw_storage_plus::Item;
+
+/// Whether only collections of the allowlist are accepted.
+pub const ALLOWLIST_ENABLED: Item<bool> = Item::new("allowlist_enabled");
+pub const ALLOWLIST: ReceiverAllowlist = ReceiverAllowlist::new("allowlist");
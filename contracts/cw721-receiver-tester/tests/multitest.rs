@@ -98,6 +98,247 @@ fn test_cw721_base_receive_fail() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_cw721_base_receive_bounce() {
+    use cw721_base::msg::*;
+    use cw721_receiver_tester::{msg::InnerMsg, ContractError};
+
+    let mut app = App::default();
+    let admin = app.api().addr_make("admin");
+
+    let Contracts {
+        nft_contract,
+        receiver_contract,
+    } = setup_contracts(&mut app, admin.clone());
+
+    // NFT is not sent back to the sender, which may be an operator, but rejected
+    let err = app
+        .execute_contract(
+            admin.clone(),
+            nft_contract.clone(),
+            &ExecuteMsg::SendNft {
+                contract: receiver_contract.to_string(),
+                token_id: "test".to_string(),
+                msg: to_json_binary(&InnerMsg::Bounce).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::CannotBounceNft {}.to_string()
+    );
+
+    let owner: cw721::msg::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            nft_contract,
+            &QueryMsg::OwnerOf {
+                token_id: "test".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, admin.to_string());
+}
+
+#[test]
+fn test_cw721_base_receive_allowlist() {
+    use cw721_base::msg::*;
+    use cw721_receiver_tester::msg::{InnerMsg, QueryMsg as ReceiverQueryMsg};
+
+    let mut app = App::default();
+    let admin = app.api().addr_make("admin");
+    let other_collection = app.api().addr_make("other_collection");
+
+    let Contracts {
+        nft_contract,
+        receiver_contract,
+    } = setup_contracts_with_allowlist(
+        &mut app,
+        admin.clone(),
+        Some(vec![other_collection.to_string()]),
+    );
+
+    let allowed_collections: Option<Vec<Addr>> = app
+        .wrap()
+        .query_wasm_smart(
+            &receiver_contract,
+            &ReceiverQueryMsg::AllowedCollections {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(allowed_collections, Some(vec![other_collection]));
+
+    // collection is not in the allowlist
+    let err = app
+        .execute_contract(
+            admin,
+            nft_contract,
+            &ExecuteMsg::SendNft {
+                contract: receiver_contract.to_string(),
+                token_id: "test".to_string(),
+                msg: to_json_binary(&InnerMsg::Succeed).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("Tokens of collection"));
+}
+
+#[test]
+fn test_cw1155_base_receive() {
+    use cw1155::msg::{
+        BalanceResponse, Cw1155InstantiateMsg, Cw1155MintMsg, OwnerToken, TokenAmount,
+    };
+    use cw1155_base::{Cw1155BaseExecuteMsg, Cw1155BaseQueryMsg};
+    use cw721_receiver_tester::contract::*;
+    use cw721_receiver_tester::msg::*;
+
+    let mut app = App::default();
+    let admin = app.api().addr_make("admin");
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let cw1155_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw1155_base::entry::execute,
+        cw1155_base::entry::instantiate,
+        cw1155_base::entry::query,
+    )));
+    let cw1155_contract = app
+        .instantiate_contract(
+            cw1155_code_id,
+            admin.clone(),
+            &Cw1155InstantiateMsg {
+                name: "tokens".to_string(),
+                symbol: "TOKENS".to_string(),
+                collection_info_extension: None,
+                minter: None,
+                creator: None,
+                default_uri: None,
+            },
+            &[],
+            "tokens".to_string(),
+            None,
+        )
+        .unwrap();
+    let receiver_contract = app
+        .instantiate_contract(
+            code_id,
+            admin.clone(),
+            &InstantiateMsg {
+                allowed_collections: Some(vec![cw1155_contract.to_string()]),
+            },
+            &[],
+            "receiver".to_string(),
+            None,
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            admin.clone(),
+            cw1155_contract.clone(),
+            &Cw1155BaseExecuteMsg::Mint {
+                recipient: admin.to_string(),
+                msg: Cw1155MintMsg {
+                    token_id: token_id.to_string(),
+                    amount: 10u128.into(),
+                    token_uri: None,
+                    extension: None,
+                    config: None,
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // single send is received
+    let response = app
+        .execute_contract(
+            admin.clone(),
+            cw1155_contract.clone(),
+            &Cw1155BaseExecuteMsg::Send {
+                from: None,
+                to: receiver_contract.to_string(),
+                token_id: "1".to_string(),
+                amount: 4u128.into(),
+                msg: Some(to_json_binary(&InnerMsg::Succeed).unwrap()),
+            },
+            &[],
+        )
+        .unwrap();
+    let receive_event = response
+        .events
+        .iter()
+        .find(|e| get_attribute(&e.attributes, "action") == Some("receive_tokens"))
+        .unwrap();
+    assert_eq!(
+        get_attribute(&receive_event.attributes, "tokens"),
+        Some("1:4")
+    );
+
+    // batch is bounced back to the sender in a single message
+    let response = app
+        .execute_contract(
+            admin.clone(),
+            cw1155_contract.clone(),
+            &Cw1155BaseExecuteMsg::SendBatch {
+                from: None,
+                to: receiver_contract.to_string(),
+                batch: vec![
+                    TokenAmount {
+                        token_id: "1".to_string(),
+                        amount: 6u128.into(),
+                    },
+                    TokenAmount {
+                        token_id: "2".to_string(),
+                        amount: 10u128.into(),
+                    },
+                ],
+                msg: Some(to_json_binary(&InnerMsg::Bounce).unwrap()),
+            },
+            &[],
+        )
+        .unwrap();
+    let bounce_event = response
+        .events
+        .iter()
+        .find(|e| get_attribute(&e.attributes, "action") == Some("bounce_tokens"))
+        .unwrap();
+    assert_eq!(
+        get_attribute(&bounce_event.attributes, "recipient"),
+        Some(admin.as_str())
+    );
+    assert_eq!(
+        get_attribute(&bounce_event.attributes, "tokens"),
+        Some("1:6,2:10")
+    );
+
+    for (token_id, admin_balance, receiver_balance) in [("1", 6u128, 4u128), ("2", 10, 0)] {
+        for (owner, expected) in [
+            (&admin, admin_balance),
+            (&receiver_contract, receiver_balance),
+        ] {
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &cw1155_contract,
+                    &Cw1155BaseQueryMsg::BalanceOf(OwnerToken {
+                        owner: owner.to_string(),
+                        token_id: token_id.to_string(),
+                    }),
+                )
+                .unwrap();
+            assert_eq!(balance.balance.u128(), expected);
+        }
+    }
+}
+
 struct Contracts {
     nft_contract: Addr,
     receiver_contract: Addr,
@@ -105,6 +346,14 @@ struct Contracts {
 
 /// Setup the cw721-receiver and cw721-base contracts and mint a test token
 fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
+    setup_contracts_with_allowlist(app, admin, None)
+}
+
+fn setup_contracts_with_allowlist(
+    app: &mut App,
+    admin: Addr,
+    allowed_collections: Option<Vec<String>>,
+) -> Contracts {
     use cw721_receiver_tester::contract::*;
     use cw721_receiver_tester::msg::*;
 
@@ -129,6 +378,7 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
                 minter: Some(admin.to_string()),
                 creator: Some(admin.to_string()),
                 withdraw_address: None,
+                max_supply: None,
             },
             &[],
             "nft".to_string(),
@@ -140,7 +390,9 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
         .instantiate_contract(
             code_id,
             admin.clone(),
-            &InstantiateMsg {},
+            &InstantiateMsg {
+                allowed_collections,
+            },
             &[],
             "receiver".to_string(),
             None,
@@ -152,7 +404,7 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
         admin.clone(),
         nft_contract.clone(),
         &base_msg::ExecuteMsg::Mint {
-            token_id: "test".to_string(),
+            token_id: Some("test".to_string()),
             owner: admin.to_string(),
             token_uri: Some("https://example.com".to_string()),
             extension: None,
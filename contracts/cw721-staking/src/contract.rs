@@ -0,0 +1,313 @@
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, PendingRewardsResponse, QueryMsg, StakesResponse};
+use crate::state::{stakes, Config, Stake, CONFIG};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdResult, Timestamp, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Denom};
+use cw721::helpers::DefaultCw721Helper;
+use cw721::msg::Cw721ExecuteMsg;
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::traits::Cw721Calls;
+use cw_storage_plus::Bound;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-staking";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let reward_denom = match msg.reward_denom {
+        Denom::Native(denom) => Denom::Native(denom),
+        Denom::Cw20(address) => Denom::Cw20(deps.api.addr_validate(address.as_str())?),
+    };
+    let config = Config {
+        collection: deps.api.addr_validate(&msg.collection)?,
+        reward_denom,
+        reward_rate: msg.reward_rate,
+        unbonding_period: msg.unbonding_period,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("collection", config.collection)
+        .add_attribute("reward_rate", config.reward_rate)
+        .add_attribute("unbonding_period", config.unbonding_period.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender, token_id, ..
+        }) => execute_stake(deps, env, info, sender, token_id),
+        ExecuteMsg::Claim { start_after, limit } => {
+            execute_claim(deps, env, info, start_after, limit)
+        }
+        ExecuteMsg::Unstake { token_id } => execute_unstake(deps, env, info, token_id),
+        ExecuteMsg::Withdraw { token_id } => execute_withdraw(deps, env, info, token_id),
+    }
+}
+
+pub fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // the sender is the cw721 contract, the NFT is already owned by this contract
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.collection {
+        return Err(ContractError::WrongCollection {});
+    }
+    let stake = Stake {
+        token_id,
+        owner: deps.api.addr_validate(&sender)?,
+        staked_at: env.block.time,
+        last_claimed: env.block.time,
+        unbonding_start: None,
+    };
+    stakes().save(deps.storage, stake.token_id.clone(), &stake)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stake")
+        .add_attribute("token_id", stake.token_id)
+        .add_attribute("owner", stake.owner))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let owned = stakes()
+        .idx
+        .owner
+        .prefix(info.sender.clone())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, stake)| stake))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut rewards = Uint128::zero();
+    for mut stake in owned {
+        // unbonding tokens were claimed on unstake and accrue no further rewards
+        if stake.unbonding_start.is_some() {
+            continue;
+        }
+        rewards = rewards.checked_add(pending_rewards(&config, &stake, env.block.time)?)?;
+        stake.last_claimed = env.block.time;
+        stakes().save(deps.storage, stake.token_id.clone(), &stake)?;
+    }
+    if rewards.is_zero() {
+        return Err(ContractError::NoRewards {});
+    }
+
+    Ok(Response::new()
+        .add_message(reward_msg(
+            &config.reward_denom,
+            info.sender.clone(),
+            rewards,
+        )?)
+        .add_attribute("action", "claim")
+        .add_attribute("owner", info.sender)
+        .add_attribute("rewards", rewards))
+}
+
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut stake = stakes().load(deps.storage, token_id.clone())?;
+    if stake.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if stake.unbonding_start.is_some() {
+        return Err(ContractError::AlreadyUnbonding {});
+    }
+
+    let rewards = pending_rewards(&config, &stake, env.block.time)?;
+    let mut res = Response::new()
+        .add_attribute("action", "unstake")
+        .add_attribute("token_id", token_id.clone())
+        .add_attribute("owner", info.sender.clone())
+        .add_attribute("rewards", rewards);
+    if !rewards.is_zero() {
+        res = res.add_message(reward_msg(
+            &config.reward_denom,
+            info.sender.clone(),
+            rewards,
+        )?);
+    }
+
+    if config.unbonding_period == 0 {
+        stakes().remove(deps.storage, token_id.clone())?;
+        return Ok(res.add_message(transfer_nft_msg(&config, &info.sender, token_id)?));
+    }
+    stake.last_claimed = env.block.time;
+    stake.unbonding_start = Some(env.block.time);
+    stakes().save(deps.storage, token_id, &stake)?;
+
+    Ok(res.add_attribute(
+        "unbonding_end",
+        env.block
+            .time
+            .plus_seconds(config.unbonding_period)
+            .to_string(),
+    ))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let stake = stakes().load(deps.storage, token_id.clone())?;
+    if stake.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let Some(unbonding_start) = stake.unbonding_start else {
+        return Err(ContractError::NotUnbonding {});
+    };
+    let end = unbonding_start.plus_seconds(config.unbonding_period);
+    if env.block.time < end {
+        return Err(ContractError::UnbondingNotEnded { end });
+    }
+    stakes().remove(deps.storage, token_id.clone())?;
+
+    Ok(Response::new()
+        .add_message(transfer_nft_msg(&config, &info.sender, token_id.clone())?)
+        .add_attribute("action", "withdraw")
+        .add_attribute("token_id", token_id)
+        .add_attribute("owner", info.sender))
+}
+
+/// Rewards accrued since the last claim, until unbonding starts.
+fn pending_rewards(config: &Config, stake: &Stake, now: Timestamp) -> StdResult<Uint128> {
+    let end = stake.unbonding_start.unwrap_or(now);
+    let seconds = end.seconds().saturating_sub(stake.last_claimed.seconds());
+    Ok(config.reward_rate.checked_mul(Uint128::from(seconds))?)
+}
+
+fn reward_msg(denom: &Denom, recipient: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.into_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+        Denom::Cw20(cw20_address) => WasmMsg::Execute {
+            contract_addr: cw20_address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+fn transfer_nft_msg(config: &Config, recipient: &Addr, token_id: String) -> StdResult<CosmosMsg> {
+    DefaultCw721Helper::new(config.collection.clone()).call(Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Stake { token_id } => to_json_binary(&query_stake(deps, token_id)?),
+        QueryMsg::Stakes {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_stakes(deps, owner, start_after, limit)?),
+        QueryMsg::PendingRewards { owner } => {
+            to_json_binary(&query_pending_rewards(deps, env, owner)?)
+        }
+    }
+}
+
+pub fn query_stake(deps: Deps, token_id: String) -> StdResult<Option<Stake>> {
+    stakes().may_load(deps.storage, token_id)
+}
+
+pub fn query_stakes(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<StakesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let owner = deps.api.addr_validate(&owner)?;
+    let stakes = stakes()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, stake)| stake))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(StakesResponse { stakes })
+}
+
+pub fn query_pending_rewards(
+    deps: Deps,
+    env: Env,
+    owner: String,
+) -> StdResult<PendingRewardsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let owner = deps.api.addr_validate(&owner)?;
+    let mut rewards = Uint128::zero();
+    for item in stakes()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, stake) = item?;
+        rewards = rewards.checked_add(pending_rewards(&config, &stake, env.block.time)?)?;
+    }
+
+    Ok(PendingRewardsResponse { rewards })
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::{OverflowError, StdError, Timestamp};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Only NFTs of the configured collection can be staked")]
+    WrongCollection {},
+
+    #[error("No rewards to claim")]
+    NoRewards {},
+
+    #[error("Token is already unbonding")]
+    AlreadyUnbonding {},
+
+    #[error("Token is not unbonding")]
+    NotUnbonding {},
+
+    #[error("Unbonding not ended yet. Unbonding ends at {end}")]
+    UnbondingNotEnded { end: Timestamp },
+}
@@ -0,0 +1,63 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Denom;
+use cw721::receiver::Cw721ReceiveMsg;
+
+use crate::state::{Config, Stake};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub collection: String,
+    pub reward_denom: Denom,
+    /// Reward per staked token per second.
+    pub reward_rate: Uint128,
+    /// Seconds between unstaking and withdrawing an NFT. Zero means NFTs are returned on unstake.
+    pub unbonding_period: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Stake an NFT of the configured collection, via cw721 `SendNft`.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Claim rewards of tokens staked by the sender, ordered by token id. Paginated like `Stakes`,
+    /// so owners of many tokens claim in batches.
+    Claim {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only the owner can unstake a token, pending rewards of the token are paid out. Without an
+    /// unbonding period the NFT is returned, otherwise it must be withdrawn once unbonded.
+    Unstake { token_id: String },
+    /// Only the owner can withdraw a token once its unbonding period ended.
+    Withdraw { token_id: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    /// Returns the stake of the given token, if staked.
+    #[returns(Option<Stake>)]
+    Stake { token_id: String },
+    /// Returns tokens staked by an owner, ordered by token id.
+    #[returns(StakesResponse)]
+    Stakes {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns rewards claimable by an owner.
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { owner: String },
+}
+
+#[cw_serde]
+pub struct StakesResponse {
+    pub stakes: Vec<Stake>,
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub rewards: Uint128,
+}
@@ -0,0 +1,50 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw20::Denom;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+
+#[cw_serde]
+pub struct Config {
+    /// Only NFTs of this collection can be staked.
+    pub collection: Addr,
+    /// Native denom or CW20 token rewards are paid in.
+    pub reward_denom: Denom,
+    /// Reward per staked token per second.
+    pub reward_rate: Uint128,
+    /// Seconds between unstaking and withdrawing an NFT. Zero means NFTs are returned on unstake.
+    pub unbonding_period: u64,
+}
+
+/// A staked NFT. The contract holds custody of the NFT until it is withdrawn.
+#[cw_serde]
+pub struct Stake {
+    pub token_id: String,
+    pub owner: Addr,
+    /// Lock start of the token.
+    pub staked_at: Timestamp,
+    /// Rewards are accrued since the last claim.
+    pub last_claimed: Timestamp,
+    /// Set on unstake, rewards stop accruing once unbonding.
+    pub unbonding_start: Option<Timestamp>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+pub struct StakeIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, Stake, String>,
+}
+
+impl<'a> IndexList<Stake> for StakeIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Stake>> + '_> {
+        let v: Vec<&dyn Index<Stake>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Stakes are keyed by token id.
+pub fn stakes<'a>() -> IndexedMap<'a, String, Stake, StakeIndexes<'a>> {
+    let indexes = StakeIndexes {
+        owner: MultiIndex::new(|_, stake| stake.owner.clone(), "stakes", "stakes__owner"),
+    };
+    IndexedMap::new("stakes", indexes)
+}
@@ -0,0 +1,386 @@
+use crate::{
+    contract::{execute, instantiate, query},
+    msg::{ExecuteMsg, InstantiateMsg, PendingRewardsResponse, QueryMsg, StakesResponse},
+    state::Stake,
+    ContractError,
+};
+use cosmwasm_std::{Addr, Binary, Empty, Uint128};
+use cw20::{Cw20ExecuteMsg, Denom};
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_nfts_multi_test::{
+    fund, instantiate_cw20, instantiate_cw721, query_cw20_balance, query_owner, DENOM,
+};
+
+const REWARD_RATE: u128 = 10;
+
+struct Contracts {
+    cw721: Addr,
+    cw20: Addr,
+    staking: Addr,
+}
+
+struct Users {
+    creator: Addr,
+    holder: Addr,
+    other: Addr,
+}
+
+fn staking_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn users(app: &App) -> Users {
+    Users {
+        creator: app.api().addr_make("creator"),
+        holder: app.api().addr_make("holder"),
+        other: app.api().addr_make("other"),
+    }
+}
+
+/// Creates a collection with tokens "1" and "2" owned by the holder and token "3" owned by the
+/// other user, a cw20 token, and the staking contract funded with native and cw20 rewards.
+fn setup(app: &mut App, cw20_rewards: bool, unbonding_period: u64) -> Contracts {
+    let Users {
+        creator,
+        holder,
+        other,
+    } = users(app);
+
+    let cw721 = instantiate_cw721(
+        app,
+        &creator,
+        None,
+        &[("1", &holder), ("2", &holder), ("3", &other)],
+    );
+    let cw20 = instantiate_cw20(app, &creator, &[(&creator, 1_000_000)]);
+
+    let reward_denom = if cw20_rewards {
+        Denom::Cw20(cw20.clone())
+    } else {
+        Denom::Native(DENOM.to_string())
+    };
+    let staking_code_id = app.store_code(staking_contract());
+    let staking = app
+        .instantiate_contract(
+            staking_code_id,
+            creator.clone(),
+            &InstantiateMsg {
+                collection: cw721.to_string(),
+                reward_denom,
+                reward_rate: Uint128::new(REWARD_RATE),
+                unbonding_period,
+            },
+            &[],
+            "staking",
+            None,
+        )
+        .unwrap();
+
+    fund(app, &staking, 1_000_000);
+    app.execute_contract(
+        creator,
+        cw20.clone(),
+        &Cw20ExecuteMsg::Transfer {
+            recipient: staking.to_string(),
+            amount: Uint128::new(1_000_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    Contracts {
+        cw721,
+        cw20,
+        staking,
+    }
+}
+
+fn stake(app: &mut App, cw721: &Addr, staking: &Addr, owner: &Addr, token_id: &str) {
+    app.execute_contract(
+        owner.clone(),
+        cw721.clone(),
+        &Cw721ExecuteMsg::SendNft {
+            contract: staking.to_string(),
+            token_id: token_id.to_string(),
+            msg: Binary::default(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn query_pending_rewards(app: &App, staking: &Addr, owner: &Addr) -> Uint128 {
+    let res: PendingRewardsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking,
+            &QueryMsg::PendingRewards {
+                owner: owner.to_string(),
+            },
+        )
+        .unwrap();
+    res.rewards
+}
+
+fn query_stakes(app: &App, staking: &Addr, owner: &Addr) -> Vec<Stake> {
+    let res: StakesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking,
+            &QueryMsg::Stakes {
+                owner: owner.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    res.stakes
+}
+
+#[test]
+fn test_stake_claim_and_unstake() {
+    let mut app = App::default();
+    let Contracts { cw721, staking, .. } = setup(&mut app, false, 0);
+    let Users { holder, other, .. } = users(&app);
+
+    stake(&mut app, &cw721, &staking, &holder, "1");
+    stake(&mut app, &cw721, &staking, &holder, "2");
+    let staked_at = app.block_info().time;
+    assert_eq!(query_owner(&app, &cw721, "1"), staking);
+    assert_eq!(
+        query_stakes(&app, &staking, &holder),
+        vec![
+            Stake {
+                token_id: "1".to_string(),
+                owner: holder.clone(),
+                staked_at,
+                last_claimed: staked_at,
+                unbonding_start: None,
+            },
+            Stake {
+                token_id: "2".to_string(),
+                owner: holder.clone(),
+                staked_at,
+                last_claimed: staked_at,
+                unbonding_start: None,
+            },
+        ]
+    );
+
+    // nothing accrued yet
+    let claim_msg = ExecuteMsg::Claim {
+        start_after: None,
+        limit: None,
+    };
+    let err = app
+        .execute_contract(holder.clone(), staking.clone(), &claim_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoRewards {}
+    );
+
+    // rewards accrue per token per second
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    assert_eq!(
+        query_pending_rewards(&app, &staking, &holder),
+        Uint128::new(2 * 100 * REWARD_RATE)
+    );
+    app.execute_contract(holder.clone(), staking.clone(), &claim_msg, &[])
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&holder, DENOM).unwrap().amount,
+        Uint128::new(2000)
+    );
+    assert_eq!(
+        query_pending_rewards(&app, &staking, &holder),
+        Uint128::zero()
+    );
+
+    // only the owner can unstake
+    let unstake_msg = ExecuteMsg::Unstake {
+        token_id: "1".to_string(),
+    };
+    let err = app
+        .execute_contract(other, staking.clone(), &unstake_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // without unbonding period, the NFT is returned and pending rewards are paid
+    app.update_block(|block| block.time = block.time.plus_seconds(50));
+    app.execute_contract(holder.clone(), staking.clone(), &unstake_msg, &[])
+        .unwrap();
+    assert_eq!(query_owner(&app, &cw721, "1"), holder);
+    assert_eq!(
+        app.wrap().query_balance(&holder, DENOM).unwrap().amount,
+        Uint128::new(2500)
+    );
+    assert_eq!(query_stakes(&app, &staking, &holder).len(), 1);
+    assert_eq!(
+        query_pending_rewards(&app, &staking, &holder),
+        Uint128::new(50 * REWARD_RATE)
+    );
+}
+
+#[test]
+fn test_claim_paginated() {
+    let mut app = App::default();
+    let Contracts { cw721, staking, .. } = setup(&mut app, false, 0);
+    let Users { holder, .. } = users(&app);
+
+    stake(&mut app, &cw721, &staking, &holder, "1");
+    stake(&mut app, &cw721, &staking, &holder, "2");
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    // only the first token is claimed
+    app.execute_contract(
+        holder.clone(),
+        staking.clone(),
+        &ExecuteMsg::Claim {
+            start_after: None,
+            limit: Some(1),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&holder, DENOM).unwrap().amount,
+        Uint128::new(100 * REWARD_RATE)
+    );
+    assert_eq!(
+        query_pending_rewards(&app, &staking, &holder),
+        Uint128::new(100 * REWARD_RATE)
+    );
+
+    // next batch starts after the first token
+    app.execute_contract(
+        holder.clone(),
+        staking.clone(),
+        &ExecuteMsg::Claim {
+            start_after: Some("1".to_string()),
+            limit: Some(1),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&holder, DENOM).unwrap().amount,
+        Uint128::new(2 * 100 * REWARD_RATE)
+    );
+    assert_eq!(
+        query_pending_rewards(&app, &staking, &holder),
+        Uint128::zero()
+    );
+
+    // no tokens left after the last one
+    let err = app
+        .execute_contract(
+            holder,
+            staking,
+            &ExecuteMsg::Claim {
+                start_after: Some("2".to_string()),
+                limit: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoRewards {}
+    );
+}
+
+#[test]
+fn test_unbonding_with_cw20_rewards() {
+    let mut app = App::default();
+    let Contracts {
+        cw721,
+        cw20,
+        staking,
+    } = setup(&mut app, true, 3600);
+    let Users { holder, .. } = users(&app);
+
+    stake(&mut app, &cw721, &staking, &holder, "1");
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let withdraw_msg = ExecuteMsg::Withdraw {
+        token_id: "1".to_string(),
+    };
+    let err = app
+        .execute_contract(holder.clone(), staking.clone(), &withdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotUnbonding {}
+    );
+
+    // unstaking pays pending rewards and starts unbonding
+    app.execute_contract(
+        holder.clone(),
+        staking.clone(),
+        &ExecuteMsg::Unstake {
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let unbonding_end = app.block_info().time.plus_seconds(3600);
+    assert_eq!(query_owner(&app, &cw721, "1"), staking);
+    assert_eq!(
+        query_cw20_balance(&app, &cw20, &holder),
+        Uint128::new(100 * REWARD_RATE)
+    );
+
+    // no rewards accrue while unbonding
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    assert_eq!(
+        query_pending_rewards(&app, &staking, &holder),
+        Uint128::zero()
+    );
+    let err = app
+        .execute_contract(holder.clone(), staking.clone(), &withdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnbondingNotEnded { end: unbonding_end }
+    );
+
+    app.update_block(|block| block.time = unbonding_end);
+    app.execute_contract(holder.clone(), staking.clone(), &withdraw_msg, &[])
+        .unwrap();
+    assert_eq!(query_owner(&app, &cw721, "1"), holder);
+    assert_eq!(query_stakes(&app, &staking, &holder), vec![]);
+}
+
+#[test]
+fn test_wrong_collection() {
+    let mut app = App::default();
+    let Contracts { staking, .. } = setup(&mut app, false, 0);
+    let Users { other, .. } = users(&app);
+
+    // another collection, instantiated with the same code
+    let Contracts {
+        cw721: other_cw721, ..
+    } = setup(&mut app, false, 0);
+    let err = app
+        .execute_contract(
+            other.clone(),
+            other_cw721.clone(),
+            &Cw721ExecuteMsg::SendNft {
+                contract: staking.to_string(),
+                token_id: "3".to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::WrongCollection {}
+    );
+    assert_eq!(query_owner(&app, &other_cw721, "3"), other);
+    assert_eq!(query_stakes(&app, &staking, &other), vec![]);
+}
@@ -0,0 +1,163 @@
+//! Shared cw-multi-test fixtures for testing contracts interacting with cw721, cw1155 and cw20
+//! contracts.
+
+use cosmwasm_std::{coins, Addr, Empty, Uint128};
+use cw1155::msg::{BalanceResponse as Cw1155BalanceResponse, OwnerToken};
+use cw1155_base::Cw1155BaseQueryMsg;
+use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg};
+use cw721::{msg::OwnerOfResponse, DefaultOptionalCollectionExtensionMsg};
+use cw721_base::msg::{
+    ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg, QueryMsg as Cw721QueryMsg,
+};
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
+
+/// Native denom used for payments.
+pub const DENOM: &str = "uatom";
+
+pub fn cw721_base_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    ))
+}
+
+pub fn cw1155_base_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw1155_base::entry::execute,
+        cw1155_base::entry::instantiate,
+        cw1155_base::entry::query,
+    ))
+}
+
+pub fn cw20_base_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ))
+}
+
+/// Instantiates a cw721-base collection with `creator` as minter and creator, and mints the given
+/// (token id, owner) NFTs.
+pub fn instantiate_cw721(
+    app: &mut App,
+    creator: &Addr,
+    collection_info_extension: DefaultOptionalCollectionExtensionMsg,
+    nfts: &[(&str, &Addr)],
+) -> Addr {
+    let code_id = app.store_code(cw721_base_contract());
+    let cw721 = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &Cw721InstantiateMsg {
+                name: "collection".to_string(),
+                symbol: "COL".to_string(),
+                collection_info_extension,
+                minter: None,
+                creator: None,
+                withdraw_address: None,
+                max_supply: None,
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+    for (token_id, owner) in nfts {
+        app.execute_contract(
+            creator.clone(),
+            cw721.clone(),
+            &Cw721ExecuteMsg::Mint {
+                token_id: Some(token_id.to_string()),
+                owner: owner.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    cw721
+}
+
+/// Instantiates a cw20-base token with the given (address, amount) balances.
+pub fn instantiate_cw20(app: &mut App, creator: &Addr, balances: &[(&Addr, u128)]) -> Addr {
+    let code_id = app.store_code(cw20_base_contract());
+    app.instantiate_contract(
+        code_id,
+        creator.clone(),
+        &cw20_base::msg::InstantiateMsg {
+            name: "token".to_string(),
+            symbol: "TOKEN".to_string(),
+            decimals: 6,
+            initial_balances: balances
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        "cw20",
+        None,
+    )
+    .unwrap()
+}
+
+/// Mints `amount` of the native `DENOM` to the address.
+pub fn fund(app: &mut App, address: &Addr, amount: u128) {
+    app.sudo(
+        BankSudo::Mint {
+            to_address: address.to_string(),
+            amount: coins(amount, DENOM),
+        }
+        .into(),
+    )
+    .unwrap();
+}
+
+pub fn query_owner(app: &App, cw721: &Addr, token_id: &str) -> Addr {
+    let res: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw721,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    Addr::unchecked(res.owner)
+}
+
+pub fn query_cw20_balance(app: &App, cw20: &Addr, address: &Addr) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw20,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+pub fn query_cw1155_balance(app: &App, cw1155: &Addr, owner: &Addr, token_id: &str) -> Uint128 {
+    let res: Cw1155BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw1155,
+            &Cw1155BaseQueryMsg::BalanceOf(OwnerToken {
+                owner: owner.to_string(),
+                token_id: token_id.to_string(),
+            }),
+        )
+        .unwrap();
+    res.balance
+}
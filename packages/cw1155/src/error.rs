@@ -1,5 +1,6 @@
 use cosmwasm_std::{OverflowError, StdError, Uint128};
 use cw2::VersionError;
+use cw721::error::Cw721ContractError;
 use cw_ownable::OwnershipError;
 use thiserror::Error;
 
@@ -17,6 +18,9 @@ pub enum Cw1155ContractError {
     #[error("OwnershipError: {0}")]
     Ownership(#[from] OwnershipError),
 
+    #[error("Cw721ContractError: {0}")]
+    Cw721(#[from] Cw721ContractError),
+
     #[error("Unauthorized: {reason}")]
     Unauthorized { reason: String },
 
@@ -32,6 +36,24 @@ pub enum Cw1155ContractError {
         requested: Uint128,
     },
 
+    #[error("Tokens of collection {collection} are not accepted")]
+    CollectionNotAllowed { collection: String },
+
+    #[error("Token {token_id} cannot be minted after its first mint.")]
+    MintingDisabled { token_id: String },
+
+    #[error(
+        "Max supply of token {token_id} exceeded. Remaining: {remaining}, Requested: {requested}."
+    )]
+    MaxSupplyExceeded {
+        token_id: String,
+        remaining: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("Collection name and symbol must be provided to migrate a contract without collection info.")]
+    MissingCollectionInfo {},
+
     #[error("Must provide tokens to update.")]
     EmptyUpdateRequest {},
 
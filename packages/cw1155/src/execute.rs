@@ -1,10 +1,17 @@
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    Addr, Attribute, BankMsg, Binary, CustomMsg, DepsMut, Empty, Env, MessageInfo, Response,
+    Addr, Attribute, BankMsg, Binary, CustomMsg, DepsMut, Empty, Env, MessageInfo, Order, Response,
     StdError, StdResult, Storage, SubMsg, Uint128,
 };
 use cw2::set_contract_version;
-use cw721::execute::migrate_version;
-use cw_ownable::initialize_owner;
+use cw721::execute::{
+    migrate_creator, migrate_legacy_minter_and_creator, migrate_minter, migrate_version,
+    update_collection_info,
+};
+use cw721::msg::{CollectionInfoMsg, Cw721InstantiateMsg, Cw721MigrateMsg};
+use cw721::state::{CollectionInfo, CREATOR, MINTER};
+use cw721::{DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg};
+use cw_storage_plus::{Item, Map};
 use cw_utils::Expiration;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -14,9 +21,11 @@ use crate::event::{
     ApproveAllEvent, ApproveEvent, BurnEvent, MintEvent, RevokeAllEvent, RevokeEvent,
     TransferEvent, UpdateDefaultUriEvent, UpdateMetadataBatchEvent, UpdateMetadataEvent,
 };
-use crate::msg::{Balance, CollectionInfo, Cw1155MintMsg, TokenAmount, TokenApproval, TokenUpdate};
+use crate::msg::{
+    Balance, Cw1155MigrateMsg, Cw1155MintMsg, TokenAmount, TokenApproval, TokenUpdate,
+};
 use crate::receiver::Cw1155BatchReceiveMsg;
-use crate::state::TokenInfo;
+use crate::state::{TokenConfig, TokenInfo};
 use crate::{
     error::Cw1155ContractError,
     msg::{Cw1155ExecuteMsg, Cw1155InstantiateMsg},
@@ -41,8 +50,8 @@ pub trait Cw1155Execute<
 {
     fn instantiate(
         &self,
-        deps: DepsMut,
-        _env: Env,
+        mut deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         msg: Cw1155InstantiateMsg,
         contract_name: &str,
@@ -55,20 +64,26 @@ pub trait Cw1155Execute<
             TMetadataExtensionMsg,
             TQueryExtensionMsg,
         >::default();
-        let collection_info = CollectionInfo {
-            name: msg.name,
-            symbol: msg.symbol,
-        };
-        config
-            .collection_info
-            .save(deps.storage, &collection_info)?;
 
-        // store minter
-        let minter = match msg.minter {
-            Some(owner) => deps.api.addr_validate(&owner)?,
-            None => info.sender,
-        };
-        initialize_owner(deps.storage, deps.api, Some(minter.as_ref()))?;
+        // store collection info, minter and creator the same way as cw721
+        let response = cw721::execute::instantiate::<
+            DefaultOptionalCollectionExtension,
+            DefaultOptionalCollectionExtensionMsg,
+            TCustomResponseMessage,
+        >(
+            deps.branch(),
+            &env,
+            &info,
+            Cw721InstantiateMsg {
+                name: msg.name,
+                symbol: msg.symbol,
+                collection_info_extension: msg.collection_info_extension,
+                minter: msg.minter,
+                creator: msg.creator,
+                withdraw_address: None,
+                max_supply: None,
+            },
+        )?;
 
         // store total supply
         config.supply.save(deps.storage, &Uint128::zero())?;
@@ -78,7 +93,7 @@ pub trait Cw1155Execute<
             .default_base_uri
             .save(deps.storage, &msg.default_uri)?;
 
-        Ok(Response::default().add_attribute("minter", minter))
+        Ok(response)
     }
 
     fn execute(
@@ -131,7 +146,17 @@ pub trait Cw1155Execute<
                 token_id,
                 amount,
             } => self.revoke_token(env, spender, token_id, amount),
-            Cw1155ExecuteMsg::UpdateOwnership(action) => Self::update_ownership(env, action),
+            #[allow(deprecated)]
+            Cw1155ExecuteMsg::UpdateOwnership(action) => Self::update_minter_ownership(env, action),
+            Cw1155ExecuteMsg::UpdateMinterOwnership(action) => {
+                Self::update_minter_ownership(env, action)
+            }
+            Cw1155ExecuteMsg::UpdateCreatorOwnership(action) => {
+                Self::update_creator_ownership(env, action)
+            }
+            Cw1155ExecuteMsg::UpdateCollectionInfo { collection_info } => {
+                self.update_collection_info(env, collection_info)
+            }
             Cw1155ExecuteMsg::UpdateMetadata(update) => self.update_metadata(env, update),
             Cw1155ExecuteMsg::UpdateMetadataBatch { updates } => {
                 self.update_metadata_batch(env, updates)
@@ -145,14 +170,30 @@ pub trait Cw1155Execute<
     fn migrate(
         &self,
         deps: DepsMut,
-        _env: Env,
-        _msg: Empty,
+        env: Env,
+        msg: Cw1155MigrateMsg,
         contract_name: &str,
         contract_version: &str,
     ) -> Result<Response, Cw1155ContractError> {
+        let Cw1155MigrateMsg::WithUpdate {
+            minter, creator, ..
+        } = &msg;
+        let cw721_msg = Cw721MigrateMsg::WithUpdate {
+            minter: minter.clone(),
+            creator: creator.clone(),
+        };
         let response = Response::<Empty>::default();
-        // migrate
+        // first migrate legacy data ...
+        let response =
+            migrate_legacy_balances_and_tokens::<TMetadataExtension>(deps.storage, response)?;
+        let response =
+            migrate_legacy_minter_and_creator(deps.storage, deps.api, &env, &cw721_msg, response)?;
+        let response = migrate_legacy_collection_info(deps.storage, &env, &msg, response)?;
+        // ... then migrate
         let response = migrate_version(deps.storage, contract_name, contract_version, response)?;
+        // ... and update creator and minter AFTER legacy migration
+        let response = migrate_creator(deps.storage, deps.api, &env, &cw721_msg, response)?;
+        let response = migrate_minter(deps.storage, deps.api, &env, &cw721_msg, response)?;
         Ok(response)
     }
 
@@ -174,10 +215,23 @@ pub trait Cw1155Execute<
             TQueryExtensionMsg,
         >::default();
 
-        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        MINTER.assert_owner(deps.storage, &info.sender)?;
 
         let to = deps.api.addr_validate(&recipient)?;
 
+        // store token info if not exist (if it is the first mint), its config is enforced on mint
+        if !config.tokens.has(deps.storage, &msg.token_id) {
+            let token_info = TokenInfo {
+                token_uri: msg.token_uri,
+                extension: msg.extension,
+                config: msg.config.unwrap_or_default(),
+                minted: Uint128::zero(),
+            };
+            config
+                .tokens
+                .save(deps.storage, &msg.token_id, &token_info)?;
+        }
+
         let mut rsp = Response::default();
 
         let event = self.update_balances(
@@ -193,17 +247,6 @@ pub trait Cw1155Execute<
         )?;
         rsp = rsp.add_attributes(event);
 
-        // store token info if not exist (if it is the first mint)
-        if !config.tokens.has(deps.storage, &msg.token_id) {
-            let token_info = TokenInfo {
-                token_uri: msg.token_uri,
-                extension: msg.extension,
-            };
-            config
-                .tokens
-                .save(deps.storage, &msg.token_id, &token_info)?;
-        }
-
         Ok(rsp)
     }
 
@@ -225,7 +268,7 @@ pub trait Cw1155Execute<
             TQueryExtensionMsg,
         >::default();
 
-        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        MINTER.assert_owner(deps.storage, &info.sender)?;
 
         let to = deps.api.addr_validate(&recipient)?;
 
@@ -237,6 +280,8 @@ pub trait Cw1155Execute<
                     let token_info = TokenInfo {
                         token_uri: msg.token_uri.clone(),
                         extension: msg.extension.clone(),
+                        config: msg.config.clone().unwrap_or_default(),
+                        minted: Uint128::zero(),
                     };
                     config
                         .tokens
@@ -730,6 +775,25 @@ pub trait Cw1155Execute<
                     return Err(Cw1155ContractError::InvalidZeroAmount {});
                 }
                 config.increment_tokens(deps.storage, token_id, amount)?;
+                // enforce supply rules of the token
+                if let Some(mut token_info) = config.tokens.may_load(deps.storage, token_id)? {
+                    if !token_info.config.mintable && !token_info.minted.is_zero() {
+                        return Err(Cw1155ContractError::MintingDisabled {
+                            token_id: token_id.to_string(),
+                        });
+                    }
+                    if let Some(remaining) = token_info.remaining_supply() {
+                        if *amount > remaining {
+                            return Err(Cw1155ContractError::MaxSupplyExceeded {
+                                token_id: token_id.to_string(),
+                                remaining,
+                                requested: *amount,
+                            });
+                        }
+                    }
+                    token_info.minted = token_info.minted.checked_add(*amount)?;
+                    config.tokens.save(deps.storage, token_id, &token_info)?;
+                }
             }
             MintEvent::new(info, to, tokens).into_iter()
         } else {
@@ -845,14 +909,43 @@ pub trait Cw1155Execute<
         }
     }
 
-    fn update_ownership(
+    fn update_minter_ownership(
+        env: ExecuteEnv,
+        action: cw_ownable::Action,
+    ) -> Result<Response<TCustomResponseMessage>, Cw1155ContractError> {
+        let ExecuteEnv { deps, info, env } = env;
+        let ownership =
+            MINTER.update_ownership(deps.api, deps.storage, &env.block, &info.sender, action)?;
+        Ok(Response::new()
+            .add_attribute("update_minter_ownership", info.sender)
+            .add_attributes(ownership.into_attributes()))
+    }
+
+    fn update_creator_ownership(
         env: ExecuteEnv,
         action: cw_ownable::Action,
     ) -> Result<Response<TCustomResponseMessage>, Cw1155ContractError> {
         let ExecuteEnv { deps, info, env } = env;
         let ownership =
-            cw_ownable::update_ownership(deps.api, deps.storage, &env.block, &info.sender, action)?;
-        Ok(Response::new().add_attributes(ownership.into_attributes()))
+            CREATOR.update_ownership(deps.api, deps.storage, &env.block, &info.sender, action)?;
+        Ok(Response::new()
+            .add_attribute("update_creator_ownership", info.sender)
+            .add_attributes(ownership.into_attributes()))
+    }
+
+    /// Allows creator to update the collection info and extension, same as in cw721.
+    fn update_collection_info(
+        &self,
+        env: ExecuteEnv,
+        collection_info: CollectionInfoMsg<DefaultOptionalCollectionExtensionMsg>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw1155ContractError> {
+        let ExecuteEnv { deps, info, env } = env;
+        CREATOR.assert_owner(deps.storage, &info.sender)?;
+        Ok(update_collection_info::<
+            DefaultOptionalCollectionExtension,
+            DefaultOptionalCollectionExtensionMsg,
+            TCustomResponseMessage,
+        >(deps, Some(&info), &env, collection_info)?)
     }
 
     /// Allows creator to update onchain metadata and token uri.
@@ -863,7 +956,7 @@ pub trait Cw1155Execute<
         update: TokenUpdate<TMetadataExtension>,
     ) -> Result<Response<TCustomResponseMessage>, Cw1155ContractError> {
         let ExecuteEnv { deps, info, .. } = env;
-        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        CREATOR.assert_owner(deps.storage, &info.sender)?;
 
         let TokenUpdate {
             token_id,
@@ -902,7 +995,7 @@ pub trait Cw1155Execute<
         updates: Vec<TokenUpdate<TMetadataExtension>>,
     ) -> Result<Response<TCustomResponseMessage>, Cw1155ContractError> {
         let ExecuteEnv { deps, info, .. } = env;
-        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        CREATOR.assert_owner(deps.storage, &info.sender)?;
 
         if updates.is_empty() {
             return Err(Cw1155ContractError::EmptyUpdateRequest {});
@@ -951,7 +1044,7 @@ pub trait Cw1155Execute<
         uri: Option<String>,
     ) -> Result<Response<TCustomResponseMessage>, Cw1155ContractError> {
         let ExecuteEnv { deps, info, .. } = env;
-        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        CREATOR.assert_owner(deps.storage, &info.sender)?;
 
         let config = Cw1155Config::<
             TMetadataExtension,
@@ -965,6 +1058,116 @@ pub trait Cw1155Execute<
     }
 }
 
+/// Migrates only in case supply is not present, which is never stored by cw-plus `cw1155-base`
+/// (v0.16 and below). Its balances and tokens are stored under the same keys, but in other shapes:
+/// - balances: `(owner, token id) -> amount`, without the `balances__token_id` index
+/// - tokens: `token id -> token uri`, where an empty uri means no uri is set
+///
+/// Total supply and token counts are rebuilt from balances. Burned amounts are unknown, so the
+/// current supply of a token is used as its minted amount. Approvals are stored the same way.
+///
+/// All balances are migrated in a single call. Each balance costs a few storage reads and writes,
+/// about 25k gas with default Cosmos SDK gas costs, so migrating up to 4,000 balances (~100M gas) is
+/// supported. Contracts with more balances may exceed the gas limit and can't be migrated this way.
+pub fn migrate_legacy_balances_and_tokens<TMetadataExtension>(
+    storage: &mut dyn Storage,
+    response: Response,
+) -> Result<Response, Cw1155ContractError>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+{
+    let config = Cw1155Config::<TMetadataExtension, Empty, Empty, Empty>::default();
+    if config.supply.may_load(storage)?.is_some() {
+        return Ok(response);
+    }
+    config.supply.save(storage, &Uint128::zero())?;
+    config.default_base_uri.save(storage, &None)?;
+
+    let legacy_balances_store: Map<(&Addr, &str), Uint128> = Map::new("balances");
+    let legacy_balances = legacy_balances_store
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((owner, token_id), amount) in legacy_balances.iter() {
+        // remove legacy balance first, saving the indexed balance would fail to load it
+        legacy_balances_store.remove(storage, (owner, token_id.as_str()));
+        config.balances.save(
+            storage,
+            (owner.clone(), token_id.to_string()),
+            &Balance {
+                owner: owner.clone(),
+                token_id: token_id.to_string(),
+                amount: *amount,
+            },
+        )?;
+        config.increment_tokens(storage, token_id, amount)?;
+    }
+
+    let legacy_tokens_store: Map<&str, String> = Map::new("tokens");
+    let legacy_tokens = legacy_tokens_store
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token_id, token_uri) in legacy_tokens.iter() {
+        let token_info = TokenInfo::<TMetadataExtension> {
+            token_uri: Some(token_uri.clone()).filter(|uri| !uri.is_empty()),
+            extension: None,
+            config: TokenConfig::default(),
+            minted: config.token_count(storage, token_id)?,
+        };
+        config.tokens.save(storage, token_id, &token_info)?;
+    }
+
+    Ok(response
+        .add_attribute("migrated balances", legacy_balances.len().to_string())
+        .add_attribute("migrated tokens", legacy_tokens.len().to_string()))
+}
+
+/// Migrates only in case collection_info is not present
+/// - older cw1155 versions: name and symbol were stored in dedicated `collection_info` store
+/// - cw-plus `cw1155-base`: no collection info at all, so name and symbol must be provided
+pub fn migrate_legacy_collection_info(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msg: &Cw1155MigrateMsg,
+    response: Response,
+) -> Result<Response, Cw1155ContractError> {
+    let config = Cw1155Config::<Empty, Empty, Empty, Empty>::default();
+    if config.collection_info.may_load(storage)?.is_some() {
+        return Ok(response);
+    }
+    let legacy_collection_info_store: Item<LegacyCollectionInfo> = Item::new("collection_info");
+    let (name, symbol) = match (legacy_collection_info_store.may_load(storage)?, msg) {
+        (Some(legacy_collection_info), _) => {
+            legacy_collection_info_store.remove(storage);
+            (legacy_collection_info.name, legacy_collection_info.symbol)
+        }
+        (
+            None,
+            Cw1155MigrateMsg::WithUpdate {
+                name: Some(name),
+                symbol: Some(symbol),
+                ..
+            },
+        ) => (name.clone(), symbol.clone()),
+        _ => return Err(Cw1155ContractError::MissingCollectionInfo {}),
+    };
+    let collection_info = CollectionInfo {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        updated_at: env.block.time,
+    };
+    config.collection_info.save(storage, &collection_info)?;
+    Ok(response
+        .add_attribute("migrated collection name", name)
+        .add_attribute("migrated collection symbol", symbol))
+}
+
+/// Collection info as stored by older cw1155 versions
+#[cw_serde]
+struct LegacyCollectionInfo {
+    name: String,
+    symbol: String,
+}
+
 /// To mitigate clippy::too_many_arguments warning
 pub struct ExecuteEnv<'a> {
     deps: DepsMut<'a>,
@@ -1,6 +1,7 @@
 pub mod error;
 pub mod event;
 pub mod execute;
+#[allow(deprecated)]
 pub mod msg;
 pub mod query;
 pub mod receiver;
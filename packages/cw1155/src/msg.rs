@@ -2,16 +2,21 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use std::fmt::{Display, Formatter};
 
 use cosmwasm_std::{Addr, Binary, Env, Uint128};
-use cw721::Approval;
-use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use cw721::msg::{CollectionInfoAndExtensionResponse, CollectionInfoMsg};
+use cw721::{Approval, DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg};
+use cw_ownable::{Action, Ownership};
 use cw_utils::Expiration;
 
+use crate::state::TokenConfig;
+
 #[cw_serde]
 pub struct Cw1155InstantiateMsg {
     /// Name of the token contract
     pub name: String,
     /// Symbol of the token contract
     pub symbol: String,
+    /// Optional extension of the collection metadata
+    pub collection_info_extension: DefaultOptionalCollectionExtensionMsg,
 
     /// The minter is the only one who can create new tokens.
     /// This is designed for a base token platform that is controlled by an external program or
@@ -19,17 +24,44 @@ pub struct Cw1155InstantiateMsg {
     /// If None, sender is the minter.
     pub minter: Option<String>,
 
+    /// Sets the creator of collection. The creator is the only one eligible to update `CollectionInfo`,
+    /// token metadata and the default base uri.
+    /// If None, sender is the creator.
+    pub creator: Option<String>,
+
     /// optional default base token uri to prepend to token id for off-chain metadata.
     /// If Some, this will be used as the base for all tokens without a set uri
     pub default_uri: Option<String>,
 }
 
+#[cw_serde]
+pub enum Cw1155MigrateMsg {
+    WithUpdate {
+        minter: Option<String>,
+        creator: Option<String>,
+        /// Name and symbol of the collection, only used in case no collection info is stored.
+        /// Required for contracts migrating from cw-plus `cw1155-base`, which has no collection info.
+        name: Option<String>,
+        symbol: Option<String>,
+    },
+}
+
 /// This is like Cw1155ExecuteMsg but we add a Mint command for a minter
 /// to make this stand-alone. You will likely want to remove mint and
 /// use other control logic in any contract that inherits this.
-#[cw_ownable_execute]
 #[cw_serde]
 pub enum Cw1155ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
+    #[deprecated(since = "0.19.0", note = "Please use UpdateMinterOwnership instead")]
+    /// Deprecated: use UpdateMinterOwnership instead! Will be removed in next release!
+    UpdateOwnership(Action),
+    UpdateMinterOwnership(Action),
+    UpdateCreatorOwnership(Action),
+
+    /// The creator is the only one eligible to update `CollectionInfo`.
+    UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg<DefaultOptionalCollectionExtensionMsg>,
+    },
+
     // cw1155
     /// BatchSendFrom is a base message to move multiple types of tokens in batch,
     /// if `env.sender` is the owner or has sufficient pre-approval.
@@ -60,7 +92,9 @@ pub enum Cw1155ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
         expires: Option<Expiration>,
     },
     /// Remove previously granted ApproveAll permission
-    RevokeAll { operator: String },
+    RevokeAll {
+        operator: String,
+    },
 
     // cw721
     /// SendFrom is a base message to move tokens,
@@ -104,7 +138,9 @@ pub enum Cw1155ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
         amount: Option<Uint128>,
     },
     /// Admin function to update default base token uri
-    UpdateDefaultUri { uri: Option<String> },
+    UpdateDefaultUri {
+        uri: Option<String>,
+    },
     /// Admin function to update token uri for a batch of tokens
     UpdateMetadata(TokenUpdate<TMetadataExtension>),
     /// Admin function to update token uri for a batch of tokens
@@ -113,10 +149,11 @@ pub enum Cw1155ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
     },
 
     /// Extension msg
-    Extension { msg: TMetadataExtensionMsg },
+    Extension {
+        msg: TMetadataExtensionMsg,
+    },
 }
 
-#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum Cw1155QueryMsg<TMetadataExtension, TQueryExtensionMsg> {
@@ -170,9 +207,26 @@ pub enum Cw1155QueryMsg<TMetadataExtension, TQueryExtensionMsg> {
     /// Returns top-level metadata about the contract.
     #[returns(cw721::state::CollectionInfo)]
     ContractInfo {},
+    /// Returns `CollectionInfoAndExtensionResponse`
+    #[returns(CollectionInfoAndExtensionResponse<DefaultOptionalCollectionExtension>)]
+    GetCollectionInfoAndExtension {},
+
+    #[deprecated(since = "0.19.0", note = "Please use GetMinterOwnership instead")]
+    #[returns(Ownership<Addr>)]
+    /// Deprecated: use GetMinterOwnership instead! Will be removed in next release!
+    Ownership {},
+
     /// Query Minter.
+    #[deprecated(since = "0.19.0", note = "Please use GetMinterOwnership instead")]
     #[returns(cw721::msg::MinterResponse)]
+    /// Deprecated: use GetMinterOwnership instead! Will be removed in next release!
     Minter {},
+
+    #[returns(Ownership<Addr>)]
+    GetMinterOwnership {},
+
+    #[returns(Ownership<Addr>)]
+    GetCreatorOwnership {},
     /// With MetaData Extension.
     /// Query metadata of token
     #[returns(TokenInfoResponse<TMetadataExtension>)]
@@ -185,6 +239,14 @@ pub enum Cw1155QueryMsg<TMetadataExtension, TQueryExtensionMsg> {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// With MetaData and Enumerable extension.
+    /// Returns all tokens owned by the given address with their balance and metadata, [] if unset.
+    #[returns(Vec<AllTokenInfoResponse<TMetadataExtension>>)]
+    AllTokenInfo {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
     #[returns(cw721::msg::TokensResponse)]
@@ -232,6 +294,8 @@ pub struct IsApprovedForAllResponse {
 #[cw_serde]
 pub struct AllTokenInfoResponse<T> {
     pub token_id: String,
+    /// Balance of the owner
+    pub balance: Uint128,
     pub info: TokenInfoResponse<T>,
 }
 
@@ -241,6 +305,10 @@ pub struct TokenInfoResponse<T> {
     pub token_uri: String,
     /// You can add any custom metadata here when you extend cw1155-base
     pub extension: Option<T>,
+    /// Supply rules, set at first mint
+    pub config: TokenConfig,
+    /// Amount which can still be minted, `None` if unlimited
+    pub remaining_supply: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -254,6 +322,8 @@ pub struct Cw1155MintMsg<T> {
     pub token_uri: Option<String>,
     /// Any custom extension used by this contract
     pub extension: Option<T>,
+    /// Only first mint can set supply rules, unlimited supply if `None`
+    pub config: Option<TokenConfig>,
 }
 
 #[cw_serde]
@@ -314,12 +384,6 @@ pub struct OwnersOfResponse {
     pub count: u64,
 }
 
-#[cw_serde]
-pub struct CollectionInfo {
-    pub name: String,
-    pub symbol: String,
-}
-
 #[cw_serde]
 pub struct TokenUpdate<TMetadataExtension> {
     pub token_id: String,
@@ -1,15 +1,21 @@
-use cosmwasm_std::{to_json_binary, Addr, Binary, CustomMsg, Deps, Env, Order, StdResult, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CustomMsg, Deps, Env, Order, StdError, StdResult, Uint128,
+};
 use cw721::msg::TokensResponse;
-use cw721::query::query_creator_ownership;
-use cw721::Approval;
+#[allow(deprecated)]
+use cw721::query::{
+    query_collection_info_and_extension, query_creator_ownership, query_minter,
+    query_minter_ownership,
+};
+use cw721::{Approval, DefaultOptionalCollectionExtension};
 use cw_storage_plus::Bound;
 use cw_utils::{maybe_addr, Expiration};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::msg::{
-    ApprovedForAllResponse, Balance, BalanceResponse, BalancesResponse, Cw1155QueryMsg,
-    DefaultBaseUriResponse, IsApprovedForAllResponse, OwnerToken, OwnersOfResponse,
+    AllTokenInfoResponse, ApprovedForAllResponse, Balance, BalanceResponse, BalancesResponse,
+    Cw1155QueryMsg, DefaultBaseUriResponse, IsApprovedForAllResponse, OwnerToken, OwnersOfResponse,
 };
 use crate::msg::{NumTokensResponse, TokenInfoResponse};
 use crate::state::Cw1155Config;
@@ -39,7 +45,20 @@ pub trait Cw1155Query<
         msg: Cw1155QueryMsg<TMetadataExtension, TQueryExtensionMsg>,
     ) -> StdResult<Binary> {
         match msg {
-            Cw1155QueryMsg::Minter {} => to_json_binary(&query_creator_ownership(deps.storage)?),
+            #[allow(deprecated)]
+            Cw1155QueryMsg::Minter {} => to_json_binary(&query_minter(deps.storage)?),
+            #[allow(deprecated)]
+            Cw1155QueryMsg::Ownership {} => to_json_binary(&query_minter_ownership(deps.storage)?),
+            Cw1155QueryMsg::GetMinterOwnership {} => {
+                to_json_binary(&query_minter_ownership(deps.storage)?)
+            }
+            Cw1155QueryMsg::GetCreatorOwnership {} => {
+                to_json_binary(&query_creator_ownership(deps.storage)?)
+            }
+            Cw1155QueryMsg::GetCollectionInfoAndExtension {} => to_json_binary(
+                &query_collection_info_and_extension::<DefaultOptionalCollectionExtension>(deps)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?,
+            ),
             Cw1155QueryMsg::BalanceOf(OwnerToken { owner, token_id }) => {
                 let config = Cw1155Config::<
                     TMetadataExtension,
@@ -181,33 +200,7 @@ pub trait Cw1155Query<
                 to_json_binary(&IsApprovedForAllResponse { approved })
             }
             Cw1155QueryMsg::TokenInfo { token_id } => {
-                let config = Cw1155Config::<
-                    TMetadataExtension,
-                    TCustomResponseMessage,
-                    TMetadataExtensionMsg,
-                    TQueryExtensionMsg,
-                >::default();
-                let token_info = config.tokens.load(deps.storage, &token_id)?;
-                let token_uri = token_info
-                    .token_uri
-                    .or_else(|| {
-                        config
-                            .default_base_uri
-                            .load(deps.storage)
-                            .map(|base_uri| {
-                                if let Some(base_uri) = base_uri {
-                                    format!("{}{}", base_uri, token_id)
-                                } else {
-                                    "".to_string()
-                                }
-                            })
-                            .ok()
-                    })
-                    .unwrap_or_default();
-                to_json_binary(&TokenInfoResponse::<TMetadataExtension> {
-                    token_uri,
-                    extension: token_info.extension,
-                })
+                to_json_binary(&self.query_token_info(deps, token_id)?)
             }
             Cw1155QueryMsg::Tokens {
                 owner,
@@ -217,6 +210,14 @@ pub trait Cw1155Query<
                 let owner_addr = deps.api.addr_validate(&owner)?;
                 to_json_binary(&self.query_owner_tokens(deps, owner_addr, start_after, limit)?)
             }
+            Cw1155QueryMsg::AllTokenInfo {
+                owner,
+                start_after,
+                limit,
+            } => {
+                let owner_addr = deps.api.addr_validate(&owner)?;
+                to_json_binary(&self.query_all_token_info(deps, owner_addr, start_after, limit)?)
+            }
             Cw1155QueryMsg::ContractInfo {} => to_json_binary(
                 &Cw1155Config::<
                     TMetadataExtension,
@@ -244,9 +245,6 @@ pub trait Cw1155Query<
             Cw1155QueryMsg::AllTokens { start_after, limit } => {
                 to_json_binary(&self.query_all_tokens(deps, start_after, limit)?)
             }
-            Cw1155QueryMsg::Ownership {} => {
-                to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
-            }
             Cw1155QueryMsg::DefaultBaseUri {} => {
                 to_json_binary(&self.query_default_base_uri(deps)?)
             }
@@ -313,6 +311,75 @@ pub trait Cw1155Query<
         Ok(TokensResponse { tokens })
     }
 
+    fn query_token_info(
+        &self,
+        deps: Deps,
+        token_id: String,
+    ) -> StdResult<TokenInfoResponse<TMetadataExtension>> {
+        let config = Cw1155Config::<
+            TMetadataExtension,
+            TCustomResponseMessage,
+            TMetadataExtensionMsg,
+            TQueryExtensionMsg,
+        >::default();
+        let token_info = config.tokens.load(deps.storage, &token_id)?;
+        let token_uri = token_info
+            .token_uri
+            .or_else(|| {
+                config
+                    .default_base_uri
+                    .load(deps.storage)
+                    .map(|base_uri| {
+                        if let Some(base_uri) = base_uri {
+                            format!("{}{}", base_uri, token_id)
+                        } else {
+                            "".to_string()
+                        }
+                    })
+                    .ok()
+            })
+            .unwrap_or_default();
+        Ok(TokenInfoResponse::<TMetadataExtension> {
+            token_uri,
+            remaining_supply: token_info.remaining_supply(),
+            extension: token_info.extension,
+            config: token_info.config,
+        })
+    }
+
+    /// Returns tokens owned by the given address with their balance and metadata.
+    fn query_all_token_info(
+        &self,
+        deps: Deps,
+        owner: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<AllTokenInfoResponse<TMetadataExtension>>> {
+        let config = Cw1155Config::<
+            TMetadataExtension,
+            TCustomResponseMessage,
+            TMetadataExtensionMsg,
+            TQueryExtensionMsg,
+        >::default();
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+        config
+            .balances
+            .prefix(owner)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (token_id, balance) = item?;
+                Ok(AllTokenInfoResponse {
+                    info: self.query_token_info(deps, token_id.clone())?,
+                    token_id,
+                    balance: balance.amount,
+                })
+            })
+            .collect()
+    }
+
     fn query_all_tokens(
         &self,
         deps: Deps,
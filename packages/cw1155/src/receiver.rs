@@ -1,7 +1,14 @@
-use crate::msg::TokenAmount;
+use crate::error::Cw1155ContractError;
+use crate::msg::{Cw1155ExecuteMsg, TokenAmount};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, MessageInfo, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, Empty, MessageInfo, StdResult,
+    Uint128, WasmMsg,
+};
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+pub use cw721::receiver::ReceiverAllowlist;
 
 /// Cw1155ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
 #[cw_serde]
@@ -39,6 +46,28 @@ impl Cw1155ReceiveMsg {
         };
         Ok(execute.into())
     }
+
+    /// Validates the hook sent by a cw1155 contract and decodes its payload. `info.sender` is the
+    /// collection, which must be in the allowlist, if given.
+    pub fn into_received<TMsg: DeserializeOwned>(
+        self,
+        deps: Deps,
+        info: &MessageInfo,
+        allowlist: Option<&ReceiverAllowlist>,
+    ) -> Result<ReceivedTokens<TMsg>, Cw1155ContractError> {
+        received_tokens(
+            deps,
+            info,
+            allowlist,
+            &self.operator,
+            self.from.as_deref(),
+            vec![TokenAmount {
+                token_id: self.token_id,
+                amount: self.amount,
+            }],
+            &self.msg,
+        )
+    }
 }
 
 /// Cw1155BatchReceiveMsg should be de/serialized under `BatchReceive()` variant in a ExecuteMsg
@@ -74,11 +103,95 @@ impl Cw1155BatchReceiveMsg {
         };
         Ok(execute.into())
     }
+
+    /// Same as `Cw1155ReceiveMsg::into_received`, for a batch of tokens.
+    pub fn into_received<TMsg: DeserializeOwned>(
+        self,
+        deps: Deps,
+        info: &MessageInfo,
+        allowlist: Option<&ReceiverAllowlist>,
+    ) -> Result<ReceivedTokens<TMsg>, Cw1155ContractError> {
+        received_tokens(
+            deps,
+            info,
+            allowlist,
+            &self.operator,
+            self.from.as_deref(),
+            self.batch,
+            &self.msg,
+        )
+    }
+}
+
+fn received_tokens<TMsg: DeserializeOwned>(
+    deps: Deps,
+    info: &MessageInfo,
+    allowlist: Option<&ReceiverAllowlist>,
+    operator: &str,
+    from: Option<&str>,
+    tokens: Vec<TokenAmount>,
+    msg: &Binary,
+) -> Result<ReceivedTokens<TMsg>, Cw1155ContractError> {
+    if let Some(allowlist) = allowlist {
+        if !allowlist.is_allowed(deps.storage, &info.sender) {
+            return Err(Cw1155ContractError::CollectionNotAllowed {
+                collection: info.sender.to_string(),
+            });
+        }
+    }
+    Ok(ReceivedTokens {
+        collection: info.sender.clone(),
+        operator: deps.api.addr_validate(operator)?,
+        from: from.map(|from| deps.api.addr_validate(from)).transpose()?,
+        tokens,
+        msg: from_json(msg)?,
+    })
+}
+
+/// A validated `Cw1155ReceiveMsg` or `Cw1155BatchReceiveMsg`, with its decoded payload.
+#[cw_serde]
+pub struct ReceivedTokens<TMsg> {
+    /// The cw1155 contract of the received tokens.
+    pub collection: Addr,
+    /// The account that executed the send message.
+    pub operator: Addr,
+    /// The account the tokens were transferred from, `None` if minted.
+    pub from: Option<Addr>,
+    pub tokens: Vec<TokenAmount>,
+    pub msg: TMsg,
+}
+
+impl<TMsg> ReceivedTokens<TMsg> {
+    /// The account the tokens are bounced to: the previous owner, or the minter for minted tokens.
+    pub fn bounce_recipient(&self) -> &Addr {
+        self.from.as_ref().unwrap_or(&self.operator)
+    }
+
+    /// creates a cosmos_msg sending the tokens back, as a single `SendBatch`. Unlike returning
+    /// an error, the hook succeeds and the tokens are returned in the same transaction.
+    pub fn bounce_msg<C>(&self) -> StdResult<CosmosMsg<C>>
+    where
+        C: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        let msg = Cw1155ExecuteMsg::<Empty, Empty>::SendBatch {
+            from: None,
+            to: self.bounce_recipient().to_string(),
+            batch: self.tokens.clone(),
+            msg: None,
+        };
+        let execute = WasmMsg::Execute {
+            contract_addr: self.collection.to_string(),
+            msg: to_json_binary(&msg)?,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
 }
 
-// This is just a helper to properly serialize the above message
+/// This is just a helper to properly serialize the above messages.
+/// The actual receiver should include these variants in the larger ExecuteMsg enum
 #[cw_serde]
-enum ReceiverExecuteMsg {
+pub enum ReceiverExecuteMsg {
     Receive(Cw1155ReceiveMsg),
     BatchReceive(Cw1155BatchReceiveMsg),
 }
@@ -1,7 +1,8 @@
 use crate::error::Cw1155ContractError;
-use crate::msg::{Balance, CollectionInfo, TokenApproval};
+use crate::msg::{Balance, TokenApproval};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, CustomMsg, Env, StdError, StdResult, Storage, Uint128};
+use cw721::state::{Attribute, CollectionInfo};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use cw_utils::Expiration;
 use serde::de::DeserializeOwned;
@@ -23,7 +24,9 @@ pub struct Cw1155Config<
     TMetadataExtensionMsg: CustomMsg,
     TQueryExtensionMsg: Serialize + DeserializeOwned + Clone,
 {
+    /// Same keys as `Cw721Config`, so collection info and extension are managed by cw721 helpers.
     pub collection_info: Item<'a, CollectionInfo>,
+    pub collection_extension: Map<'a, String, Attribute>,
     pub supply: Item<'a, Uint128>, // total supply of all tokens
     pub default_base_uri: Item<'a, Option<String>>, // default base token uri for tokens with no token_uri
     // key: token id
@@ -57,7 +60,8 @@ where
 {
     fn default() -> Self {
         Self::new(
-            "collection_info",
+            "cw721_collection_info",
+            "cw721_collection_info_extension",
             "tokens",
             "token_count",
             "supply",
@@ -84,7 +88,8 @@ where
 {
     #[allow(clippy::too_many_arguments)]
     fn new(
-        contract_info_key: &'a str,
+        collection_info_key: &'a str,
+        collection_info_extension_key: &'a str,
         tokens_key: &'a str,
         token_count_key: &'a str,
         supply_key: &'a str,
@@ -101,7 +106,8 @@ where
             ),
         };
         Self {
-            collection_info: Item::new(contract_info_key),
+            collection_info: Item::new(collection_info_key),
+            collection_extension: Map::new(collection_info_extension_key),
             tokens: Map::new(tokens_key),
             token_count: Map::new(token_count_key),
             supply: Item::new(supply_key),
@@ -206,6 +212,56 @@ pub struct TokenInfo<T> {
     pub token_uri: Option<String>,
     /// You can add any custom metadata here when you extend cw1155-base
     pub extension: Option<T>,
+    /// Supply rules, set at first mint
+    #[serde(default)]
+    pub config: TokenConfig,
+    /// Total amount ever minted, including burned tokens
+    #[serde(default)]
+    pub minted: Uint128,
+}
+
+impl<T> TokenInfo<T> {
+    /// Returns the amount which can still be minted, `None` if unlimited.
+    pub fn remaining_supply(&self) -> Option<Uint128> {
+        if !self.config.mintable && !self.minted.is_zero() {
+            return Some(Uint128::zero());
+        }
+        self.config
+            .max_supply()
+            .map(|max_supply| max_supply.saturating_sub(self.minted))
+    }
+}
+
+/// Supply rules of a token, set at its first mint.
+#[cw_serde]
+pub struct TokenConfig {
+    /// Maximum amount ever minted, unlimited if `None`. Burned tokens are not minted again.
+    pub max_supply: Option<Uint128>,
+    /// A unique NFT, with a max supply of 1.
+    pub unique: bool,
+    /// Whether the token can be minted again after its first mint.
+    pub mintable: bool,
+}
+
+impl Default for TokenConfig {
+    fn default() -> Self {
+        Self {
+            max_supply: None,
+            unique: false,
+            mintable: true,
+        }
+    }
+}
+
+impl TokenConfig {
+    /// Returns the max supply, which is 1 for unique NFTs.
+    pub fn max_supply(&self) -> Option<Uint128> {
+        if self.unique {
+            Some(Uint128::one())
+        } else {
+            self.max_supply
+        }
+    }
 }
 
 pub struct BalanceIndexes<'a> {
@@ -1,6 +1,6 @@
 use std::{num::ParseIntError, str::ParseBoolError};
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp};
 use cw_ownable::OwnershipError;
 use thiserror::Error;
 use url::ParseError;
@@ -34,6 +34,21 @@ pub enum Cw721ContractError {
     #[error("token_id already claimed")]
     Claimed {},
 
+    #[error("token_id must be provided, since no token id sequence is set")]
+    MissingTokenId {},
+
+    #[error("Token id sequence cannot be rewound, next must not be less than {next}")]
+    TokenIdSequenceRewound { next: u64 },
+
+    #[error("Max supply of {max_supply} NFTs reached")]
+    MaxSupplyReached { max_supply: u64 },
+
+    #[error("Max supply can only be decreased, current max supply: {max_supply}")]
+    MaxSupplyIncreased { max_supply: u64 },
+
+    #[error("Max supply must not be less than {total_minted} NFTs minted so far")]
+    MaxSupplyBelowMinted { total_minted: u64 },
+
     #[error("Caller is not collection creator")]
     NotCreator {},
 
@@ -46,12 +61,18 @@ pub enum Cw721ContractError {
     #[error("Cannot set approval that is already expired")]
     Expired {},
 
+    #[error("Trading not started yet. Trading starts at {start_trading_time}")]
+    TradingNotStarted { start_trading_time: Timestamp },
+
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
 
     #[error("No withdraw address set")]
     NoWithdrawAddress {},
 
+    #[error("No collection extension set")]
+    NoCollectionExtension {},
+
     #[error("Collection name must not be empty")]
     CollectionNameEmpty {},
 
@@ -76,6 +97,15 @@ pub enum Cw721ContractError {
     #[error("Trait display type in metadata must not be empty")]
     TraitDisplayTypeEmpty {},
 
+    #[error("Batch must not be empty")]
+    EmptyBatch {},
+
+    #[error("NFTs are not transferable by the sender due to the transfer policy")]
+    NonTransferable {},
+
+    #[error("Tokens of collection {collection} are not accepted")]
+    CollectionNotAllowed { collection: String },
+
     #[error("Internal error. Missing argument: Info")]
     NoInfo,
 }
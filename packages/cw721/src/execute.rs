@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    Addr, Api, BankMsg, Binary, Coin, CustomMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdResult, Storage,
+    to_json_binary, Addr, Api, BankMsg, Binary, Coin, CustomMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, Storage,
 };
 use cw_ownable::{none_or, Action, Ownership, OwnershipError};
 use cw_storage_plus::Item;
@@ -12,10 +12,20 @@ use crate::{
         Cw721BaseExtensions, Cw721EmptyExtensions, Cw721Extensions, Cw721OnchainExtensions,
     },
     helpers::value_or_empty,
-    msg::{CollectionInfoMsg, Cw721InstantiateMsg, Cw721MigrateMsg, NftInfoMsg},
-    query::query_collection_info_and_extension,
+    msg::{
+        validate_royalty_share, CollectionInfoMsg, Cw721InstantiateMsg, Cw721MigrateMsg, MintMsg,
+        NftInfoMsg, RoyaltySplitMsg, TransferMsg,
+    },
+    query::{
+        query_collection_info_and_extension, query_collection_royalties,
+        query_collection_royalty_info, query_start_trading_time,
+    },
     receiver::Cw721ReceiveMsg,
-    state::{CollectionInfo, Cw721Config, NftInfo, CREATOR, MINTER},
+    state::{
+        Attribute, CollectionInfo, Cw721Config, NftInfo, ProvenanceAction, ProvenanceEntry,
+        TokenIdSequence, TransferPolicy, ATTRIBUTE_ROYALTY_INFO, ATTRIBUTE_ROYALTY_SPLIT, CREATOR,
+        MINTER,
+    },
     traits::{
         Cw721CustomMsg, Cw721Execute, Cw721State, FromAttributesState, StateFactory,
         ToAttributesState,
@@ -59,6 +69,7 @@ where
         name: Some(msg.name),
         symbol: Some(msg.symbol),
         extension: msg.collection_info_extension,
+        max_supply: msg.max_supply,
     };
     let collection_info = collection_metadata_msg.create(deps.as_ref(), env, info.into(), None)?;
     let extension_attributes = collection_info.extension.to_attributes_state()?;
@@ -71,6 +82,9 @@ where
             .collection_extension
             .save(deps.storage, attr.key.clone(), &attr)?;
     }
+    if let Some(max_supply) = msg.max_supply {
+        update_max_supply(deps.storage, max_supply)?;
+    }
 
     // ---- set minter and creator ----
     // use info.sender if None is passed
@@ -121,6 +135,28 @@ pub fn transfer_nft<TNftExtension>(
     recipient: &str,
     token_id: &str,
 ) -> Result<NftInfo<TNftExtension>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    move_nft(
+        deps,
+        env,
+        info,
+        recipient,
+        token_id,
+        ProvenanceAction::Transfer,
+    )
+}
+
+/// Changes the owner of an NFT and records it in the NFT's history as `action`.
+fn move_nft<TNftExtension>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: &str,
+    token_id: &str,
+    action: ProvenanceAction,
+) -> Result<NftInfo<TNftExtension>, Cw721ContractError>
 where
     TNftExtension: Cw721State,
 {
@@ -128,13 +164,56 @@ where
     let mut token = config.nft_info.load(deps.storage, token_id)?;
     // ensure we have permissions
     check_can_send(deps.as_ref(), env, info.sender.as_str(), &token)?;
+    assert_trading_started(deps.storage, env, &info.sender)?;
     // set owner and remove existing approvals
+    let previous_owner = token.owner;
     token.owner = deps.api.addr_validate(recipient)?;
     token.approvals = vec![];
     config.nft_info.save(deps.storage, token_id, &token)?;
+    record_provenance(
+        deps.storage,
+        env,
+        token_id,
+        Some(previous_owner),
+        Some(token.owner.clone()),
+        action,
+    )?;
     Ok(token)
 }
 
+/// Appends an entry to the NFT's history, if provenance tracking is enabled.
+pub fn record_provenance(
+    storage: &mut dyn Storage,
+    env: &Env,
+    token_id: &str,
+    previous_owner: Option<Addr>,
+    new_owner: Option<Addr>,
+    action: ProvenanceAction,
+) -> StdResult<()> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if !config.is_provenance_tracked(storage)? {
+        return Ok(());
+    }
+    let index = config
+        .token_history
+        .prefix(token_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    let entry = ProvenanceEntry {
+        index,
+        previous_owner,
+        new_owner,
+        action,
+        block_height: env.block.height,
+        time: env.block.time,
+    };
+    config
+        .token_history
+        .save(storage, (token_id, index), &entry)
+}
+
 pub fn send_nft<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -148,7 +227,14 @@ where
     TCustomResponseMsg: CustomMsg,
 {
     // Transfer token
-    transfer_nft::<TNftExtension>(deps, env, info, &contract, &token_id)?;
+    move_nft::<TNftExtension>(
+        deps,
+        env,
+        info,
+        &contract,
+        &token_id,
+        ProvenanceAction::Send,
+    )?;
 
     let send = Cw721ReceiveMsg {
         sender: info.sender.to_string(),
@@ -211,6 +297,7 @@ where
 
     // only difference between approve and revoke
     if add {
+        assert_trading_started(deps.storage, env, &info.sender)?;
         // reject expired data as invalid
         let expires = expires.unwrap_or_default();
         if expires.is_expired(&env.block) {
@@ -254,6 +341,7 @@ pub fn approve_all<TCustomResponseMsg>(
     operator: String,
     expires: Option<Expiration>,
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_trading_started(deps.storage, env, &info.sender)?;
     // reject expired data as invalid
     let expires = expires.unwrap_or_default();
     if expires.is_expired(&env.block) {
@@ -301,10 +389,9 @@ pub fn burn_nft<TCustomResponseMsg>(
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
     let config = Cw721Config::<Option<Empty>>::default();
     let token = config.nft_info.load(deps.storage, &token_id)?;
-    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token)?;
+    check_can_burn(deps.as_ref(), env, info.sender.as_str(), &token)?;
 
-    config.nft_info.remove(deps.storage, &token_id)?;
-    config.decrement_tokens(deps.storage)?;
+    remove_nft(deps.storage, env, &token_id)?;
 
     Ok(Response::new()
         .add_attribute("action", "burn")
@@ -312,6 +399,29 @@ pub fn burn_nft<TCustomResponseMsg>(
         .add_attribute("token_id", token_id))
 }
 
+/// Removes an NFT and its royalties, regardless of who owns it. Returns the former owner.
+/// NOTE: the caller is responsible for checking permissions.
+pub fn remove_nft(
+    storage: &mut dyn Storage,
+    env: &Env,
+    token_id: &str,
+) -> Result<Addr, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let token = config.nft_info.load(storage, token_id)?;
+    config.nft_info.remove(storage, token_id)?;
+    config.token_royalties.remove(storage, token_id);
+    config.decrement_tokens(storage)?;
+    record_provenance(
+        storage,
+        env,
+        token_id,
+        Some(token.owner.clone()),
+        None,
+        ProvenanceAction::Burn,
+    )?;
+    Ok(token.owner)
+}
+
 pub fn update_collection_info<TCollectionExtension, TCollectionExtensionMsg, TCustomResponseMsg>(
     deps: DepsMut,
     info: Option<&MessageInfo>,
@@ -330,13 +440,26 @@ where
     config
         .collection_info
         .save(deps.storage, &collection_info.into())?;
+    let current_royalty_info = config
+        .collection_extension
+        .may_load(deps.storage, ATTRIBUTE_ROYALTY_INFO.to_string())?;
     for attr in extension_attributes {
+        // an updated royalty info replaces the collection's royalty split
+        if attr.key == ATTRIBUTE_ROYALTY_INFO && Some(&attr) != current_royalty_info.as_ref() {
+            config
+                .collection_extension
+                .remove(deps.storage, ATTRIBUTE_ROYALTY_SPLIT.to_string());
+        }
         config
             .collection_extension
             .save(deps.storage, attr.key.clone(), &attr)?;
     }
 
-    let response = Response::new().add_attribute("action", "update_collection_info");
+    let mut response = Response::new().add_attribute("action", "update_collection_info");
+    if let Some(max_supply) = msg.max_supply {
+        update_max_supply(deps.storage, max_supply)?;
+        response = response.add_attribute("max_supply", max_supply.to_string());
+    }
     if let Some(info) = info {
         Ok(response.add_attribute("sender", info.sender.to_string()))
     } else {
@@ -344,12 +467,33 @@ where
     }
 }
 
+/// Sets the max supply. Once set, it can only be decreased, but not below the number of NFTs minted so far.
+pub fn update_max_supply(
+    storage: &mut dyn Storage,
+    max_supply: u64,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if let Some(current) = config.max_supply.may_load(storage)? {
+        if max_supply > current {
+            return Err(Cw721ContractError::MaxSupplyIncreased {
+                max_supply: current,
+            });
+        }
+    }
+    let total_minted = config.total_minted(storage)?;
+    if max_supply < total_minted {
+        return Err(Cw721ContractError::MaxSupplyBelowMinted { total_minted });
+    }
+    config.max_supply.save(storage, &max_supply)?;
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn mint<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
     info: &MessageInfo,
-    token_id: String,
+    token_id: Option<String>,
     owner: String,
     token_uri: Option<String>,
     extension: TNftExtensionMsg,
@@ -359,6 +503,7 @@ where
     TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCustomResponseMsg: CustomMsg,
 {
+    let token_id = assign_token_id(deps.storage, token_id)?;
     // create the token
     let token_msg = NftInfoMsg {
         owner: owner.clone(),
@@ -368,16 +513,31 @@ where
     };
     let token = token_msg.create(deps.as_ref(), env, info.into(), None)?;
     let config = Cw721Config::<TNftExtension>::default();
-    config
+    let token = config
         .nft_info
         .update(deps.storage, &token_id, |old| match old {
             Some(_) => Err(Cw721ContractError::Claimed {}),
             None => Ok(token),
         })?;
 
+    if let Some(max_supply) = config.max_supply.may_load(deps.storage)? {
+        if config.total_minted(deps.storage)? >= max_supply {
+            return Err(Cw721ContractError::MaxSupplyReached { max_supply });
+        }
+    }
+    config.increment_total_minted(deps.storage)?;
     config.increment_tokens(deps.storage)?;
+    record_provenance(
+        deps.storage,
+        env,
+        &token_id,
+        None,
+        Some(token.owner),
+        ProvenanceAction::Mint,
+    )?;
 
     let mut res = Response::new()
+        .set_data(to_json_binary(&token_id)?)
         .add_attribute("action", "mint")
         .add_attribute("minter", info.sender.to_string())
         .add_attribute("owner", owner)
@@ -388,6 +548,187 @@ where
     Ok(res)
 }
 
+/// Returns the given token id. If None, the next id of the token id sequence is assigned,
+/// skipping ids the sequence assigned before, ids already minted, as well as burned ids with an
+/// ownership history.
+pub fn assign_token_id(
+    storage: &mut dyn Storage,
+    token_id: Option<String>,
+) -> Result<String, Cw721ContractError> {
+    if let Some(token_id) = token_id {
+        return Ok(token_id);
+    }
+    let config = Cw721Config::<Option<Empty>>::default();
+    let mut sequence = config
+        .token_id_sequence
+        .may_load(storage)?
+        .ok_or(Cw721ContractError::MissingTokenId {})?;
+    if let Some(assigned) = config
+        .token_id_sequence_assigned
+        .may_load(storage, &sequence.prefix)?
+    {
+        sequence.next = sequence.next.max(assigned + 1);
+    }
+    let mut token_id = format!("{}{}", sequence.prefix, sequence.next);
+    while config.nft_info.has(storage, &token_id)
+        || config
+            .token_history
+            .prefix(&token_id)
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+    {
+        sequence.next += 1;
+        token_id = format!("{}{}", sequence.prefix, sequence.next);
+    }
+    config
+        .token_id_sequence_assigned
+        .save(storage, &sequence.prefix, &sequence.next)?;
+    sequence.next += 1;
+    config.token_id_sequence.save(storage, &sequence)?;
+    Ok(token_id)
+}
+
+/// Mints all NFTs atomically. Response contains `owner`, `token_id` and optional `token_uri` attributes for each NFT.
+/// Response data contains the minted token ids.
+pub fn batch_mint<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    mints: Vec<MintMsg<TNftExtensionMsg>>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    if mints.is_empty() {
+        return Err(Cw721ContractError::EmptyBatch {});
+    }
+    let mut res = Response::new()
+        .add_attribute("action", "batch_mint")
+        .add_attribute("minter", info.sender.to_string());
+    let mut token_ids = Vec::with_capacity(mints.len());
+    for MintMsg {
+        token_id,
+        owner,
+        token_uri,
+        extension,
+    } in mints
+    {
+        let token_id = assign_token_id(deps.storage, token_id)?;
+        mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps.branch(),
+            env,
+            info,
+            Some(token_id.clone()),
+            owner.clone(),
+            token_uri.clone(),
+            extension,
+        )?;
+        res = res
+            .add_attribute("owner", owner)
+            .add_attribute("token_id", token_id.clone());
+        if let Some(token_uri) = token_uri {
+            res = res.add_attribute("token_uri", value_or_empty(&token_uri));
+        }
+        token_ids.push(token_id);
+    }
+    Ok(res.set_data(to_json_binary(&token_ids)?))
+}
+
+/// Transfers all NFTs atomically. Response contains `recipient` and `token_id` attributes for each NFT.
+pub fn batch_transfer_nft<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    transfers: Vec<TransferMsg>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    if transfers.is_empty() {
+        return Err(Cw721ContractError::EmptyBatch {});
+    }
+    let mut res = Response::new()
+        .add_attribute("action", "batch_transfer_nft")
+        .add_attribute("sender", info.sender.to_string());
+    for TransferMsg {
+        recipient,
+        token_id,
+    } in transfers
+    {
+        transfer_nft::<TNftExtension>(deps.branch(), env, info, &recipient, &token_id)?;
+        res = res
+            .add_attribute("recipient", recipient)
+            .add_attribute("token_id", token_id);
+    }
+    Ok(res)
+}
+
+/// Sends all NFTs atomically to the given contract, with a `Cw721ReceiveMsg` for each NFT.
+/// Response contains a `token_id` attribute for each NFT.
+pub fn batch_send_nft<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    contract: String,
+    token_ids: Vec<String>,
+    msg: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    if token_ids.is_empty() {
+        return Err(Cw721ContractError::EmptyBatch {});
+    }
+    let mut res = Response::new()
+        .add_attribute("action", "batch_send_nft")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", contract.clone());
+    for token_id in token_ids {
+        move_nft::<TNftExtension>(
+            deps.branch(),
+            env,
+            info,
+            &contract,
+            &token_id,
+            ProvenanceAction::Send,
+        )?;
+        let send = Cw721ReceiveMsg {
+            sender: info.sender.to_string(),
+            token_id: token_id.clone(),
+            msg: msg.clone(),
+        };
+        res = res
+            .add_message(send.into_cosmos_msg(contract.clone())?)
+            .add_attribute("token_id", token_id);
+    }
+    Ok(res)
+}
+
+/// Burns all NFTs atomically. Response contains a `token_id` attribute for each NFT.
+pub fn batch_burn_nft<TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_ids: Vec<String>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    if token_ids.is_empty() {
+        return Err(Cw721ContractError::EmptyBatch {});
+    }
+    let mut res = Response::new()
+        .add_attribute("action", "batch_burn")
+        .add_attribute("sender", info.sender.to_string());
+    for token_id in token_ids {
+        burn_nft::<TCustomResponseMsg>(deps.branch(), env, info, token_id.clone())?;
+        res = res.add_attribute("token_id", token_id);
+    }
+    Ok(res)
+}
+
 pub fn update_minter_ownership<TCustomResponseMsg>(
     api: &dyn Api,
     storage: &mut dyn Storage,
@@ -444,6 +785,171 @@ where
         .add_attribute("token_id", token_id))
 }
 
+/// The creator is the only one eligible to set a royalty split for a given NFT.
+/// Combined share is validated the same way as the collection's royalty info: max share increase
+/// applies to the royalties currently in effect for the NFT, also when removing the override.
+pub fn set_token_royalty<TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    royalty_split: Option<RoyaltySplitMsg>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, &info.sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    // ensure token exists
+    config.nft_info.load(deps.storage, &token_id)?;
+
+    let res = Response::new()
+        .add_attribute("action", "set_token_royalty")
+        .add_attribute("token_id", token_id.clone());
+    let collection_royalties = query_collection_royalties(deps.storage)?;
+    let current = config.token_royalties.may_load(deps.storage, &token_id)?;
+    match royalty_split {
+        Some(royalty_split) => {
+            let current = current.or(collection_royalties);
+            let royalty_split =
+                royalty_split.create(deps.as_ref(), env, Some(info), current.as_ref())?;
+            config
+                .token_royalties
+                .save(deps.storage, &token_id, &royalty_split)?;
+            Ok(res.add_attribute("share", royalty_split.total_share().to_string()))
+        }
+        None => {
+            // removing the override falls back to the collection's royalties
+            if let (Some(current), Some(collection_royalties)) = (current, collection_royalties) {
+                validate_royalty_share(
+                    collection_royalties.total_share(),
+                    Some(current.total_share()),
+                )?;
+            }
+            config.token_royalties.remove(deps.storage, &token_id);
+            Ok(res)
+        }
+    }
+}
+
+/// The creator is the only one eligible to set a royalty split for the collection.
+/// The split is stored along with the collection extension, hence it requires a collection extension.
+/// Max share increase applies to the current split, or else to the collection's royalty info.
+/// Removing the split falls back to the collection's royalty info, which is validated the same way.
+pub fn set_collection_royalty_split<TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    royalty_split: Option<RoyaltySplitMsg>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, &info.sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    if !config
+        .collection_extension
+        .has(deps.storage, ATTRIBUTE_ROYALTY_INFO.to_string())
+    {
+        return Err(Cw721ContractError::NoCollectionExtension {});
+    }
+
+    let res = Response::new().add_attribute("action", "set_collection_royalty_split");
+    let current = query_collection_royalties(deps.storage)?;
+    match royalty_split {
+        Some(royalty_split) => {
+            let royalty_split =
+                royalty_split.create(deps.as_ref(), env, Some(info), current.as_ref())?;
+            let attribute = Attribute {
+                key: ATTRIBUTE_ROYALTY_SPLIT.to_string(),
+                value: to_json_binary(&royalty_split)?,
+            };
+            config
+                .collection_extension
+                .save(deps.storage, attribute.key.clone(), &attribute)?;
+            Ok(res.add_attribute("share", royalty_split.total_share().to_string()))
+        }
+        None => {
+            if let (Some(current), Some(royalty_info)) =
+                (current, query_collection_royalty_info(deps.storage)?)
+            {
+                validate_royalty_share(royalty_info.share, Some(current.total_share()))?;
+            }
+            config
+                .collection_extension
+                .remove(deps.storage, ATTRIBUTE_ROYALTY_SPLIT.to_string());
+            Ok(res)
+        }
+    }
+}
+
+/// Only the creator can set who is eligible to transfer and send NFTs.
+pub fn set_transfer_policy<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    transfer_policy: TransferPolicy,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    config
+        .transfer_policy
+        .save(deps.storage, &transfer_policy)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_transfer_policy")
+        .add_attribute("transfer_policy", format!("{:?}", transfer_policy)))
+}
+
+/// Only the creator can set the sequence of token ids assigned on mint. None disables it.
+/// For the same prefix, the sequence cannot be rewound below the highest number it ever assigned,
+/// even after being disabled or switched to another prefix, so ids of burned NFTs are not assigned again.
+pub fn set_token_id_sequence<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    token_id_sequence: Option<TokenIdSequence>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    let res = Response::new().add_attribute("action", "set_token_id_sequence");
+    match token_id_sequence {
+        Some(token_id_sequence) => {
+            if let Some(current) = config.token_id_sequence.may_load(deps.storage)? {
+                if current.prefix == token_id_sequence.prefix
+                    && token_id_sequence.next < current.next
+                {
+                    return Err(Cw721ContractError::TokenIdSequenceRewound { next: current.next });
+                }
+            }
+            if let Some(assigned) = config
+                .token_id_sequence_assigned
+                .may_load(deps.storage, &token_id_sequence.prefix)?
+            {
+                if token_id_sequence.next <= assigned {
+                    return Err(Cw721ContractError::TokenIdSequenceRewound { next: assigned + 1 });
+                }
+            }
+            config
+                .token_id_sequence
+                .save(deps.storage, &token_id_sequence)?;
+            Ok(res
+                .add_attribute("prefix", value_or_empty(&token_id_sequence.prefix))
+                .add_attribute("next", token_id_sequence.next.to_string()))
+        }
+        None => {
+            config.token_id_sequence.remove(deps.storage);
+            Ok(res)
+        }
+    }
+}
+
+/// Only the creator can enable or disable recording of ownership history.
+/// History recorded before disabling is kept.
+pub fn set_provenance_tracking<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    enabled: bool,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    config.provenance_tracking.save(deps.storage, &enabled)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_provenance_tracking")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
 pub fn set_withdraw_address<TCustomResponseMsg>(
     deps: DepsMut,
     sender: &Addr,
@@ -531,12 +1037,36 @@ where
     }
 }
 
-/// returns true if the sender can transfer ownership of the token
+/// returns true if the sender can transfer ownership of the token, as allowed by the transfer policy
 pub fn check_can_send<TNftExtension>(
     deps: Deps,
     env: &Env,
     sender: &str,
     token: &NftInfo<TNftExtension>,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    match config.transfer_policy(deps.storage)? {
+        TransferPolicy::Transferable => check_can_burn(deps, env, sender, token),
+        TransferPolicy::Soulbound => Err(Cw721ContractError::NonTransferable {}),
+        TransferPolicy::Revocable => {
+            let sender = deps.api.addr_validate(sender)?;
+            if MINTER.assert_owner(deps.storage, &sender).is_ok()
+                || CREATOR.assert_owner(deps.storage, &sender).is_ok()
+            {
+                Ok(())
+            } else {
+                Err(Cw721ContractError::NonTransferable {})
+            }
+        }
+    }
+}
+
+/// returns true if the sender can burn the token, regardless of the transfer policy
+pub fn check_can_burn<TNftExtension>(
+    deps: Deps,
+    env: &Env,
+    sender: &str,
+    token: &NftInfo<TNftExtension>,
 ) -> Result<(), Cw721ContractError> {
     let sender = deps.api.addr_validate(sender)?;
     // owner can send
@@ -586,6 +1116,27 @@ pub fn assert_creator(storage: &dyn Storage, sender: &Addr) -> Result<(), Cw721C
     Ok(())
 }
 
+/// Trading (transfer, send and approvals) is only possible once `start_trading_time` in the collection extension has been reached.
+/// The minter is not restricted, so it can mint and distribute NFTs before trading starts.
+/// With a `Revocable` transfer policy, the creator is not restricted either, so it can revoke NFTs anytime.
+pub fn assert_trading_started(
+    storage: &dyn Storage,
+    env: &Env,
+    sender: &Addr,
+) -> Result<(), Cw721ContractError> {
+    if let Some(start_trading_time) = query_start_trading_time(storage)? {
+        if env.block.time < start_trading_time && assert_minter(storage, sender).is_err() {
+            let config = Cw721Config::<Option<Empty>>::default();
+            let revoking_creator = config.transfer_policy(storage)? == TransferPolicy::Revocable
+                && assert_creator(storage, sender).is_ok();
+            if !revoking_creator {
+                return Err(Cw721ContractError::TradingNotStarted { start_trading_time });
+            }
+        }
+    }
+    Ok(())
+}
+
 // ------- migrate -------
 pub fn migrate(
     deps: DepsMut,
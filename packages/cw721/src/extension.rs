@@ -144,6 +144,7 @@ impl Default for Cw721EmptyExtensions<'static> {
 ///     minter: None,
 ///     creator: None,
 ///     withdraw_address: None,
+///     max_supply: None,
 /// };
 /// //...
 /// // mint:
@@ -164,7 +165,7 @@ impl Default for Cw721EmptyExtensions<'static> {
 ///     DefaultOptionalCollectionExtensionMsg,
 ///     Empty,
 /// >::Mint {
-///     token_id: token_id.to_string(),
+///     token_id: Some(token_id.to_string()),
 ///     owner: "john".to_string(),
 ///     token_uri: token_uri.clone(),
 ///     extension: extension.clone(), // use `extension: None` for no metadata
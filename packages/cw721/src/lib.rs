@@ -15,7 +15,10 @@ use msg::{
     CollectionExtensionMsg, CollectionInfoAndExtensionResponse, NftExtensionMsg,
     RoyaltyInfoResponse,
 };
-pub use state::{Approval, Attribute, CollectionExtension, NftExtension, RoyaltyInfo};
+pub use state::{
+    Approval, Attribute, CollectionExtension, NftExtension, ProvenanceAction, ProvenanceEntry,
+    RoyaltyInfo, RoyaltySplit, TokenIdSequence, TransferPolicy,
+};
 
 // Expose for 3rd party contracts interacting without a need to directly dependend on cw_ownable.
 //
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
     to_json_binary, Addr, Binary, Coin, ContractInfoResponse, Decimal, Deps, Env, MessageInfo,
-    Timestamp,
+    Timestamp, Uint128,
 };
 use cw_ownable::{Action, Ownership};
 use cw_utils::Expiration;
@@ -12,15 +12,17 @@ use url::Url;
 
 use crate::error::Cw721ContractError;
 use crate::execute::{assert_creator, assert_minter};
+use crate::query::query_collection_royalty_split;
 use crate::state::{
-    Attribute, CollectionExtension, CollectionExtensionAttributes, CollectionInfo, NftInfo, Trait,
-    ATTRIBUTE_DESCRIPTION, ATTRIBUTE_EXPLICIT_CONTENT, ATTRIBUTE_EXTERNAL_LINK, ATTRIBUTE_IMAGE,
-    ATTRIBUTE_ROYALTY_INFO, ATTRIBUTE_START_TRADING_TIME, CREATOR,
-    MAX_COLLECTION_DESCRIPTION_LENGTH, MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
+    Attribute, CollectionExtension, CollectionExtensionAttributes, CollectionInfo, NftInfo,
+    ProvenanceEntry, TokenIdSequence, Trait, TransferPolicy, ATTRIBUTE_DESCRIPTION,
+    ATTRIBUTE_EXPLICIT_CONTENT, ATTRIBUTE_EXTERNAL_LINK, ATTRIBUTE_IMAGE, ATTRIBUTE_ROYALTY_INFO,
+    ATTRIBUTE_START_TRADING_TIME, CREATOR, MAX_COLLECTION_DESCRIPTION_LENGTH,
+    MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
 };
 use crate::traits::{Cw721CustomMsg, Cw721State, FromAttributesState, ToAttributesState};
 use crate::NftExtension;
-use crate::{traits::StateFactory, Approval, RoyaltyInfo};
+use crate::{traits::StateFactory, Approval, RoyaltyInfo, RoyaltySplit};
 
 #[cw_serde]
 pub enum Cw721ExecuteMsg<
@@ -78,8 +80,9 @@ pub enum Cw721ExecuteMsg<
 
     /// Mint a new NFT, can only be called by the contract minter
     Mint {
-        /// Unique ID of the NFT
-        token_id: String,
+        /// Unique ID of the NFT. If None, the next id of the token id sequence is assigned.
+        /// The minted token id is returned in the response data.
+        token_id: Option<String>,
         /// The owner of the newly minter NFT
         owner: String,
         /// Universal resource identifier for this NFT
@@ -95,6 +98,54 @@ pub enum Cw721ExecuteMsg<
         token_id: String,
     },
 
+    /// Mint multiple NFTs at once, can only be called by the contract minter.
+    /// All mints are executed atomically: if one fails, none is minted.
+    BatchMint {
+        mints: Vec<MintMsg<TNftExtensionMsg>>,
+    },
+    /// Transfer multiple NFTs at once, each to its own recipient.
+    /// All transfers are executed atomically: if one fails, none is transferred.
+    BatchTransfer {
+        transfers: Vec<TransferMsg>,
+    },
+    /// Send multiple NFTs at once to a contract. Like `SendNft`, a `ReceiveNft` callback
+    /// with the given msg is triggered for each NFT.
+    BatchSend {
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+    },
+    /// Burn multiple NFTs at once the sender has access to.
+    BatchBurn {
+        token_ids: Vec<String>,
+    },
+
+    /// The creator is the only one eligible to set a royalty split for a given NFT, overriding the
+    /// collection's royalties. None removes the override.
+    SetTokenRoyalty {
+        token_id: String,
+        royalty_split: Option<RoyaltySplitMsg>,
+    },
+    /// The creator is the only one eligible to set a royalty split for the collection, overriding the
+    /// collection's `royalty_info` until it is updated via `UpdateCollectionInfo`. None removes the override.
+    SetCollectionRoyaltySplit {
+        royalty_split: Option<RoyaltySplitMsg>,
+    },
+    /// The creator is the only one eligible to enable or disable recording of each NFT's
+    /// ownership history. Disabled by default.
+    SetProvenanceTracking {
+        enabled: bool,
+    },
+    /// The creator is the only one eligible to set who can transfer and send NFTs.
+    SetTransferPolicy {
+        transfer_policy: TransferPolicy,
+    },
+    /// The creator is the only one eligible to set the sequence of token ids assigned on mint,
+    /// in case no token id is provided. None disables it.
+    SetTokenIdSequence {
+        token_id_sequence: Option<TokenIdSequence>,
+    },
+
     /// Custom msg execution. This is a no-op in default implementation.
     UpdateExtension {
         msg: TExtensionMsg,
@@ -122,6 +173,28 @@ pub enum Cw721ExecuteMsg<
     },
 }
 
+/// Used by `Cw721ExecuteMsg::BatchMint`, same as `Cw721ExecuteMsg::Mint`.
+#[cw_serde]
+pub struct MintMsg<TNftExtensionMsg> {
+    /// Unique ID of the NFT. If None, the next id of the token id sequence is assigned.
+    pub token_id: Option<String>,
+    /// The owner of the newly minter NFT
+    pub owner: String,
+    /// Universal resource identifier for this NFT
+    /// Should point to a JSON file that conforms to the ERC721
+    /// Metadata JSON Schema
+    pub token_uri: Option<String>,
+    /// Any custom extension used by this contract
+    pub extension: TNftExtensionMsg,
+}
+
+/// Used by `Cw721ExecuteMsg::BatchTransfer`, same as `Cw721ExecuteMsg::TransferNft`.
+#[cw_serde]
+pub struct TransferMsg {
+    pub recipient: String,
+    pub token_id: String,
+}
+
 #[cw_serde]
 pub struct Cw721InstantiateMsg<TCollectionExtensionMsg> {
     /// Name of the NFT contract
@@ -140,6 +213,10 @@ pub struct Cw721InstantiateMsg<TCollectionExtensionMsg> {
     pub creator: Option<String>,
 
     pub withdraw_address: Option<String>,
+
+    /// Max number of NFTs that can ever be minted, including burned ones. None means unlimited.
+    /// Once set, it can only be decreased via `UpdateCollectionInfo`.
+    pub max_supply: Option<u64>,
 }
 
 #[cw_serde]
@@ -281,6 +358,49 @@ pub enum Cw721QueryMsg<
 
     #[returns(Option<String>)]
     GetWithdrawAddress {},
+
+    /// Returns `TradingStatusResponse`, whether NFTs can be transferred, sent and approved by owners.
+    #[returns(TradingStatusResponse)]
+    GetTradingStatus {},
+
+    /// CW2981: returns the royalty owed for the given sale price.
+    /// Royalties of the NFT are used, falling back to the collection's royalties.
+    /// NOTE: in case royalties are split, the total amount is returned along with the first recipient.
+    /// Use `RoyaltySplitInfo` for paying each recipient.
+    /// See https://eips.ethereum.org/EIPS/eip-2981
+    #[returns(RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        // the denom of this sale must also be the denom returned by RoyaltiesInfoResponse
+        sale_price: Uint128,
+    },
+    /// Returns the royalty owed to each recipient for the given sale price.
+    #[returns(RoyaltySplitInfoResponse)]
+    RoyaltySplitInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    /// CW2981: returns whether marketplaces should check royalties on sale.
+    #[returns(CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+
+    /// Returns who is eligible to transfer and send NFTs.
+    #[returns(TransferPolicy)]
+    GetTransferPolicy {},
+
+    /// Returns the sequence of token ids assigned on mint, if set.
+    #[returns(Option<TokenIdSequence>)]
+    GetTokenIdSequence {},
+
+    /// Returns the recorded ownership history of an NFT, oldest first.
+    /// History is kept after the NFT is burned.
+    #[returns(TokenHistoryResponse)]
+    TokenHistory {
+        token_id: String,
+        /// Index of the last entry of the previous page.
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -296,6 +416,9 @@ pub struct CollectionInfoMsg<TCollectionExtensionMsg> {
     pub name: Option<String>,
     pub symbol: Option<String>,
     pub extension: TCollectionExtensionMsg,
+    /// Sets or decreases the max supply enforced on mint. Must not be less than the number of NFTs
+    /// minted so far. None leaves it unchanged.
+    pub max_supply: Option<u64>,
 }
 
 #[cw_serde]
@@ -416,22 +539,26 @@ impl StateFactory<CollectionExtension<RoyaltyInfo>>
                     updated.start_trading_time = Some(start_trading_time);
                 }
                 if let Some(royalty_info_response) = &self.royalty_info {
-                    match current.royalty_info.clone() {
-                        // Some: existing royalty info for update
-                        Some(current_royalty_info) => {
-                            updated.royalty_info = Some(royalty_info_response.create(
-                                deps,
-                                env,
-                                info,
-                                Some(&current_royalty_info),
-                            )?);
-                        }
-                        // None: no royalty info, so create new
-                        None => {
-                            updated.royalty_info =
-                                Some(royalty_info_response.create(deps, env, info, None)?);
+                    // max share increase applies to the royalties in effect, so in case there is a
+                    // royalty split, it is validated against its combined share
+                    let current_royalty_info = match query_collection_royalty_split(deps.storage)? {
+                        Some(royalty_split) => {
+                            royalty_split
+                                .recipients
+                                .first()
+                                .map(|recipient| RoyaltyInfo {
+                                    payment_address: recipient.payment_address.clone(),
+                                    share: royalty_split.total_share(),
+                                })
                         }
-                    }
+                        None => current.royalty_info.clone(),
+                    };
+                    updated.royalty_info = Some(royalty_info_response.create(
+                        deps,
+                        env,
+                        info,
+                        current_royalty_info.as_ref(),
+                    )?);
                 }
                 Ok(updated)
             }
@@ -558,30 +685,103 @@ impl StateFactory<RoyaltyInfo> for RoyaltyInfoResponse {
         _info: Option<&MessageInfo>,
         current: Option<&RoyaltyInfo>,
     ) -> Result<(), Cw721ContractError> {
-        if let Some(current_royalty_info) = current {
-            // check max share delta
-            if current_royalty_info.share < self.share {
-                let share_delta = self.share.abs_diff(current_royalty_info.share);
-
-                if share_delta > Decimal::percent(MAX_ROYALTY_SHARE_DELTA_PCT) {
-                    return Err(Cw721ContractError::InvalidRoyalties(format!(
-                        "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
-                    )));
-                }
-            }
-        }
-        // check max share
-        if self.share > Decimal::percent(MAX_ROYALTY_SHARE_PCT) {
-            return Err(Cw721ContractError::InvalidRoyalties(format!(
-                "Share cannot be greater than {MAX_ROYALTY_SHARE_PCT}%"
-            )));
-        }
+        validate_royalty_share(self.share, current.map(|current| current.share))?;
         // validate payment address
         deps.api.addr_validate(self.payment_address.as_str())?;
         Ok(())
     }
 }
 
+/// Checks max share, and max share increase in case there is a current share.
+pub fn validate_royalty_share(
+    share: Decimal,
+    current_share: Option<Decimal>,
+) -> Result<(), Cw721ContractError> {
+    if let Some(current_share) = current_share {
+        // check max share delta
+        if current_share < share {
+            let share_delta = share.abs_diff(current_share);
+
+            if share_delta > Decimal::percent(MAX_ROYALTY_SHARE_DELTA_PCT) {
+                return Err(Cw721ContractError::InvalidRoyalties(format!(
+                    "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
+                )));
+            }
+        }
+    }
+    // check max share
+    if share > Decimal::percent(MAX_ROYALTY_SHARE_PCT) {
+        return Err(Cw721ContractError::InvalidRoyalties(format!(
+            "Share cannot be greater than {MAX_ROYALTY_SHARE_PCT}%"
+        )));
+    }
+    Ok(())
+}
+
+#[cw_serde]
+pub struct RoyaltySplitMsg {
+    pub recipients: Vec<RoyaltyInfoResponse>,
+}
+
+impl Cw721CustomMsg for RoyaltySplitMsg {}
+
+impl StateFactory<RoyaltySplit> for RoyaltySplitMsg {
+    fn create(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: Option<&MessageInfo>,
+        current: Option<&RoyaltySplit>,
+    ) -> Result<RoyaltySplit, Cw721ContractError> {
+        self.validate(deps, env, info, current)?;
+        let recipients = self
+            .recipients
+            .iter()
+            .map(|recipient| RoyaltyInfo {
+                payment_address: Addr::unchecked(recipient.payment_address.as_str()), // no check needed, since it is already done in validate
+                share: recipient.share,
+            })
+            .collect();
+        Ok(RoyaltySplit { recipients })
+    }
+
+    fn validate(
+        &self,
+        deps: Deps,
+        _env: &Env,
+        _info: Option<&MessageInfo>,
+        current: Option<&RoyaltySplit>,
+    ) -> Result<(), Cw721ContractError> {
+        if self.recipients.is_empty() {
+            return Err(Cw721ContractError::InvalidRoyalties(
+                "Royalty split must have at least one recipient".to_string(),
+            ));
+        }
+        let mut total_share = Decimal::zero();
+        for (i, recipient) in self.recipients.iter().enumerate() {
+            if recipient.share.is_zero() {
+                return Err(Cw721ContractError::InvalidRoyalties(
+                    "Share must be greater than 0".to_string(),
+                ));
+            }
+            // validate payment address
+            deps.api.addr_validate(recipient.payment_address.as_str())?;
+            if self.recipients[..i]
+                .iter()
+                .any(|other| other.payment_address == recipient.payment_address)
+            {
+                return Err(Cw721ContractError::InvalidRoyalties(format!(
+                    "Duplicate recipient {}",
+                    recipient.payment_address
+                )));
+            }
+            total_share += recipient.share;
+        }
+        // max share and max share delta apply to the combined share
+        validate_royalty_share(total_share, current.map(|current| current.total_share()))
+    }
+}
+
 impl From<RoyaltyInfo> for RoyaltyInfoResponse {
     fn from(royalty_info: RoyaltyInfo) -> Self {
         Self {
@@ -601,6 +801,9 @@ pub struct ConfigResponse<TCollectionExtension> {
     pub collection_info: CollectionInfo,
     pub collection_extension: TCollectionExtension,
     pub contract_info: ContractInfoResponse,
+    pub max_supply: Option<u64>,
+    /// Number of NFTs minted so far, including burned ones.
+    pub total_minted: u64,
 }
 
 /// This is a wrapper around CollectionInfo that includes the extension.
@@ -622,6 +825,9 @@ pub struct AllInfoResponse {
     pub collection_extension: CollectionExtensionAttributes,
     // NFT details
     pub num_tokens: u64,
+    pub max_supply: Option<u64>,
+    /// Number of NFTs minted so far, including burned ones.
+    pub total_minted: u64,
 }
 
 impl<T> From<CollectionInfoAndExtensionResponse<T>> for CollectionInfo {
@@ -698,7 +904,7 @@ where
         // - CREATOR store is empty/not initioized (like in instantiation)
         // - info is none (like in migration)
         let creator_initialized = CREATOR.item.may_load(deps.storage)?;
-        if (self.name.is_some() || self.symbol.is_some())
+        if (self.name.is_some() || self.symbol.is_some() || self.max_supply.is_some())
             && creator_initialized.is_some()
             && info.is_some()
             && CREATOR
@@ -799,6 +1005,40 @@ where
     }
 }
 
+#[cw_serde]
+pub struct TradingStatusResponse {
+    /// Start trading time as defined in the collection extension.
+    pub start_trading_time: Option<Timestamp>,
+    /// True in case there is no start trading time or it has been reached.
+    pub is_open: bool,
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    /// Empty in case no royalties are owed.
+    pub address: String,
+    /// Note: this must be the same denom as the sale price. Rounded down.
+    pub royalty_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TokenHistoryResponse {
+    pub history: Vec<ProvenanceEntry>,
+}
+
+#[cw_serde]
+pub struct RoyaltySplitInfoResponse {
+    /// Empty in case no royalties are owed.
+    pub royalties: Vec<RoyaltiesInfoResponse>,
+}
+
+/// Shows if the contract implements royalties.
+/// If royalty_payments is true, marketplaces should pay them.
+#[cw_serde]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
+
 #[cw_serde]
 pub struct OwnerOfResponse {
     /// Owner of the token
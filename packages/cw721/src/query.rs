@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    Addr, BlockInfo, CustomMsg, Deps, Empty, Env, Order, StdError, StdResult, Storage,
+    Addr, BlockInfo, CustomMsg, Deps, Empty, Env, Order, StdError, StdResult, Storage, Timestamp,
+    Uint128,
 };
 use cw_ownable::Ownership;
 use cw_storage_plus::Bound;
@@ -12,16 +13,19 @@ use crate::{
     },
     msg::{
         AllInfoResponse, AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
-        CollectionInfoAndExtensionResponse, ConfigResponse, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        CheckRoyaltiesResponse, CollectionInfoAndExtensionResponse, ConfigResponse, MinterResponse,
+        NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
+        RoyaltiesInfoResponse, RoyaltySplitInfoResponse, TokenHistoryResponse, TokensResponse,
+        TradingStatusResponse,
     },
     state::{
-        Approval, CollectionExtensionAttributes, CollectionInfo, Cw721Config, NftInfo, CREATOR,
-        MINTER,
+        Approval, CollectionExtensionAttributes, CollectionInfo, Cw721Config, NftInfo,
+        TokenIdSequence, TransferPolicy, ATTRIBUTE_ROYALTY_INFO, ATTRIBUTE_ROYALTY_SPLIT,
+        ATTRIBUTE_START_TRADING_TIME, CREATOR, MINTER,
     },
     traits::{Contains, Cw721CustomMsg, Cw721Query, Cw721State, FromAttributesState},
     DefaultOptionalCollectionExtension, DefaultOptionalNftExtension,
-    EmptyOptionalCollectionExtension, EmptyOptionalNftExtension,
+    EmptyOptionalCollectionExtension, EmptyOptionalNftExtension, RoyaltyInfo, RoyaltySplit,
 };
 
 pub const DEFAULT_LIMIT: u32 = 10;
@@ -107,6 +111,9 @@ where
     let creator_ownership = query_creator_ownership(deps.storage)?;
     let withdraw_address = query_withdraw_address(deps)?;
     let contract_info = deps.querier.query_wasm_contract_info(contract_addr)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    let max_supply = config.max_supply.may_load(deps.storage)?;
+    let total_minted = config.total_minted(deps.storage)?;
     Ok(ConfigResponse {
         num_tokens,
         minter_ownership,
@@ -115,6 +122,8 @@ where
         collection_extension,
         withdraw_address,
         contract_info,
+        max_supply,
+        total_minted,
     })
 }
 pub fn query_collection_info_and_extension<TCollectionExtension>(
@@ -137,7 +146,10 @@ where
 pub fn query_all_info(deps: Deps, env: &Env) -> StdResult<AllInfoResponse> {
     let collection_info = query_collection_info(deps.storage)?;
     let attributes = query_collection_extension_attributes(deps)?;
-    let num_tokens = Cw721Config::<Option<Empty>>::default().token_count(deps.storage)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    let num_tokens = config.token_count(deps.storage)?;
+    let max_supply = config.max_supply.may_load(deps.storage)?;
+    let total_minted = config.total_minted(deps.storage)?;
     let contract_info = deps
         .querier
         .query_wasm_contract_info(env.contract.address.clone())?;
@@ -145,10 +157,40 @@ pub fn query_all_info(deps: Deps, env: &Env) -> StdResult<AllInfoResponse> {
         collection_info,
         collection_extension: attributes,
         num_tokens,
+        max_supply,
+        total_minted,
         contract_info,
     })
 }
 
+/// Returns the start trading time stored in the collection extension, if any.
+pub fn query_start_trading_time(
+    storage: &dyn Storage,
+) -> Result<Option<Timestamp>, Cw721ContractError> {
+    let attribute = Cw721Config::<Option<Empty>>::default()
+        .collection_extension
+        .may_load(storage, ATTRIBUTE_START_TRADING_TIME.to_string())?;
+    match attribute {
+        Some(attribute) => attribute.value::<Option<Timestamp>>(),
+        None => Ok(None),
+    }
+}
+
+pub fn query_trading_status(
+    deps: Deps,
+    env: &Env,
+) -> Result<TradingStatusResponse, Cw721ContractError> {
+    let start_trading_time = query_start_trading_time(deps.storage)?;
+    let is_open = match start_trading_time {
+        Some(start_trading_time) => env.block.time >= start_trading_time,
+        None => true,
+    };
+    Ok(TradingStatusResponse {
+        start_trading_time,
+        is_open,
+    })
+}
+
 pub fn query_num_tokens(storage: &dyn Storage) -> StdResult<NumTokensResponse> {
     let count = Cw721Config::<Option<Empty>>::default().token_count(storage)?;
     Ok(NumTokensResponse { count })
@@ -419,6 +461,146 @@ pub fn query_withdraw_address(deps: Deps) -> StdResult<Option<String>> {
         .may_load(deps.storage)
 }
 
+pub fn query_transfer_policy(deps: Deps) -> StdResult<TransferPolicy> {
+    Cw721Config::<Option<Empty>>::default().transfer_policy(deps.storage)
+}
+
+pub fn query_token_id_sequence(deps: Deps) -> StdResult<Option<TokenIdSequence>> {
+    Cw721Config::<Option<Empty>>::default()
+        .token_id_sequence
+        .may_load(deps.storage)
+}
+
+/// Returns the royalty info stored in the collection extension, if any.
+pub fn query_collection_royalty_info(
+    storage: &dyn Storage,
+) -> Result<Option<RoyaltyInfo>, Cw721ContractError> {
+    let attribute = Cw721Config::<Option<Empty>>::default()
+        .collection_extension
+        .may_load(storage, ATTRIBUTE_ROYALTY_INFO.to_string())?;
+    match attribute {
+        Some(attribute) => attribute.value::<Option<RoyaltyInfo>>(),
+        None => Ok(None),
+    }
+}
+
+/// Returns the royalty split stored in the collection extension, if any.
+pub fn query_collection_royalty_split(
+    storage: &dyn Storage,
+) -> Result<Option<RoyaltySplit>, Cw721ContractError> {
+    let attribute = Cw721Config::<Option<Empty>>::default()
+        .collection_extension
+        .may_load(storage, ATTRIBUTE_ROYALTY_SPLIT.to_string())?;
+    match attribute {
+        Some(attribute) => attribute.value::<Option<RoyaltySplit>>(),
+        None => Ok(None),
+    }
+}
+
+/// Returns the royalties of the collection. The collection's royalty split is used, falling back to
+/// the collection's royalty info.
+pub fn query_collection_royalties(
+    storage: &dyn Storage,
+) -> Result<Option<RoyaltySplit>, Cw721ContractError> {
+    match query_collection_royalty_split(storage)? {
+        Some(royalty_split) => Ok(Some(royalty_split)),
+        None => Ok(query_collection_royalty_info(storage)?.map(RoyaltySplit::from)),
+    }
+}
+
+/// Returns the royalties of a given NFT. The royalty split of the NFT is used, falling back to the
+/// collection's royalties.
+pub fn query_royalty_split(
+    storage: &dyn Storage,
+    token_id: &str,
+) -> Result<Option<RoyaltySplit>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // ensure token exists
+    config.nft_info.load(storage, token_id)?;
+    if let Some(royalty_split) = config.token_royalties.may_load(storage, token_id)? {
+        return Ok(Some(royalty_split));
+    }
+    query_collection_royalties(storage)
+}
+
+/// Returns the royalty owed to each recipient.
+/// NOTE: royalty amounts are rounded down, EIP2981 leaves rounding to the implementer.
+pub fn query_royalty_split_info(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> Result<RoyaltySplitInfoResponse, Cw721ContractError> {
+    let royalties = match query_royalty_split(deps.storage, &token_id)? {
+        Some(royalty_split) => royalty_split
+            .recipients
+            .into_iter()
+            .map(|recipient| RoyaltiesInfoResponse {
+                address: recipient.payment_address.to_string(),
+                royalty_amount: sale_price * recipient.share,
+            })
+            .collect(),
+        None => vec![],
+    };
+    Ok(RoyaltySplitInfoResponse { royalties })
+}
+
+pub fn query_token_history(
+    deps: Deps,
+    token_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TokenHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let history = Cw721Config::<Option<Empty>>::default()
+        .token_history
+        .prefix(&token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokenHistoryResponse { history })
+}
+
+/// CW2981 royalty info. In case royalties are split, the total amount is returned along with the
+/// first recipient, so plain CW2981 marketplaces pay the full royalty. Use `RoyaltySplitInfo`
+/// for paying each recipient.
+/// NOTE: royalty amount is rounded down, EIP2981 leaves rounding to the implementer.
+pub fn query_royalty_info(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> Result<RoyaltiesInfoResponse, Cw721ContractError> {
+    match query_royalty_split(deps.storage, &token_id)? {
+        Some(royalty_split) => Ok(RoyaltiesInfoResponse {
+            address: royalty_split
+                .recipients
+                .first()
+                .map(|recipient| recipient.payment_address.to_string())
+                .unwrap_or_default(),
+            royalty_amount: sale_price * royalty_split.total_share(),
+        }),
+        None => Ok(RoyaltiesInfoResponse {
+            address: String::new(),
+            royalty_amount: Uint128::zero(),
+        }),
+    }
+}
+
+/// Royalties are owed in case the collection or any NFT has royalties.
+pub fn query_check_royalties(deps: Deps) -> Result<CheckRoyaltiesResponse, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let has_token_royalties = config
+        .token_royalties
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    let royalty_payments =
+        has_token_royalties || query_collection_royalties(deps.storage)?.is_some();
+    Ok(CheckRoyaltiesResponse { royalty_payments })
+}
+
 impl<'a> Cw721Query<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>
     for Cw721OnchainExtensions<'a>
 {
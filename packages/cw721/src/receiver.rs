@@ -1,7 +1,17 @@
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, Empty, MessageInfo, Order, StdResult,
+    Storage, WasmMsg,
+};
+use cw_storage_plus::{Bound, Map};
+
+use crate::error::Cw721ContractError;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
 
 /// Cw721ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
 #[cw_serde]
@@ -34,6 +44,89 @@ impl Cw721ReceiveMsg {
         };
         Ok(execute.into())
     }
+
+    /// Validates the hook sent by a cw721 contract and decodes its payload. `info.sender` is the
+    /// collection, which must be in the allowlist, if given.
+    pub fn into_received<TMsg: DeserializeOwned>(
+        self,
+        deps: Deps,
+        info: &MessageInfo,
+        allowlist: Option<&ReceiverAllowlist>,
+    ) -> Result<ReceivedNft<TMsg>, Cw721ContractError> {
+        if let Some(allowlist) = allowlist {
+            allowlist.assert_allowed(deps.storage, &info.sender)?;
+        }
+        Ok(ReceivedNft {
+            collection: info.sender.clone(),
+            sender: deps.api.addr_validate(&self.sender)?,
+            token_id: self.token_id,
+            msg: from_json(&self.msg)?,
+        })
+    }
+}
+
+/// A validated `Cw721ReceiveMsg`, with its decoded payload.
+///
+/// To reject an NFT, the receiver returns an error, reverting the send. Transferring the NFT back is
+/// not safe, since `sender` is not necessarily the previous owner.
+#[cw_serde]
+pub struct ReceivedNft<TMsg> {
+    /// The cw721 contract of the received NFT.
+    pub collection: Addr,
+    /// The account that executed `SendNft`: the previous owner, or an operator or spender approved by
+    /// the previous owner. `Cw721ReceiveMsg` does not carry the previous owner.
+    pub sender: Addr,
+    pub token_id: String,
+    pub msg: TMsg,
+}
+
+/// Collections a receiver contract accepts tokens from. Shared by cw721 and cw1155 receivers.
+pub struct ReceiverAllowlist<'a>(Map<'a, Addr, Empty>);
+
+impl<'a> ReceiverAllowlist<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Self(Map::new(namespace))
+    }
+
+    pub fn allow(&self, storage: &mut dyn Storage, collection: &Addr) -> StdResult<()> {
+        self.0.save(storage, collection.clone(), &Empty {})
+    }
+
+    pub fn disallow(&self, storage: &mut dyn Storage, collection: &Addr) {
+        self.0.remove(storage, collection.clone())
+    }
+
+    pub fn is_allowed(&self, storage: &dyn Storage, collection: &Addr) -> bool {
+        self.0.has(storage, collection.clone())
+    }
+
+    pub fn assert_allowed(
+        &self,
+        storage: &dyn Storage,
+        collection: &Addr,
+    ) -> Result<(), Cw721ContractError> {
+        if !self.is_allowed(storage, collection) {
+            return Err(Cw721ContractError::CollectionNotAllowed {
+                collection: collection.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the allowed collections, paginated by address.
+    pub fn collections(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Addr>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.cloned().map(Bound::exclusive);
+        self.0
+            .keys(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
 }
 
 /// This is just a helper to properly serialize the above message.
@@ -39,6 +39,9 @@ pub const ATTRIBUTE_EXTERNAL_LINK: &str = "external_link";
 pub const ATTRIBUTE_EXPLICIT_CONTENT: &str = "explicit_content";
 pub const ATTRIBUTE_START_TRADING_TIME: &str = "start_trading_time";
 pub const ATTRIBUTE_ROYALTY_INFO: &str = "royalty_info";
+/// Optional royalty split of the collection, stored next to the collection extension attributes.
+/// It takes precedence over the royalty info, until the royalty info is updated.
+pub const ATTRIBUTE_ROYALTY_SPLIT: &str = "royalty_split";
 // ----------------------
 
 pub struct Cw721Config<
@@ -57,6 +60,23 @@ pub struct Cw721Config<
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub nft_info: IndexedMap<'a, &'a str, NftInfo<TNftExtension>, TokenIndexes<'a, TNftExtension>>,
     pub withdraw_address: Item<'a, String>,
+    /// Optional royalty split per NFT, overriding the collection's royalties.
+    pub token_royalties: Map<'a, &'a str, RoyaltySplit>,
+    /// Whether ownership changes are recorded in `token_history`. Disabled by default.
+    pub provenance_tracking: Item<'a, bool>,
+    /// Ownership history of each NFT, stored as (token_id, index).
+    pub token_history: Map<'a, (&'a str, u64), ProvenanceEntry>,
+    /// Who is eligible to transfer and send NFTs. Defaults to `TransferPolicy::Transferable`.
+    pub transfer_policy: Item<'a, TransferPolicy>,
+    /// If set, `Mint` may omit the token id and the next id of the sequence is assigned.
+    pub token_id_sequence: Item<'a, TokenIdSequence>,
+    /// Highest number assigned by the token id sequence per prefix. Never lowered, so the
+    /// sequence can't assign ids of burned NFTs again, even after being disabled or switched.
+    pub token_id_sequence_assigned: Map<'a, &'a str, u64>,
+    /// Max number of NFTs that can ever be minted, including burned ones. Unlimited if not set.
+    pub max_supply: Item<'a, u64>,
+    /// Number of NFTs minted so far, including burned ones.
+    pub total_minted: Item<'a, u64>,
 }
 
 impl<TNftExtension> Default for Cw721Config<'static, TNftExtension>
@@ -73,6 +93,14 @@ where
             "tokens",
             "tokens__owner",
             "withdraw_address",
+            "token_royalties",
+            "provenance_tracking",
+            "token_history",
+            "transfer_policy",
+            "token_id_sequence",
+            "token_id_sequence_assigned",
+            "max_supply",
+            "total_minted",
         )
     }
 }
@@ -81,6 +109,7 @@ impl<'a, TNftExtension> Cw721Config<'a, TNftExtension>
 where
     TNftExtension: Cw721State,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         collection_info_key: &'a str,
         collection_info_extension_key: &'a str,
@@ -89,6 +118,14 @@ where
         nft_info_key: &'a str,
         nft_info_owner_key: &'a str,
         withdraw_address_key: &'a str,
+        token_royalties_key: &'a str,
+        provenance_tracking_key: &'a str,
+        token_history_key: &'a str,
+        transfer_policy_key: &'a str,
+        token_id_sequence_key: &'a str,
+        token_id_sequence_assigned_key: &'a str,
+        max_supply_key: &'a str,
+        total_minted_key: &'a str,
     ) -> Self {
         let indexes = TokenIndexes {
             owner: MultiIndex::new(token_owner_idx, nft_info_key, nft_info_owner_key),
@@ -100,6 +137,14 @@ where
             nft_info: IndexedMap::new(nft_info_key, indexes),
             withdraw_address: Item::new(withdraw_address_key),
             collection_extension: Map::new(collection_info_extension_key),
+            token_royalties: Map::new(token_royalties_key),
+            provenance_tracking: Item::new(provenance_tracking_key),
+            token_history: Map::new(token_history_key),
+            transfer_policy: Item::new(transfer_policy_key),
+            token_id_sequence: Item::new(token_id_sequence_key),
+            token_id_sequence_assigned: Map::new(token_id_sequence_assigned_key),
+            max_supply: Item::new(max_supply_key),
+            total_minted: Item::new(total_minted_key),
         }
     }
 
@@ -118,6 +163,31 @@ where
         self.num_tokens.save(storage, &val)?;
         Ok(val)
     }
+
+    /// Contracts minting before `total_minted` was tracked fall back to the number of existing NFTs.
+    pub fn total_minted(&self, storage: &dyn Storage) -> StdResult<u64> {
+        match self.total_minted.may_load(storage)? {
+            Some(total_minted) => Ok(total_minted),
+            None => self.token_count(storage),
+        }
+    }
+
+    pub fn increment_total_minted(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let val = self.total_minted(storage)? + 1;
+        self.total_minted.save(storage, &val)?;
+        Ok(val)
+    }
+
+    pub fn is_provenance_tracked(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self
+            .provenance_tracking
+            .may_load(storage)?
+            .unwrap_or_default())
+    }
+
+    pub fn transfer_policy(&self, storage: &dyn Storage) -> StdResult<TransferPolicy> {
+        Ok(self.transfer_policy.may_load(storage)?.unwrap_or_default())
+    }
 }
 
 pub fn token_owner_idx<TNftExtension>(_pk: &[u8], d: &NftInfo<TNftExtension>) -> Addr {
@@ -140,6 +210,51 @@ pub struct NftInfo<TNftExtension> {
     pub extension: TNftExtension,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub enum TransferPolicy {
+    /// Owners, approved spenders and operators can transfer and send NFTs.
+    #[default]
+    Transferable,
+    /// NFTs are bound to their owner and can't be transferred or sent by anyone.
+    /// Owners can still burn their NFTs.
+    Soulbound,
+    /// Only the minter and creator can transfer and send NFTs, e.g. for revocation or recovery.
+    /// Owners can still burn their NFTs.
+    Revocable,
+}
+
+/// Sequence of token ids assigned on mint, in case no token id is provided.
+/// Ids are `{prefix}{next}`, e.g. `nft-1`, `nft-2`, ... Ids already minted are skipped.
+#[cw_serde]
+pub struct TokenIdSequence {
+    pub prefix: String,
+    /// Number of the next token id to be assigned.
+    pub next: u64,
+}
+
+#[cw_serde]
+pub enum ProvenanceAction {
+    Mint,
+    Transfer,
+    Send,
+    Burn,
+}
+
+/// An ownership change of an NFT.
+#[cw_serde]
+pub struct ProvenanceEntry {
+    /// Position in the NFT's history, starting at 0.
+    pub index: u64,
+    /// None on mint.
+    pub previous_owner: Option<Addr>,
+    /// None on burn.
+    pub new_owner: Option<Addr>,
+    pub action: ProvenanceAction,
+    pub block_height: u64,
+    pub time: Timestamp,
+}
+
 #[cw_serde]
 pub struct Approval {
     /// Account that can transfer/send the token
@@ -207,6 +322,31 @@ pub struct RoyaltyInfo {
 impl Cw721State for RoyaltyInfo {}
 impl Cw721CustomMsg for RoyaltyInfo {}
 
+/// Royalty split between multiple recipients, each with an individual share of the sale price.
+/// The combined share is capped by `MAX_ROYALTY_SHARE_PCT`.
+#[cw_serde]
+pub struct RoyaltySplit {
+    pub recipients: Vec<RoyaltyInfo>,
+}
+
+impl RoyaltySplit {
+    pub fn total_share(&self) -> Decimal {
+        self.recipients
+            .iter()
+            .fold(Decimal::zero(), |total, recipient| total + recipient.share)
+    }
+}
+
+impl From<RoyaltyInfo> for RoyaltySplit {
+    fn from(royalty_info: RoyaltyInfo) -> Self {
+        RoyaltySplit {
+            recipients: vec![royalty_info],
+        }
+    }
+}
+
+impl Cw721State for RoyaltySplit {}
+
 impl ToAttributesState for Empty {
     fn to_attributes_state(&self) -> Result<Vec<Attribute>, Cw721ContractError> {
         Ok(vec![])
@@ -1,19 +1,25 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Coin, CosmosMsg, DepsMut, Empty, Response, StdError,
-    Timestamp, WasmMsg,
+    from_json, to_json_binary, Addr, Coin, CosmosMsg, Decimal, DepsMut, Empty, Env, Response,
+    StdError, Timestamp, Uint128, WasmMsg,
 };
 
 use crate::error::Cw721ContractError;
 use crate::extension::Cw721OnchainExtensions;
 use crate::msg::{
     ApprovalResponse, CollectionExtensionMsg, NftExtensionMsg, NftInfoResponse, OperatorResponse,
-    OperatorsResponse, OwnerOfResponse, RoyaltyInfoResponse,
+    OperatorsResponse, OwnerOfResponse, RoyaltiesInfoResponse, RoyaltyInfoResponse,
+    RoyaltySplitInfoResponse, RoyaltySplitMsg, TradingStatusResponse,
+};
+use crate::msg::{
+    CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg, MintMsg, TransferMsg,
 };
-use crate::msg::{CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg};
 use crate::receiver::Cw721ReceiveMsg;
-use crate::state::{NftExtension, Trait, CREATOR, MINTER};
+use crate::state::{
+    Cw721Config, NftExtension, ProvenanceAction, ProvenanceEntry, TokenIdSequence, Trait,
+    TransferPolicy, CREATOR, MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
+};
 use crate::{
     traits::{Cw721Execute, Cw721Query},
     Approval, DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtension,
@@ -36,6 +42,7 @@ fn setup_contract(deps: DepsMut<'_>) -> Cw721OnchainExtensions<'static> {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: None,
+        max_supply: None,
     };
     let info_creator = mock_info(CREATOR_ADDR, &[]);
     let res = contract
@@ -64,6 +71,7 @@ fn test_instantiate() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_supply: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -139,6 +147,7 @@ fn test_instantiate_with_collection_info_and_extension() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_supply: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -222,6 +231,7 @@ fn test_instantiate_with_minimal_collection_info_and_extension() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_supply: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -273,7 +283,7 @@ fn test_mint() {
 
     let token_id1 = "petrify".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: String::from("medusa"),
         token_uri: Some("invalid_uri".to_string()),
         extension: None,
@@ -294,7 +304,7 @@ fn test_mint() {
     let info_random = mock_info("random", &[]);
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
@@ -345,7 +355,7 @@ fn test_mint() {
 
     // Cannot mint same token_id again
     let mint_msg2 = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: String::from("hercules"),
         token_uri: None,
         extension: None,
@@ -367,7 +377,7 @@ fn test_mint() {
     // minter mints another one
     let token_id2 = "id2".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: String::from("medusa"),
         token_uri: Some("".to_string()), // empty token uri
         extension: None,
@@ -419,7 +429,7 @@ fn test_mint() {
     // minter mints another one
     let token_id3 = "id3".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id3.clone(),
+        token_id: Some(token_id3.clone()),
         owner: String::from("medusa"),
         token_uri: None, // empty token uri
         extension: None,
@@ -476,7 +486,7 @@ fn test_update_nft_info() {
 
     let token_id = "1".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("owner"),
         token_uri: Some("ipfs://foo.bar".to_string()),
         extension: None,
@@ -629,7 +639,7 @@ fn test_mint_with_metadata() {
             youtube_url: Some("file://youtube_url".to_string()),
         };
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri),
             extension: Some(valid_extension_msg.clone()),
@@ -656,7 +666,7 @@ fn test_mint_with_metadata() {
             DefaultOptionalCollectionExtensionMsg,
             Empty,
         >::Mint {
-            token_id: "2".to_string(),
+            token_id: Some("2".to_string()),
             owner: String::from("medusa"),
             token_uri: None,
             extension: Some(NftExtensionMsg {
@@ -698,7 +708,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.description = Some("".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: None,
             extension: Some(metadata),
@@ -711,7 +721,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.name = Some("".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: None,
             extension: Some(metadata),
@@ -724,7 +734,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.background_color = Some("".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: None,
             extension: Some(metadata),
@@ -763,7 +773,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.image = Some("invalid".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -779,7 +789,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.external_url = Some("invalid".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -795,7 +805,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.animation_url = Some("invalid".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -811,7 +821,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.youtube_url = Some("invalid".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -828,7 +838,7 @@ fn test_mint_with_metadata() {
         let mut metadata = valid_extension_msg.clone();
         metadata.image_data = Some("".to_string());
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -844,7 +854,7 @@ fn test_mint_with_metadata() {
             display_type: Some("display_type".to_string()),
         }]);
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -861,7 +871,7 @@ fn test_mint_with_metadata() {
             display_type: Some("display_type".to_string()),
         }]);
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: String::from("medusa"),
             token_uri: Some(token_uri.clone()),
             extension: Some(metadata),
@@ -878,7 +888,7 @@ fn test_mint_with_metadata() {
             display_type: Some("".to_string()),
         }]);
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id,
+            token_id: Some(token_id),
             owner: String::from("medusa"),
             token_uri: Some(token_uri),
             extension: Some(metadata),
@@ -900,6 +910,7 @@ fn test_update_collection_info() {
             name: Some("new name".to_string()),
             symbol: Some("NEW".to_string()),
             extension: None,
+            max_supply: None,
         },
     };
 
@@ -979,6 +990,7 @@ fn test_update_collection_info() {
             name: Some("new name".to_string()),
             symbol: Some("NEW".to_string()),
             extension: None,
+            max_supply: None,
         },
     };
 
@@ -1013,7 +1025,7 @@ fn test_update_minter() {
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id,
+        token_id: Some(token_id),
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
@@ -1086,7 +1098,7 @@ fn test_update_minter() {
     assert_eq!(minter_ownership.owner, Some(new_minter_info.sender.clone()));
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: "randoms_token".to_string(),
+        token_id: Some("randoms_token".to_string()),
         owner: String::from("medusa"),
         token_uri: Some(token_uri),
         extension: None,
@@ -1118,7 +1130,7 @@ fn test_burn() {
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: MINTER_ADDR.to_string(),
         token_uri: Some(token_uri),
         extension: None,
@@ -1171,7 +1183,7 @@ fn test_transfer_nft() {
     let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("venus"),
         token_uri: Some(token_uri),
         extension: None,
@@ -1225,7 +1237,7 @@ fn test_send_nft() {
     let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("venus"),
         token_uri: Some(token_uri),
         extension: None,
@@ -1281,6 +1293,343 @@ fn test_send_nft() {
     );
 }
 
+#[test]
+fn test_start_trading_time() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let start_trading_time = env.block.time.plus_seconds(100);
+
+    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: Some(CollectionExtensionMsg {
+            description: None,
+            image: None,
+            explicit_content: None,
+            external_link: None,
+            start_trading_time: Some(start_trading_time),
+            royalty_info: None,
+        }),
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        max_supply: None,
+    };
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .instantiate(deps.as_mut(), &env, &info_creator, msg)
+        .unwrap();
+
+    // trading is closed
+    let trading_status = contract.query_trading_status(deps.as_ref(), &env).unwrap();
+    assert_eq!(
+        trading_status,
+        TradingStatusResponse {
+            start_trading_time: Some(start_trading_time),
+            is_open: false,
+        }
+    );
+
+    // minter can mint and distribute before trading starts
+    let token_id = "melt".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: Some(token_id.clone()),
+        owner: String::from(MINTER_ADDR),
+        token_uri: None,
+        extension: None,
+    };
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("venus"),
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &minter, transfer_msg)
+        .unwrap();
+
+    // owner cannot transfer, send, approve or approve all before trading starts
+    let owner = mock_info("venus", &[]);
+    let expected_err = Cw721ContractError::TradingNotStarted { start_trading_time };
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &owner, transfer_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, expected_err);
+
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: String::from("another_contract"),
+        token_id: token_id.clone(),
+        msg: to_json_binary("You now have the melting power").unwrap(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &owner, send_msg)
+        .unwrap_err();
+    assert_eq!(err, expected_err);
+
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &owner, approve_msg)
+        .unwrap_err();
+    assert_eq!(err, expected_err);
+
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("random"),
+        expires: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &owner, approve_all_msg)
+        .unwrap_err();
+    assert_eq!(err, expected_err);
+
+    // owner can transfer once trading has started
+    let mut env = mock_env();
+    env.block.time = start_trading_time;
+    let trading_status = contract.query_trading_status(deps.as_ref(), &env).unwrap();
+    assert!(trading_status.is_open);
+    contract
+        .execute(deps.as_mut(), &env, &owner, transfer_msg)
+        .unwrap();
+    let owner_of = contract
+        .query_owner_of(deps.as_ref(), &env, token_id.clone(), false)
+        .unwrap();
+    assert_eq!(owner_of.owner, "random");
+
+    // creator can revoke NFTs before trading starts, in case of a revocable transfer policy
+    let env = mock_env();
+    let revoke_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from(CREATOR_ADDR),
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, revoke_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTransferPolicy {
+                transfer_policy: TransferPolicy::Revocable,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, revoke_msg)
+        .unwrap();
+    let owner_of = contract
+        .query_owner_of(deps.as_ref(), &env, token_id, false)
+        .unwrap();
+    assert_eq!(owner_of.owner, CREATOR_ADDR);
+}
+
+#[test]
+fn test_batch_mint_transfer_send_burn() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    // empty batch is rejected
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::BatchMint { mints: vec![] },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::EmptyBatch {});
+
+    let mint = |token_id: &str| MintMsg {
+        token_id: Some(token_id.to_string()),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+    };
+
+    // only minter can batch mint
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &random,
+            Cw721ExecuteMsg::BatchMint {
+                mints: vec![mint("1")],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::BatchMint {
+                mints: vec![mint("1"), mint("2"), mint("3"), mint("4")],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "batch_mint")
+            .add_attribute("minter", MINTER_ADDR)
+            .add_attribute("owner", "venus")
+            .add_attribute("token_id", "1")
+            .add_attribute("owner", "venus")
+            .add_attribute("token_id", "2")
+            .add_attribute("owner", "venus")
+            .add_attribute("token_id", "3")
+            .add_attribute("owner", "venus")
+            .add_attribute("token_id", "4")
+            .set_data(to_json_binary(&vec!["1", "2", "3", "4"]).unwrap())
+    );
+    assert_eq!(
+        4,
+        contract
+            .query_num_tokens(deps.as_ref().storage)
+            .unwrap()
+            .count
+    );
+
+    // already minted token ids are rejected
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::BatchMint {
+                mints: vec![mint("1")],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Claimed {});
+
+    // batch transfer
+    let owner = mock_info("venus", &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &owner,
+            Cw721ExecuteMsg::BatchTransfer {
+                transfers: vec![
+                    TransferMsg {
+                        recipient: String::from("mars"),
+                        token_id: String::from("1"),
+                    },
+                    TransferMsg {
+                        recipient: String::from("jupiter"),
+                        token_id: String::from("2"),
+                    },
+                ],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "batch_transfer_nft")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "mars")
+            .add_attribute("token_id", "1")
+            .add_attribute("recipient", "jupiter")
+            .add_attribute("token_id", "2")
+    );
+    let owner_of = contract
+        .query_owner_of(deps.as_ref(), &env, "2".to_string(), false)
+        .unwrap();
+    assert_eq!(owner_of.owner, "jupiter");
+
+    // batch send: one receive callback per token
+    let msg = to_json_binary("You now have the melting power").unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &owner,
+            Cw721ExecuteMsg::BatchSend {
+                contract: String::from("another_contract"),
+                token_ids: vec![String::from("3"), String::from("4")],
+                msg: msg.clone(),
+            },
+        )
+        .unwrap();
+    let expected = |token_id: &str| -> CosmosMsg {
+        Cw721ReceiveMsg {
+            sender: String::from("venus"),
+            token_id: token_id.to_string(),
+            msg: msg.clone(),
+        }
+        .into_cosmos_msg("another_contract")
+        .unwrap()
+    };
+    assert_eq!(
+        res,
+        Response::new()
+            .add_message(expected("3"))
+            .add_message(expected("4"))
+            .add_attribute("action", "batch_send_nft")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "another_contract")
+            .add_attribute("token_id", "3")
+            .add_attribute("token_id", "4")
+    );
+
+    // batch burn fails if sender has no access to any of the tokens
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            Cw721ExecuteMsg::BatchBurn {
+                token_ids: vec![String::from("2"), String::from("1")],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("another_contract", &[]),
+            Cw721ExecuteMsg::BatchBurn {
+                token_ids: vec![String::from("3"), String::from("4")],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "batch_burn")
+            .add_attribute("sender", "another_contract")
+            .add_attribute("token_id", "3")
+            .add_attribute("token_id", "4")
+    );
+    assert_eq!(
+        2,
+        contract
+            .query_num_tokens(deps.as_ref().storage)
+            .unwrap()
+            .count
+    );
+}
+
 #[test]
 fn test_approve_revoke() {
     let mut deps = mock_dependencies();
@@ -1291,7 +1640,7 @@ fn test_approve_revoke() {
     let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri),
         extension: None,
@@ -1438,7 +1787,7 @@ fn test_approve_all_revoke_all() {
     let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
 
     let mint_msg1 = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri1),
         extension: None,
@@ -1450,7 +1799,7 @@ fn test_approve_all_revoke_all() {
         .unwrap();
 
     let mint_msg2 = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri2),
         extension: None,
@@ -1822,7 +2171,7 @@ fn query_tokens_by_owner() {
     let token_id3 = "sing".to_string();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
@@ -1832,7 +2181,7 @@ fn query_tokens_by_owner() {
         .unwrap();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: ceres.clone(),
         token_uri: None,
         extension: None,
@@ -1842,7 +2191,7 @@ fn query_tokens_by_owner() {
         .unwrap();
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id3.clone(),
+        token_id: Some(token_id3.clone()),
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
@@ -1897,3 +2246,1110 @@ fn query_tokens_by_owner() {
         .unwrap();
     assert_eq!(&by_demeter[1..], &tokens.tokens[..]);
 }
+
+#[test]
+fn test_royalty_info() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+
+    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: Some(CollectionExtensionMsg {
+            description: None,
+            image: None,
+            explicit_content: None,
+            external_link: None,
+            start_trading_time: None,
+            royalty_info: Some(RoyaltyInfoResponse {
+                payment_address: "collection_payee".into(),
+                share: Decimal::percent(5),
+            }),
+        }),
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        max_supply: None,
+    };
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .instantiate(deps.as_mut(), &env, &info_creator, msg)
+        .unwrap();
+
+    let token_id = "grow".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: Some(token_id.clone()),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+    };
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+
+    // falls back to collection royalty info
+    let royalty_info = contract
+        .query_royalty_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalty_info,
+        RoyaltiesInfoResponse {
+            address: "collection_payee".to_string(),
+            royalty_amount: Uint128::new(50),
+        }
+    );
+    let check_royalties = contract.query_check_royalties(deps.as_ref()).unwrap();
+    assert!(check_royalties.royalty_payments);
+
+    // unknown token
+    contract
+        .query_royalty_info(deps.as_ref(), "unknown".to_string(), Uint128::new(1000))
+        .unwrap_err();
+
+    // only creator can set token royalty
+    let set_royalty_msg = Cw721ExecuteMsg::SetTokenRoyalty {
+        token_id: token_id.clone(),
+        royalty_split: Some(RoyaltySplitMsg {
+            recipients: vec![RoyaltyInfoResponse {
+                payment_address: "token_payee".into(),
+                share: Decimal::percent(8),
+            }],
+        }),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, set_royalty_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // share is validated
+    let invalid_royalty_msg = Cw721ExecuteMsg::SetTokenRoyalty {
+        token_id: token_id.clone(),
+        royalty_split: Some(RoyaltySplitMsg {
+            recipients: vec![RoyaltyInfoResponse {
+                payment_address: "token_payee".into(),
+                share: Decimal::percent(11),
+            }],
+        }),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, invalid_royalty_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share cannot be greater than {MAX_ROYALTY_SHARE_PCT}%"
+        ))
+    );
+
+    // max share delta applies to the collection royalty in effect for the NFT
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, set_royalty_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
+        ))
+    );
+
+    // token royalty overrides collection royalty
+    let set_royalty_msg = |share: u64| Cw721ExecuteMsg::SetTokenRoyalty {
+        token_id: token_id.clone(),
+        royalty_split: Some(RoyaltySplitMsg {
+            recipients: vec![RoyaltyInfoResponse {
+                payment_address: "token_payee".into(),
+                share: Decimal::percent(share),
+            }],
+        }),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, set_royalty_msg(7))
+        .unwrap();
+    let royalty_info = contract
+        .query_royalty_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalty_info,
+        RoyaltiesInfoResponse {
+            address: "token_payee".to_string(),
+            royalty_amount: Uint128::new(70),
+        }
+    );
+
+    // removing the override falls back to collection royalty
+    let remove_royalty_msg = Cw721ExecuteMsg::SetTokenRoyalty {
+        token_id: token_id.clone(),
+        royalty_split: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            remove_royalty_msg.clone(),
+        )
+        .unwrap();
+    let royalty_info = contract
+        .query_royalty_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(royalty_info.address, "collection_payee".to_string());
+
+    // removing the override is validated against the collection royalty, too
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, set_royalty_msg(1))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, remove_royalty_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
+        ))
+    );
+}
+
+#[test]
+fn test_royalty_split() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+
+    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: Some(CollectionExtensionMsg {
+            description: None,
+            image: None,
+            explicit_content: None,
+            external_link: None,
+            start_trading_time: None,
+            royalty_info: Some(RoyaltyInfoResponse {
+                payment_address: "collection_payee".into(),
+                share: Decimal::percent(5),
+            }),
+        }),
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        max_supply: None,
+    };
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .instantiate(deps.as_mut(), &env, &info_creator, msg)
+        .unwrap();
+    let token_id = "grow".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: Some(token_id.clone()),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+
+    let split = |shares: &[(&str, u64)]| RoyaltySplitMsg {
+        recipients: shares
+            .iter()
+            .map(|(payment_address, share)| RoyaltyInfoResponse {
+                payment_address: payment_address.to_string(),
+                share: Decimal::percent(*share),
+            })
+            .collect(),
+    };
+
+    // no recipients
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: Some(split(&[])),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(
+            "Royalty split must have at least one recipient".to_string()
+        )
+    );
+
+    // duplicate recipients
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: Some(split(&[("artist", 2), ("artist", 2)])),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties("Duplicate recipient artist".to_string())
+    );
+
+    // max share delta applies to total share, compared to collection royalty info
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: Some(split(&[("artist", 4), ("curator", 4)])),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
+        ))
+    );
+
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: Some(split(&[("artist", 3), ("curator", 4)])),
+            },
+        )
+        .unwrap();
+    let royalties = contract
+        .query_royalty_split_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalties,
+        RoyaltySplitInfoResponse {
+            royalties: vec![
+                RoyaltiesInfoResponse {
+                    address: "artist".to_string(),
+                    royalty_amount: Uint128::new(30),
+                },
+                RoyaltiesInfoResponse {
+                    address: "curator".to_string(),
+                    royalty_amount: Uint128::new(40),
+                },
+            ],
+        }
+    );
+    // CW2981 returns the total amount to the first recipient
+    let royalty_info = contract
+        .query_royalty_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalty_info,
+        RoyaltiesInfoResponse {
+            address: "artist".to_string(),
+            royalty_amount: Uint128::new(70),
+        }
+    );
+
+    // max share applies to total share
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTokenRoyalty {
+                token_id: token_id.clone(),
+                royalty_split: Some(split(&[("artist", 6), ("curator", 5)])),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share cannot be greater than {MAX_ROYALTY_SHARE_PCT}%"
+        ))
+    );
+
+    // CW2981 returns the total amount of a token split to its first recipient, too
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTokenRoyalty {
+                token_id: token_id.clone(),
+                royalty_split: Some(split(&[("curator", 5), ("artist", 3)])),
+            },
+        )
+        .unwrap();
+    let royalty_info = contract
+        .query_royalty_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalty_info,
+        RoyaltiesInfoResponse {
+            address: "curator".to_string(),
+            royalty_amount: Uint128::new(80),
+        }
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTokenRoyalty {
+                token_id: token_id.clone(),
+                royalty_split: None,
+            },
+        )
+        .unwrap();
+
+    // removing collection split falls back to collection royalty info
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: None,
+            },
+        )
+        .unwrap();
+    let royalties = contract
+        .query_royalty_split_info(deps.as_ref(), token_id.clone(), Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalties.royalties,
+        vec![RoyaltiesInfoResponse {
+            address: "collection_payee".to_string(),
+            royalty_amount: Uint128::new(50),
+        }]
+    );
+
+    // removing collection split is validated against collection royalty info
+    let set_split_msg = Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+        royalty_split: Some(split(&[("artist", 1), ("curator", 1)])),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, set_split_msg)
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
+        ))
+    );
+
+    // updating collection royalty info is validated against collection split
+    let update_royalty_info_msg = |share: u64| Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: None,
+                image: None,
+                explicit_content: None,
+                external_link: None,
+                start_trading_time: None,
+                royalty_info: Some(RoyaltyInfoResponse {
+                    payment_address: "collection_payee".into(),
+                    share: Decimal::percent(share),
+                }),
+            }),
+            max_supply: None,
+        },
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            update_royalty_info_msg(5),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyalties(format!(
+            "Share increase cannot be greater than {MAX_ROYALTY_SHARE_DELTA_PCT}%"
+        ))
+    );
+
+    // updated collection royalty info replaces collection split
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            update_royalty_info_msg(4),
+        )
+        .unwrap();
+    let royalties = contract
+        .query_royalty_split_info(deps.as_ref(), token_id, Uint128::new(1000))
+        .unwrap();
+    assert_eq!(
+        royalties.royalties,
+        vec![RoyaltiesInfoResponse {
+            address: "collection_payee".to_string(),
+            royalty_amount: Uint128::new(40),
+        }]
+    );
+}
+
+#[test]
+fn test_collection_royalty_split_requires_collection_extension() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit {
+                royalty_split: Some(RoyaltySplitMsg {
+                    recipients: vec![RoyaltyInfoResponse {
+                        payment_address: "artist".into(),
+                        share: Decimal::percent(5),
+                    }],
+                }),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoCollectionExtension {});
+}
+
+#[test]
+fn test_token_history() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let mint = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: Some(token_id.to_string()),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+    };
+
+    // tracking is disabled by default
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint("untracked"))
+        .unwrap();
+    let res = contract
+        .query_token_history(deps.as_ref(), "untracked".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.history, vec![]);
+
+    // only creator can enable tracking
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            Cw721ExecuteMsg::SetProvenanceTracking { enabled: true },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::SetProvenanceTracking { enabled: true },
+        )
+        .unwrap();
+
+    let token_id = "melt".to_string();
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(&token_id))
+        .unwrap();
+    env.block.height += 1;
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("random"),
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+    env.block.height += 1;
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("random", &[]),
+            Cw721ExecuteMsg::SendNft {
+                contract: String::from("another_contract"),
+                token_id: token_id.clone(),
+                msg: to_json_binary("You now have the melting power").unwrap(),
+            },
+        )
+        .unwrap();
+    env.block.height += 1;
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("another_contract", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+
+    // history is kept after burn
+    let history = contract
+        .query_token_history(deps.as_ref(), token_id.clone(), None, None)
+        .unwrap()
+        .history;
+    let expected = [
+        (None, Some("venus"), ProvenanceAction::Mint),
+        (Some("venus"), Some("random"), ProvenanceAction::Transfer),
+        (
+            Some("random"),
+            Some("another_contract"),
+            ProvenanceAction::Send,
+        ),
+        (Some("another_contract"), None, ProvenanceAction::Burn),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(
+        |(index, (previous_owner, new_owner, action))| ProvenanceEntry {
+            index: index as u64,
+            previous_owner: previous_owner.map(Addr::unchecked),
+            new_owner: new_owner.map(Addr::unchecked),
+            action,
+            block_height: mock_env().block.height + index as u64,
+            time: mock_env().block.time,
+        },
+    )
+    .collect::<Vec<_>>();
+    assert_eq!(history, expected);
+
+    // paginated
+    let page = contract
+        .query_token_history(deps.as_ref(), token_id.clone(), None, Some(2))
+        .unwrap()
+        .history;
+    assert_eq!(page, expected[..2]);
+    let page = contract
+        .query_token_history(deps.as_ref(), token_id, Some(1), Some(2))
+        .unwrap()
+        .history;
+    assert_eq!(page, expected[2..]);
+}
+
+#[test]
+fn test_transfer_policy() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    for token_id in ["grow", "melt"] {
+        contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &mock_info(MINTER_ADDR, &[]),
+                Cw721ExecuteMsg::Mint {
+                    token_id: Some(token_id.to_string()),
+                    owner: String::from("venus"),
+                    token_uri: None,
+                    extension: None,
+                },
+            )
+            .unwrap();
+    }
+    let transfer = |recipient: &str| Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: "grow".to_string(),
+    };
+    let policy = contract.query_transfer_policy(deps.as_ref()).unwrap();
+    assert_eq!(policy, TransferPolicy::Transferable);
+
+    // only creator can set policy
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::SetTransferPolicy {
+                transfer_policy: TransferPolicy::Soulbound,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // soulbound: nobody can transfer, but owner can burn
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTransferPolicy {
+                transfer_policy: TransferPolicy::Soulbound,
+            },
+        )
+        .unwrap();
+    let policy = contract.query_transfer_policy(deps.as_ref()).unwrap();
+    assert_eq!(policy, TransferPolicy::Soulbound);
+    for sender in ["venus", MINTER_ADDR, CREATOR_ADDR] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &mock_info(sender, &[]),
+                transfer("random"),
+            )
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::NonTransferable {});
+    }
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "melt".to_string(),
+            },
+        )
+        .unwrap();
+
+    // revocable: only minter and creator can transfer
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTransferPolicy {
+                transfer_policy: TransferPolicy::Revocable,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            transfer("random"),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NonTransferable {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            transfer("random"),
+        )
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, transfer("venus"))
+        .unwrap();
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "grow".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "venus");
+}
+
+#[test]
+fn test_token_id_sequence() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    let mint = |token_id: Option<&str>| Cw721ExecuteMsg::Mint {
+        token_id: token_id.map(str::to_string),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+    };
+
+    // token id is required by default
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(None))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MissingTokenId {});
+    let sequence = contract.query_token_id_sequence(deps.as_ref()).unwrap();
+    assert_eq!(sequence, None);
+
+    // only creator can set sequence
+    let token_id_sequence = TokenIdSequence {
+        prefix: "nft-".to_string(),
+        next: 1,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            Cw721ExecuteMsg::SetTokenIdSequence {
+                token_id_sequence: Some(token_id_sequence.clone()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTokenIdSequence {
+                token_id_sequence: Some(token_id_sequence),
+            },
+        )
+        .unwrap();
+
+    // assigned token id is returned in data and attributes
+    let res = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(None))
+        .unwrap();
+    assert_eq!(res.data, Some(to_json_binary("nft-1").unwrap()));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "token_id" && attr.value == "nft-1"));
+
+    // provided token ids don't advance the sequence, but already minted ids are skipped
+    let res = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(Some("nft-3")))
+        .unwrap();
+    assert_eq!(res.data, Some(to_json_binary("nft-3").unwrap()));
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            Cw721ExecuteMsg::BatchMint {
+                mints: vec![
+                    MintMsg {
+                        token_id: None,
+                        owner: String::from("venus"),
+                        token_uri: None,
+                        extension: None,
+                    },
+                    MintMsg {
+                        token_id: None,
+                        owner: String::from("venus"),
+                        token_uri: None,
+                        extension: None,
+                    },
+                ],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.data,
+        Some(to_json_binary(&vec!["nft-2", "nft-4"]).unwrap())
+    );
+    let sequence = contract.query_token_id_sequence(deps.as_ref()).unwrap();
+    assert_eq!(
+        sequence,
+        Some(TokenIdSequence {
+            prefix: "nft-".to_string(),
+            next: 5,
+        })
+    );
+    assert_eq!(
+        4,
+        contract
+            .query_num_tokens(deps.as_ref().storage)
+            .unwrap()
+            .count
+    );
+
+    // sequence cannot be rewound
+    let set_sequence = |prefix: &str, next: u64| Cw721ExecuteMsg::SetTokenIdSequence {
+        token_id_sequence: Some(TokenIdSequence {
+            prefix: prefix.to_string(),
+            next,
+        }),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, set_sequence("nft-", 1))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenIdSequenceRewound { next: 5 });
+
+    // burned ids with ownership history are not assigned again
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetProvenanceTracking { enabled: true },
+        )
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(Some("other-1")))
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "other-1".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            set_sequence("other-", 1),
+        )
+        .unwrap();
+    let res = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(None))
+        .unwrap();
+    assert_eq!(res.data, Some(to_json_binary("other-2").unwrap()));
+
+    // disabling sequence requires token id again
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            Cw721ExecuteMsg::SetTokenIdSequence {
+                token_id_sequence: None,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint(None))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MissingTokenId {});
+}
+
+/// Mints an NFT owned by venus, using the token id sequence. Returns the assigned token id.
+fn mint_from_sequence(deps: DepsMut, contract: &Cw721OnchainExtensions, env: &Env) -> String {
+    let res = contract
+        .execute(
+            deps,
+            env,
+            &mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: None,
+                owner: String::from("venus"),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    from_json(res.data.unwrap()).unwrap()
+}
+
+#[test]
+fn test_token_id_sequence_not_rewound_after_reset() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    let set_sequence = |token_id_sequence: Option<TokenIdSequence>| {
+        Cw721ExecuteMsg::SetTokenIdSequence { token_id_sequence }
+    };
+    let sequence = |next: u64| {
+        Some(TokenIdSequence {
+            prefix: "nft-".to_string(),
+            next,
+        })
+    };
+
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            set_sequence(sequence(1)),
+        )
+        .unwrap();
+    let token_id = mint_from_sequence(deps.as_mut(), &contract, &env);
+    assert_eq!(token_id, "nft-1");
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn { token_id },
+        )
+        .unwrap();
+
+    // disabling and re-setting the sequence doesn't allow rewinding it
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, set_sequence(None))
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            set_sequence(sequence(1)),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenIdSequenceRewound { next: 2 });
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            set_sequence(sequence(2)),
+        )
+        .unwrap();
+    let token_id = mint_from_sequence(deps.as_mut(), &contract, &env);
+    assert_eq!(token_id, "nft-2");
+}
+
+#[test]
+fn test_token_id_sequence_not_rewound_after_prefix_switch() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    let set_sequence = |prefix: &str, next: u64| Cw721ExecuteMsg::SetTokenIdSequence {
+        token_id_sequence: Some(TokenIdSequence {
+            prefix: prefix.to_string(),
+            next,
+        }),
+    };
+
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, set_sequence("nft-", 1))
+        .unwrap();
+    let token_id = mint_from_sequence(deps.as_mut(), &contract, &env);
+    assert_eq!(token_id, "nft-1");
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn { token_id },
+        )
+        .unwrap();
+
+    // switching to another prefix and back doesn't allow rewinding the sequence
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_creator,
+            set_sequence("other-", 1),
+        )
+        .unwrap();
+    let token_id = mint_from_sequence(deps.as_mut(), &contract, &env);
+    assert_eq!(token_id, "other-1");
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, set_sequence("nft-", 1))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenIdSequenceRewound { next: 2 });
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, set_sequence("nft-", 2))
+        .unwrap();
+    let token_id = mint_from_sequence(deps.as_mut(), &contract, &env);
+    assert_eq!(token_id, "nft-2");
+}
+
+#[test]
+fn test_max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let info_creator = mock_info(CREATOR_ADDR, &[]);
+    let config = Cw721Config::<Option<Empty>>::default();
+    let mint = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: Some(token_id.to_string()),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+    };
+    let update_max_supply = |max_supply: u64| Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: None,
+            max_supply: Some(max_supply),
+        },
+    };
+
+    // unlimited by default
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint("1"))
+        .unwrap();
+    assert_eq!(config.max_supply.may_load(&deps.storage).unwrap(), None);
+    assert_eq!(config.total_minted(&deps.storage).unwrap(), 1);
+
+    // only creator can set max supply
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_minter, update_max_supply(2))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // max supply must not be less than minted NFTs
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, update_max_supply(0))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::MaxSupplyBelowMinted { total_minted: 1 }
+    );
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, update_max_supply(3))
+        .unwrap();
+
+    // max supply can only be decreased
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_creator, update_max_supply(4))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::MaxSupplyIncreased { max_supply: 3 }
+    );
+    contract
+        .execute(deps.as_mut(), &env, &info_creator, update_max_supply(2))
+        .unwrap();
+    assert_eq!(config.max_supply.load(&deps.storage).unwrap(), 2);
+
+    // burned NFTs count towards max supply
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint("2"))
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint("3"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MaxSupplyReached { max_supply: 2 });
+    assert_eq!(
+        1,
+        contract
+            .query_num_tokens(deps.as_ref().storage)
+            .unwrap()
+            .count
+    );
+    assert_eq!(config.total_minted(&deps.storage).unwrap(), 2);
+}
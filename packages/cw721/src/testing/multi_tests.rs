@@ -1,13 +1,14 @@
 use crate::{
     error::Cw721ContractError,
     extension::Cw721OnchainExtensions,
+    helpers::EmptyCw721Helper,
     msg::{
         CollectionExtensionMsg, ConfigResponse, Cw721ExecuteMsg, Cw721InstantiateMsg,
         Cw721MigrateMsg, Cw721QueryMsg, MinterResponse, NumTokensResponse, OwnerOfResponse,
         RoyaltyInfoResponse,
     },
     state::{CollectionInfo, NftExtension, Trait},
-    traits::{Cw721Execute, Cw721Query},
+    traits::{Cw721Calls, Cw721Execute, Cw721Query},
     DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
     DefaultOptionalNftExtension, DefaultOptionalNftExtensionMsg, NftExtensionMsg,
 };
@@ -16,7 +17,7 @@ use bech32::{decode, encode, Hrp};
 use cosmwasm_std::{
     instantiate2_address, to_json_binary, Addr, Api, Binary, CanonicalAddr, Decimal, Deps, DepsMut,
     Empty, Env, GovMsg, MemoryStorage, MessageInfo, QuerierWrapper, RecoverPubkeyError, Response,
-    StdError, StdResult, Storage, Timestamp, VerificationError, WasmMsg,
+    StdError, StdResult, Storage, Timestamp, Uint128, VerificationError, WasmMsg,
 };
 use cw721_016::NftInfoResponse;
 use cw_multi_test::{
@@ -356,7 +357,7 @@ fn mint_transfer_and_burn(app: &mut MockApp, cw721: Addr, sender: Addr, token_id
         sender.clone(),
         cw721.clone(),
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-            token_id: token_id.clone(),
+            token_id: Some(token_id.clone()),
             owner: sender.to_string(),
             token_uri: None,
             extension: Empty::default(),
@@ -412,6 +413,7 @@ fn test_operator() {
                 creator: Some(creator.to_string()),
                 collection_info_extension: None,
                 withdraw_address: None,
+                max_supply: None,
             },
             &[],
             "cw721-base",
@@ -424,7 +426,7 @@ fn test_operator() {
         minter,
         cw721.clone(),
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-            token_id: "1".to_string(),
+            token_id: Some("1".to_string()),
             owner: nft_owner.to_string(),
             token_uri: Some("".to_string()), // empty uri, response contains attribute with value "empty"
             extension: Empty::default(),
@@ -646,7 +648,7 @@ fn test_migration_legacy_to_latest() {
                 other.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -785,7 +787,7 @@ fn test_migration_legacy_to_latest() {
                 legacy_creator_and_minter.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -916,7 +918,7 @@ fn test_migration_legacy_to_latest() {
                 other.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -1055,7 +1057,7 @@ fn test_migration_legacy_to_latest() {
                 legacy_creator_and_minter.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -1186,7 +1188,7 @@ fn test_migration_legacy_to_latest() {
                 other.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -1325,7 +1327,7 @@ fn test_migration_legacy_to_latest() {
                 legacy_creator_and_minter.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -1456,7 +1458,7 @@ fn test_migration_legacy_to_latest() {
                 other.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -1595,7 +1597,7 @@ fn test_migration_legacy_to_latest() {
                 legacy_creator_and_minter.clone(),
                 cw721.clone(),
                 &Cw721ExecuteMsg::<Empty, Empty, Empty>::Mint {
-                    token_id: "1".to_string(),
+                    token_id: Some("1".to_string()),
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
@@ -1686,6 +1688,7 @@ fn test_instantiate() {
         minter: Some(minter.to_string()),
         creator: Some(creator.to_string()),
         withdraw_address: Some(withdraw_addr.to_string()),
+        max_supply: None,
         collection_info_extension: Some(CollectionExtensionMsg {
             description: Some("description".to_string()),
             image: Some("ipfs://ark.pass".to_string()),
@@ -1872,6 +1875,7 @@ fn test_update_nft_metadata() {
                 creator: None, // in case of none, sender is creator
                 collection_info_extension: None,
                 withdraw_address: None,
+                max_supply: None,
             },
             &[],
             "cw721-base",
@@ -1903,7 +1907,7 @@ fn test_update_nft_metadata() {
             DefaultOptionalCollectionExtensionMsg,
             Empty,
         >::Mint {
-            token_id: "1".to_string(),
+            token_id: Some("1".to_string()),
             owner: nft_owner.to_string(),
             token_uri: Some("ipfs://foo.bar/metadata.json".to_string()),
             extension: Some(nft_metadata_msg.clone()),
@@ -2537,6 +2541,7 @@ fn test_queries() {
                 creator: None, // in case of none, sender is creator
                 collection_info_extension: None,
                 withdraw_address: Some(withdraw_addr.to_string()),
+                max_supply: None,
             },
             &[],
             "cw721-base",
@@ -2568,7 +2573,7 @@ fn test_queries() {
             DefaultOptionalCollectionExtensionMsg,
             Empty,
         >::Mint {
-            token_id: "1".to_string(),
+            token_id: Some("1".to_string()),
             owner: nft_owner.to_string(),
             token_uri: Some("ipfs://foo.bar/metadata.json".to_string()),
             extension: Some(nft_metadata_msg.clone()),
@@ -2651,7 +2656,112 @@ fn test_queries() {
             collection_extension: None,
             num_tokens: 1,
             withdraw_address: Some(withdraw_addr.into_string()),
-            contract_info
+            contract_info,
+            max_supply: None,
+            total_minted: 1,
         }
     );
 }
+
+#[test]
+fn test_royalty_payouts() {
+    let mut app = new();
+    let admin = app.api().addr_make(ADMIN_ADDR);
+    let creator = app.api().addr_make(CREATOR_ADDR);
+    let payment_address = app.api().addr_make(OTHER1_ADDR);
+    let code_id_latest = app.store_code(cw721_base_latest_contract());
+    let instantiate_latest = |app: &mut MockApp, royalty_info: Option<RoyaltyInfoResponse>| {
+        let init_msg = Cw721InstantiateMsg {
+            name: "collection".to_string(),
+            symbol: "symbol".to_string(),
+            minter: None,
+            creator: None,
+            withdraw_address: None,
+            max_supply: None,
+            collection_info_extension: Some(CollectionExtensionMsg {
+                description: None,
+                image: None,
+                explicit_content: None,
+                external_link: None,
+                start_trading_time: None,
+                royalty_info,
+            }),
+        };
+        let cw721 = app
+            .instantiate_contract(
+                code_id_latest,
+                creator.clone(),
+                &init_msg,
+                &[],
+                "cw721-base",
+                None,
+            )
+            .unwrap();
+        EmptyCw721Helper::new(cw721)
+    };
+
+    // royalties of the collection are paid
+    let cw721 = instantiate_latest(
+        &mut app,
+        Some(RoyaltyInfoResponse {
+            payment_address: payment_address.to_string(),
+            share: Decimal::percent(10),
+        }),
+    );
+    app.execute_contract(
+        creator.clone(),
+        cw721.addr(),
+        &Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: Some("1".to_string()),
+            owner: creator.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let payouts = cw721
+        .royalty_payouts(app.api(), &app.wrap(), "1", Uint128::new(100))
+        .unwrap();
+    assert_eq!(payouts, vec![(payment_address, Uint128::new(10))]);
+
+    // query errors of collections requiring royalty payments are returned, e.g. unknown NFT
+    cw721
+        .royalty_payouts(app.api(), &app.wrap(), "2", Uint128::new(100))
+        .unwrap_err();
+
+    // collection not requiring royalty payments
+    let cw721 = instantiate_latest(&mut app, None);
+    let payouts = cw721
+        .royalty_payouts(app.api(), &app.wrap(), "1", Uint128::new(100))
+        .unwrap();
+    assert!(payouts.is_empty());
+
+    // collection not supporting CW2981 fails the probe and owes no royalties
+    {
+        use cw721_base_018 as v18;
+        let code_id_018 = app.store_code(cw721_base_018_contract());
+        let cw721 = app
+            .instantiate_contract(
+                code_id_018,
+                creator.clone(),
+                &v18::InstantiateMsg {
+                    name: "collection".to_string(),
+                    symbol: "symbol".to_string(),
+                    minter: creator.to_string(),
+                },
+                &[],
+                "cw721-base",
+                Some(admin.to_string()),
+            )
+            .unwrap();
+        let payouts = EmptyCw721Helper::new(cw721)
+            .royalty_payouts(app.api(), &app.wrap(), "1", Uint128::new(100))
+            .unwrap();
+        assert!(payouts.is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
         Cw721ExecuteMsg, Cw721InstantiateMsg, NftExtensionMsg, RoyaltyInfoResponse,
     },
     query::MAX_LIMIT,
+    receiver::{Cw721ReceiveMsg, ReceivedNft, ReceiverAllowlist},
     state::{
         NftExtension, Trait, CREATOR, MAX_COLLECTION_DESCRIPTION_LENGTH,
         MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
@@ -15,7 +16,7 @@ use crate::{
 };
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info},
-    Addr, Api, Decimal, Timestamp,
+    to_json_binary, Addr, Api, Decimal, Timestamp,
 };
 use cw2::ContractVersion;
 use cw_ownable::Action;
@@ -41,6 +42,7 @@ fn test_instantiation() {
                 creator: None,
                 minter: None,
                 withdraw_address: None,
+                max_supply: None,
             },
             "contract_name",
             "contract_version",
@@ -61,6 +63,7 @@ fn test_instantiation() {
                 creator: None,
                 minter: None,
                 withdraw_address: None,
+                max_supply: None,
             },
             "contract_name",
             "contract_version",
@@ -80,6 +83,7 @@ fn test_instantiation() {
                 minter: Some("minter".into()),
                 creator: Some("creator".into()),
                 withdraw_address: None,
+                max_supply: None,
             },
             "contract_name",
             "contract_version",
@@ -131,6 +135,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -159,6 +164,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -187,6 +193,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: None,
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -215,6 +222,7 @@ fn test_instantiation_with_proper_minter_and_creator() {
                     creator: None,
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -276,6 +284,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -322,6 +331,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -359,6 +369,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -396,6 +407,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -430,6 +442,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -466,6 +479,7 @@ fn test_instantiation_with_collection_info() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -529,6 +543,7 @@ fn test_collection_info_update() {
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -548,6 +563,7 @@ fn test_collection_info_update() {
             name: None,
             symbol: None,
             extension: Some(empty_extension_msg),
+            max_supply: None,
         };
         contract
             .execute(
@@ -586,6 +602,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         contract
             .execute(
@@ -635,6 +652,7 @@ fn test_collection_info_update() {
             name: None,
             symbol: None,
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let info_minter = mock_info(MINTER_ADDR, &[]);
         contract
@@ -704,6 +722,7 @@ fn test_collection_info_update() {
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -729,6 +748,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -761,6 +781,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -798,6 +819,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -833,6 +855,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -868,6 +891,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -905,6 +929,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -956,6 +981,7 @@ fn test_collection_info_update() {
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -981,6 +1007,7 @@ fn test_collection_info_update() {
             name: Some("new_collection_name".into()),
             symbol: Some("new_collection_symbol".into()),
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let info_other = mock_info(OTHER1_ADDR, &[]);
         let err = contract
@@ -1073,6 +1100,7 @@ fn test_collection_info_update() {
                     creator: None, // in case of none, sender is creator
                     minter: info_minter.sender.to_string().into(),
                     withdraw_address: None,
+                    max_supply: None,
                 },
                 "contract_name",
                 "contract_version",
@@ -1092,6 +1120,7 @@ fn test_collection_info_update() {
             name: None,
             symbol: None,
             extension: Some(updated_extension_msg),
+            max_supply: None,
         };
         let err = contract
             .execute(
@@ -1141,6 +1170,7 @@ fn test_nft_mint() {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         let env = mock_env();
         contract
@@ -1157,7 +1187,7 @@ fn test_nft_mint() {
         let token_id = "Enterprise";
         let token_uri = Some("https://starships.example.com/Starship/Enterprise.json".into());
         let exec_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: None,
@@ -1173,7 +1203,7 @@ fn test_nft_mint() {
         assert_eq!(res.extension, None);
         // mint with empty token_uri
         let exec_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.to_string(), // already minted/claimed
+            token_id: Some(token_id.to_string()), // already minted/claimed
             owner: "john".to_string(),
             token_uri: "".to_string().into(), // empty token_uri
             extension: None,
@@ -1185,7 +1215,7 @@ fn test_nft_mint() {
         // non-minter cant mint
         let info = mock_info("john", &[]);
         let exec_msg = Cw721ExecuteMsg::Mint {
-            token_id: "Enterprise".to_string(),
+            token_id: Some("Enterprise".to_string()),
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: None,
@@ -1208,6 +1238,7 @@ fn test_nft_mint() {
             minter: None,
             creator: None,
             withdraw_address: None,
+            max_supply: None,
         };
         let env = mock_env();
         contract
@@ -1234,7 +1265,7 @@ fn test_nft_mint() {
             ..NftExtensionMsg::default()
         });
         let exec_msg = Cw721ExecuteMsg::Mint {
-            token_id: nft_1.to_string(),
+            token_id: Some(nft_1.to_string()),
             owner: "john".to_string(),
             token_uri: uri_1.clone(),
             extension: extension_1_msg.clone(),
@@ -1275,7 +1306,7 @@ fn test_nft_mint() {
             ..NftExtensionMsg::default()
         });
         let exec_msg = Cw721ExecuteMsg::Mint {
-            token_id: nft_2.to_string(),
+            token_id: Some(nft_2.to_string()),
             owner: "allen".to_string(),
             token_uri: uri_2.clone(),
             extension: extension_2_msg.clone(),
@@ -1367,7 +1398,7 @@ fn test_migrate_v16_onchain_metadata_contract() {
     for i in 0..200 {
         let info = mock_info("legacy_minter", &[]);
         let msg = v16::ExecuteMsg::Mint(v16::MintMsg {
-            token_id: i.to_string(),
+            token_id: Some(i.to_string()),
             owner: "owner".into(),
             token_uri: None,
             extension: Some(v16::Metadata {
@@ -1524,3 +1555,63 @@ fn test_migrate_v16_onchain_metadata_contract() {
         assert_eq!(token.owner.as_str(), "owner");
     }
 }
+
+#[test]
+fn test_receiver_allowlist() {
+    let mut deps = mock_dependencies();
+    let allowlist = ReceiverAllowlist::new("allowlist");
+    let collection = deps.api.addr_make("collection");
+    let other_collection = deps.api.addr_make("other_collection");
+    let owner = deps.api.addr_make("owner");
+    allowlist.allow(deps.as_mut().storage, &collection).unwrap();
+
+    let receive_msg = Cw721ReceiveMsg {
+        sender: owner.to_string(),
+        token_id: "1".to_string(),
+        msg: to_json_binary("payload").unwrap(),
+    };
+
+    // collection not in allowlist
+    let info = mock_info(other_collection.as_str(), &[]);
+    let err = receive_msg
+        .clone()
+        .into_received::<String>(deps.as_ref(), &info, Some(&allowlist))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::CollectionNotAllowed {
+            collection: other_collection.to_string()
+        }
+    );
+
+    // any collection accepted without allowlist
+    let received = receive_msg
+        .clone()
+        .into_received::<String>(deps.as_ref(), &info, None)
+        .unwrap();
+    assert_eq!(received.collection, other_collection);
+
+    // payload is decoded
+    let info = mock_info(collection.as_str(), &[]);
+    let received = receive_msg
+        .into_received::<String>(deps.as_ref(), &info, Some(&allowlist))
+        .unwrap();
+    assert_eq!(
+        received,
+        ReceivedNft {
+            collection: collection.clone(),
+            sender: owner.clone(),
+            token_id: "1".to_string(),
+            msg: "payload".to_string(),
+        }
+    );
+
+    allowlist.disallow(deps.as_mut().storage, &collection);
+    assert!(!allowlist.is_allowed(deps.as_ref().storage, &collection));
+    assert_eq!(
+        allowlist
+            .collections(deps.as_ref().storage, None, None)
+            .unwrap(),
+        Vec::<Addr>::new()
+    );
+}
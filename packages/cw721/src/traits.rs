@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use cosmwasm_std::{
     to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, CustomMsg, Deps, DepsMut, Empty, Env,
-    MessageInfo, QuerierWrapper, Response, StdResult, Storage, WasmMsg, WasmQuery,
+    MessageInfo, QuerierWrapper, Response, StdResult, Storage, Uint128, WasmMsg, WasmQuery,
 };
 use cw_ownable::{Action, Ownership};
 use cw_utils::Expiration;
@@ -13,30 +13,37 @@ use serde::{de::DeserializeOwned, Serialize};
 use crate::{
     error::Cw721ContractError,
     execute::{
-        approve, approve_all, burn_nft, initialize_creator, initialize_minter, instantiate,
-        instantiate_with_version, migrate, mint, remove_withdraw_address, revoke, revoke_all,
-        send_nft, set_withdraw_address, transfer_nft, update_collection_info,
-        update_creator_ownership, update_minter_ownership, update_nft_info, withdraw_funds,
+        approve, approve_all, batch_burn_nft, batch_mint, batch_send_nft, batch_transfer_nft,
+        burn_nft, initialize_creator, initialize_minter, instantiate, instantiate_with_version,
+        migrate, mint, remove_withdraw_address, revoke, revoke_all, send_nft,
+        set_collection_royalty_split, set_provenance_tracking, set_token_id_sequence,
+        set_token_royalty, set_transfer_policy, set_withdraw_address, transfer_nft,
+        update_collection_info, update_creator_ownership, update_minter_ownership, update_nft_info,
+        withdraw_funds,
     },
     msg::{
-        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
+        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, CheckRoyaltiesResponse,
         CollectionInfoAndExtensionResponse, CollectionInfoMsg, Cw721ExecuteMsg,
-        Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg, MintMsg, MinterResponse,
+        NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
+        RoyaltiesInfoResponse, RoyaltySplitInfoResponse, RoyaltySplitMsg, TokenHistoryResponse,
+        TokensResponse, TradingStatusResponse, TransferMsg,
     },
     query::{
         query_all_nft_info, query_all_tokens, query_approval, query_approvals,
         query_collection_extension_attributes, query_collection_info,
         query_collection_info_and_extension, query_creator_ownership, query_minter,
         query_minter_ownership, query_nft_info, query_num_tokens, query_operator, query_operators,
-        query_owner_of, query_tokens, query_withdraw_address,
+        query_owner_of, query_royalty_info, query_royalty_split_info, query_token_history,
+        query_token_id_sequence, query_tokens, query_trading_status, query_transfer_policy,
+        query_withdraw_address,
     },
-    state::CollectionInfo,
+    state::{CollectionInfo, ProvenanceEntry, TokenIdSequence, TransferPolicy},
     Attribute,
 };
 use crate::{
     msg::{AllInfoResponse, ConfigResponse},
-    query::{query_all_info, query_config, query_nft_by_extension},
+    query::{query_all_info, query_check_royalties, query_config, query_nft_by_extension},
     Approval,
 };
 
@@ -218,6 +225,18 @@ pub trait Cw721Execute<
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
             Cw721ExecuteMsg::Burn { token_id } => self.burn_nft(deps, env, info, token_id),
+            Cw721ExecuteMsg::BatchMint { mints } => self.batch_mint(deps, env, info, mints),
+            Cw721ExecuteMsg::BatchTransfer { transfers } => {
+                self.batch_transfer_nft(deps, env, info, transfers)
+            }
+            Cw721ExecuteMsg::BatchSend {
+                contract,
+                token_ids,
+                msg,
+            } => self.batch_send_nft(deps, env, info, contract, token_ids, msg),
+            Cw721ExecuteMsg::BatchBurn { token_ids } => {
+                self.batch_burn_nft(deps, env, info, token_ids)
+            }
             #[allow(deprecated)]
             Cw721ExecuteMsg::UpdateOwnership(action) => {
                 self.update_minter_ownership(deps.api, deps.storage, env, info, action)
@@ -237,6 +256,22 @@ pub trait Cw721Execute<
                 token_uri,
                 extension,
             } => self.update_nft_info(deps, env, info, token_id, token_uri, extension),
+            Cw721ExecuteMsg::SetTokenRoyalty {
+                token_id,
+                royalty_split,
+            } => self.set_token_royalty(deps, env, info, token_id, royalty_split),
+            Cw721ExecuteMsg::SetCollectionRoyaltySplit { royalty_split } => {
+                self.set_collection_royalty_split(deps, env, info, royalty_split)
+            }
+            Cw721ExecuteMsg::SetProvenanceTracking { enabled } => {
+                self.set_provenance_tracking(deps, &info.sender, enabled)
+            }
+            Cw721ExecuteMsg::SetTransferPolicy { transfer_policy } => {
+                self.set_transfer_policy(deps, &info.sender, transfer_policy)
+            }
+            Cw721ExecuteMsg::SetTokenIdSequence { token_id_sequence } => {
+                self.set_token_id_sequence(deps, &info.sender, token_id_sequence)
+            }
             Cw721ExecuteMsg::SetWithdrawAddress { address } => {
                 self.set_withdraw_address(deps, &info.sender, address)
             }
@@ -342,6 +377,40 @@ pub trait Cw721Execute<
         burn_nft::<TCustomResponseMsg>(deps, env, info, token_id)
     }
 
+    fn batch_transfer_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        transfers: Vec<TransferMsg>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        batch_transfer_nft::<TNftExtension, TCustomResponseMsg>(deps, env, info, transfers)
+    }
+
+    fn batch_send_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        contract: String,
+        token_ids: Vec<String>,
+        msg: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        batch_send_nft::<TNftExtension, TCustomResponseMsg>(
+            deps, env, info, contract, token_ids, msg,
+        )
+    }
+
+    fn batch_burn_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        batch_burn_nft::<TCustomResponseMsg>(deps, env, info, token_ids)
+    }
+
     // ------- opionated cw721 functions -------
     fn initialize_creator(
         &self,
@@ -379,7 +448,7 @@ pub trait Cw721Execute<
         deps: DepsMut,
         env: &Env,
         info: &MessageInfo,
-        token_id: String,
+        token_id: Option<String>,
         owner: String,
         token_uri: Option<String>,
         extension: TNftExtensionMsg,
@@ -389,6 +458,16 @@ pub trait Cw721Execute<
         )
     }
 
+    fn batch_mint(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        mints: Vec<MintMsg<TNftExtensionMsg>>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        batch_mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(deps, env, info, mints)
+    }
+
     fn update_minter_ownership(
         &self,
         api: &dyn Api,
@@ -443,6 +522,54 @@ pub trait Cw721Execute<
         )
     }
 
+    fn set_token_royalty(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        royalty_split: Option<RoyaltySplitMsg>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_token_royalty::<TCustomResponseMsg>(deps, env, info, token_id, royalty_split)
+    }
+
+    fn set_collection_royalty_split(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        royalty_split: Option<RoyaltySplitMsg>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_collection_royalty_split::<TCustomResponseMsg>(deps, env, info, royalty_split)
+    }
+
+    fn set_provenance_tracking(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        enabled: bool,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_provenance_tracking::<TCustomResponseMsg>(deps, sender, enabled)
+    }
+
+    fn set_transfer_policy(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        transfer_policy: TransferPolicy,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_transfer_policy::<TCustomResponseMsg>(deps, sender, transfer_policy)
+    }
+
+    fn set_token_id_sequence(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        token_id_sequence: Option<TokenIdSequence>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_token_id_sequence::<TCustomResponseMsg>(deps, sender, token_id_sequence)
+    }
+
     fn set_withdraw_address(
         &self,
         deps: DepsMut,
@@ -614,6 +741,40 @@ pub trait Cw721Query<
             Cw721QueryMsg::GetWithdrawAddress {} => {
                 Ok(to_json_binary(&self.query_withdraw_address(deps)?)?)
             }
+            Cw721QueryMsg::GetTradingStatus {} => {
+                Ok(to_json_binary(&self.query_trading_status(deps, env)?)?)
+            }
+            Cw721QueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => Ok(to_json_binary(
+                &self.query_royalty_info(deps, token_id, sale_price)?,
+            )?),
+            Cw721QueryMsg::RoyaltySplitInfo {
+                token_id,
+                sale_price,
+            } => Ok(to_json_binary(
+                &self.query_royalty_split_info(deps, token_id, sale_price)?,
+            )?),
+            Cw721QueryMsg::CheckRoyalties {} => {
+                Ok(to_json_binary(&self.query_check_royalties(deps)?)?)
+            }
+            Cw721QueryMsg::GetTransferPolicy {} => {
+                Ok(to_json_binary(&self.query_transfer_policy(deps)?)?)
+            }
+            Cw721QueryMsg::GetTokenIdSequence {} => {
+                Ok(to_json_binary(&self.query_token_id_sequence(deps)?)?)
+            }
+            Cw721QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(&self.query_token_history(
+                deps,
+                token_id,
+                start_after,
+                limit,
+            )?)?),
         }
     }
 
@@ -808,6 +969,57 @@ pub trait Cw721Query<
     fn query_withdraw_address(&self, deps: Deps) -> StdResult<Option<String>> {
         query_withdraw_address(deps)
     }
+
+    fn query_trading_status(
+        &self,
+        deps: Deps,
+        env: &Env,
+    ) -> Result<TradingStatusResponse, Cw721ContractError> {
+        query_trading_status(deps, env)
+    }
+
+    fn query_royalty_info(
+        &self,
+        deps: Deps,
+        token_id: String,
+        sale_price: Uint128,
+    ) -> Result<RoyaltiesInfoResponse, Cw721ContractError> {
+        query_royalty_info(deps, token_id, sale_price)
+    }
+
+    fn query_royalty_split_info(
+        &self,
+        deps: Deps,
+        token_id: String,
+        sale_price: Uint128,
+    ) -> Result<RoyaltySplitInfoResponse, Cw721ContractError> {
+        query_royalty_split_info(deps, token_id, sale_price)
+    }
+
+    fn query_check_royalties(
+        &self,
+        deps: Deps,
+    ) -> Result<CheckRoyaltiesResponse, Cw721ContractError> {
+        query_check_royalties(deps)
+    }
+
+    fn query_transfer_policy(&self, deps: Deps) -> StdResult<TransferPolicy> {
+        query_transfer_policy(deps)
+    }
+
+    fn query_token_id_sequence(&self, deps: Deps) -> StdResult<Option<TokenIdSequence>> {
+        query_token_id_sequence(deps)
+    }
+
+    fn query_token_history(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TokenHistoryResponse> {
+        query_token_history(deps, token_id, start_after, limit)
+    }
 }
 
 /// Generic trait with onchain nft and collection extensions used to call query and execute messages for a given CW721 addr.
@@ -857,6 +1069,32 @@ pub trait Cw721Calls<
         querier.query(&query)
     }
 
+    /*** executes ***/
+    fn batch_mint(&self, mints: Vec<MintMsg<TNftExtensionMsg>>) -> StdResult<CosmosMsg> {
+        self.call(Cw721ExecuteMsg::BatchMint { mints })
+    }
+
+    fn batch_transfer(&self, transfers: Vec<TransferMsg>) -> StdResult<CosmosMsg> {
+        self.call(Cw721ExecuteMsg::BatchTransfer { transfers })
+    }
+
+    fn batch_send<T: Into<String>>(
+        &self,
+        contract: T,
+        token_ids: Vec<String>,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw721ExecuteMsg::BatchSend {
+            contract: contract.into(),
+            token_ids,
+            msg,
+        })
+    }
+
+    fn batch_burn(&self, token_ids: Vec<String>) -> StdResult<CosmosMsg> {
+        self.call(Cw721ExecuteMsg::BatchBurn { token_ids })
+    }
+
     /*** queries ***/
     fn owner_of<T: Into<String>>(
         &self,
@@ -934,6 +1172,105 @@ pub trait Cw721Calls<
         self.query(querier, req)
     }
 
+    /// Returns whether owners can already transfer, send and approve NFTs
+    fn trading_status(&self, querier: &QuerierWrapper) -> StdResult<TradingStatusResponse> {
+        let req = Cw721QueryMsg::GetTradingStatus {};
+        self.query(querier, req)
+    }
+
+    /// Returns the CW2981 royalty owed for the given NFT and sale price
+    fn royalty_info<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        sale_price: Uint128,
+    ) -> StdResult<RoyaltiesInfoResponse> {
+        let req = Cw721QueryMsg::RoyaltyInfo {
+            token_id: token_id.into(),
+            sale_price,
+        };
+        self.query(querier, req)
+    }
+
+    /// Returns the royalty owed to each recipient for the given NFT and sale price
+    fn royalty_split_info<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        sale_price: Uint128,
+    ) -> StdResult<Vec<RoyaltiesInfoResponse>> {
+        let req = Cw721QueryMsg::RoyaltySplitInfo {
+            token_id: token_id.into(),
+            sale_price,
+        };
+        let res: RoyaltySplitInfoResponse = self.query(querier, req)?;
+        Ok(res.royalties)
+    }
+
+    /// Returns the royalty to be paid to each recipient for the given NFT and sale price.
+    /// Collections are probed via `CheckRoyalties`: collections not supporting CW2981, or not
+    /// requiring royalty payments, owe no royalties. Falls back to the CW2981 `RoyaltyInfo` query
+    /// for collections not supporting royalty splits (e.g. cw2981-royalties), its error is returned.
+    /// Royalties without address or amount are skipped.
+    fn royalty_payouts<T: Into<String>>(
+        &self,
+        api: &dyn Api,
+        querier: &QuerierWrapper,
+        token_id: T,
+        sale_price: Uint128,
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        if !self.check_royalties(querier).unwrap_or(false) {
+            return Ok(vec![]);
+        }
+        let token_id: String = token_id.into();
+        let royalties = match self.royalty_split_info(querier, &token_id, sale_price) {
+            Ok(royalties) => royalties,
+            Err(_) => vec![self.royalty_info(querier, &token_id, sale_price)?],
+        };
+        royalties
+            .into_iter()
+            // an empty address means no royalties are owed
+            .filter(|royalty| !royalty.address.is_empty() && !royalty.royalty_amount.is_zero())
+            .map(|royalty| Ok((api.addr_validate(&royalty.address)?, royalty.royalty_amount)))
+            .collect()
+    }
+
+    /// Returns whether royalties should be checked on sale
+    fn check_royalties(&self, querier: &QuerierWrapper) -> StdResult<bool> {
+        let req = Cw721QueryMsg::CheckRoyalties {};
+        let res: CheckRoyaltiesResponse = self.query(querier, req)?;
+        Ok(res.royalty_payments)
+    }
+
+    /// Returns who is eligible to transfer and send NFTs
+    fn transfer_policy(&self, querier: &QuerierWrapper) -> StdResult<TransferPolicy> {
+        let req = Cw721QueryMsg::GetTransferPolicy {};
+        self.query(querier, req)
+    }
+
+    /// Returns the sequence of token ids assigned on mint, if set
+    fn token_id_sequence(&self, querier: &QuerierWrapper) -> StdResult<Option<TokenIdSequence>> {
+        let req = Cw721QueryMsg::GetTokenIdSequence {};
+        self.query(querier, req)
+    }
+
+    /// Returns the recorded ownership history of an NFT, oldest first
+    fn token_history<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ProvenanceEntry>> {
+        let req = Cw721QueryMsg::TokenHistory {
+            token_id: token_id.into(),
+            start_after,
+            limit,
+        };
+        let res: TokenHistoryResponse = self.query(querier, req)?;
+        Ok(res.history)
+    }
+
     /// This is a helper to get the metadata and extension data in one call
     fn collection_info<U: DeserializeOwned>(
         &self,